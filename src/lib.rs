@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map,
-    Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
 };
 
 /// Centralized contract error codes. Auth failures are signaled by host panic (require_auth).
@@ -11,7 +11,8 @@ use soroban_sdk::{
 pub enum RevoraError {
     /// revenue_share_bps exceeded 10000 (100%).
     InvalidRevenueShareBps = 1,
-    /// Reserved for future use (e.g. offering limit per issuer).
+    /// Issuer reached its offering limit. Also returned by legacy setters when the
+    /// offering or admin is missing.
     LimitReached = 2,
     /// Holder concentration exceeds configured limit and enforcement is enabled.
     ConcentrationLimitExceeded = 3,
@@ -31,6 +32,84 @@ pub enum RevoraError {
     ContractFrozen = 10,
     /// Revenue for this period is not yet claimable (delay not elapsed).
     ClaimDelayNotElapsed = 11,
+    /// Reporting or release schedule parameters are invalid (e.g. zero-length period,
+    /// tranches not summing to 10000 bps).
+    InvalidSchedule = 12,
+    /// Operation is not allowed in the offering's current status.
+    InvalidOfferingStatus = 13,
+    /// Reports are still missing for periods whose grace window has passed, or a report
+    /// skipped a period under `ZeroReportPolicy::Required`.
+    MissedReportsOutstanding = 14,
+    /// Withholding rate exceeded 10000 bps (100%).
+    InvalidWithholdingBps = 15,
+    /// No recipient address is configured for the requested remittance.
+    RecipientNotSet = 16,
+    /// Timelocked admin operation not found.
+    AdminOpNotFound = 17,
+    /// Timelock not elapsed (queued admin operation, token migration, or slash appeal window).
+    TimelockNotElapsed = 18,
+    /// The installed code is newer than the recorded storage layout; state changes wait
+    /// for `post_upgrade`.
+    MigrationPending = 19,
+    /// `post_upgrade` called without a matching older recorded version, or a token migration
+    /// target is already registered or does not match the proposal.
+    InvalidMigration = 20,
+    /// Registration is permissioned and the issuer is not on the allowlist.
+    IssuerNotAllowed = 21,
+    /// Amount must be positive (or non-negative where zero is meaningful).
+    InvalidAmount = 22,
+    /// Caller is neither the offering's issuer nor its designated operator.
+    Unauthorized = 23,
+    /// Merkle proof does not resolve to the committed whitelist root.
+    InvalidProof = 24,
+    /// Bulk import chunk out of order, oversized, or inconsistent with the import in progress.
+    InvalidChunk = 25,
+    /// Investor fails an eligibility rule other than the blacklist (see `is_eligible`).
+    NotEligible = 26,
+    /// No revenue report exists for the period.
+    ReportNotFound = 27,
+    /// Period is finalized; its report can no longer change.
+    PeriodFinalized = 28,
+    /// Offering requires an auditor attestation of the current amount before finalization.
+    AttestationRequired = 29,
+    /// Price oracle returned a non-positive rate.
+    InvalidOraclePrice = 30,
+    /// Report deductions exceed the offering's maximum share of gross revenue.
+    DeductionCapExceeded = 31,
+    /// No dispute (or slash) exists for the offering period.
+    DisputeNotFound = 32,
+    /// Dispute or slash is not in a state that allows this operation.
+    InvalidDisputeState = 33,
+    /// Available balance (pool, escrow or bond) is too small for the requested amount.
+    InsufficientFunds = 34,
+    /// Operation attempted outside its permitted time window.
+    WindowClosed = 35,
+    /// No term amendment with the given id, or no pending token migration.
+    ProposalNotFound = 36,
+    /// Investor already voted on this amendment.
+    AlreadyVoted = 37,
+    /// Period is not in the state the operation requires.
+    InvalidPeriodState = 38,
+    /// Admitting the investor would exceed the offering's investor cap.
+    InvestorCapReached = 39,
+    /// Reporter set is empty, too large, has duplicates or an out-of-range threshold.
+    InvalidReporterSet = 40,
+    /// Cross-chain message was already recorded for the offering.
+    MessageReplayed = 41,
+    /// Period is below the offering's highest finalized period and was not reopened by a ruling.
+    StalePeriod = 42,
+    /// Offering escrow still holds undistributed funds.
+    EscrowOutstanding = 43,
+    /// Period is frozen pending audit; its claims and refunds are blocked.
+    PeriodFrozen = 44,
+    /// Engine sequence number was not above its last used one (replayed or reordered call).
+    StaleSequence = 45,
+    /// Token is already registered as an offering and is not Closed.
+    OfferingAlreadyExists = 46,
+    /// Reported period has not started under the offering's reporting schedule.
+    FuturePeriod = 47,
+    /// A holder list passed to a merge left out a holder with a non-zero share.
+    HoldersIncomplete = 48,
 }
 
 // ── Event symbols ────────────────────────────────────────────
@@ -50,13 +129,346 @@ const EVENT_TESTNET_MODE: Symbol = symbol_short!("test_mode");
 const EVENT_INIT: Symbol = symbol_short!("init");
 const EVENT_PAUSED: Symbol = symbol_short!("paused");
 const EVENT_UNPAUSED: Symbol = symbol_short!("unpaused");
+const EVENT_SCHEDULE_SET: Symbol = symbol_short!("sched_set");
+const EVENT_REPORT_LATE: Symbol = symbol_short!("rev_late");
+const EVENT_SUSPENSION_SET: Symbol = symbol_short!("susp_set");
+const EVENT_STATUS_CHANGED: Symbol = symbol_short!("off_stat");
+const EVENT_DELINQUENT: Symbol = symbol_short!("delinq");
+const EVENT_WITHHOLDING_SET: Symbol = symbol_short!("wh_set");
+const EVENT_JURISDICTION_SET: Symbol = symbol_short!("juris_set");
+const EVENT_WH_RECIPIENT_SET: Symbol = symbol_short!("wh_rcpt");
+const EVENT_WH_REMITTED: Symbol = symbol_short!("wh_remit");
+const EVENT_ADMIN_OP_PROPOSED: Symbol = symbol_short!("adm_prop");
+const EVENT_ADMIN_OP_EXECUTED: Symbol = symbol_short!("adm_exec");
+const EVENT_ADMIN_OP_CANCELLED: Symbol = symbol_short!("adm_cncl");
+const EVENT_UPGRADED: Symbol = symbol_short!("upgraded");
+const EVENT_MIGRATED: Symbol = symbol_short!("migrated");
+const EVENT_OFFERING_LIMIT_SET: Symbol = symbol_short!("limit_set");
+const EVENT_ISSUER_MODE: Symbol = symbol_short!("iss_mode");
+const EVENT_ISSUER_ALLOW: Symbol = symbol_short!("iss_allow");
+const EVENT_TREASURY_SET: Symbol = symbol_short!("treas_set");
+const EVENT_FEE_SET: Symbol = symbol_short!("fee_set");
+const EVENT_FEE_WAIVER: Symbol = symbol_short!("fee_waive");
+const EVENT_FEE_PAID: Symbol = symbol_short!("fee_paid");
+const EVENT_OPERATOR_SET: Symbol = symbol_short!("oper_set");
+const EVENT_WL_ROOT: Symbol = symbol_short!("wl_root");
+const EVENT_WL_CLAIM: Symbol = symbol_short!("wl_claim");
+const EVENT_BL_IMPORT: Symbol = symbol_short!("bl_import");
+const EVENT_COMPLIANCE_SET: Symbol = symbol_short!("compl_set");
+const EVENT_COMPLIANCE_MODULE: Symbol = symbol_short!("compl_mod");
+const EVENT_STRATEGY_SET: Symbol = symbol_short!("strat_set");
+const EVENT_REPORT_FINALIZED: Symbol = symbol_short!("rep_final");
+const EVENT_LISTENER_ADDED: Symbol = symbol_short!("lsn_add");
+const EVENT_LISTENER_REMOVED: Symbol = symbol_short!("lsn_rem");
+const EVENT_REVENUE_REPORT_PARTIAL: Symbol = symbol_short!("rev_part");
+const EVENT_AUDITOR_SET: Symbol = symbol_short!("aud_set");
+const EVENT_REPORT_ATTESTED: Symbol = symbol_short!("attested");
+const EVENT_ACCOUNTING_KEY_SET: Symbol = symbol_short!("acct_key");
+const EVENT_SIGNED_ATTESTATION: Symbol = symbol_short!("sig_att");
+const EVENT_FIAT_CONFIG_SET: Symbol = symbol_short!("fiat_set");
+const EVENT_DEDUCTION_CAP_SET: Symbol = symbol_short!("ded_cap");
+const EVENT_REVIEW_FLAGGED: Symbol = symbol_short!("review");
+const EVENT_BOND_CONFIG_SET: Symbol = symbol_short!("bond_set");
+const EVENT_BOND_POSTED: Symbol = symbol_short!("bond_post");
+const EVENT_ROLE_SET: Symbol = symbol_short!("role_set");
+const EVENT_DISPUTE_OPENED: Symbol = symbol_short!("disp_open");
+const EVENT_DISPUTE_RESOLVED: Symbol = symbol_short!("disp_res");
+const EVENT_BOND_SLASHED: Symbol = symbol_short!("slashed");
+const EVENT_SLASH_REVERSED: Symbol = symbol_short!("slash_rev");
+const EVENT_SLASH_EXECUTED: Symbol = symbol_short!("slash_exe");
+const EVENT_APPEAL_WINDOW_SET: Symbol = symbol_short!("appl_win");
+const EVENT_INSURANCE_BPS_SET: Symbol = symbol_short!("ins_bps");
+const EVENT_INSURANCE_FUNDED: Symbol = symbol_short!("ins_fund");
+const EVENT_INSURANCE_PAID: Symbol = symbol_short!("ins_comp");
+const EVENT_NFT_CLASS_SET: Symbol = symbol_short!("nft_class");
+const EVENT_NFT_SNAPSHOT: Symbol = symbol_short!("nft_snap");
+const EVENT_NFT_CLAIM: Symbol = symbol_short!("nft_claim");
+const EVENT_CLASS_SHARE_SET: Symbol = symbol_short!("cls_share");
+const EVENT_CONVERSION_SET: Symbol = symbol_short!("conv_set");
+const EVENT_CLASS_CONVERTED: Symbol = symbol_short!("cls_conv");
+const EVENT_FORCED_TRANSFER: Symbol = symbol_short!("forced_tx");
+const EVENT_CLAWBACK: Symbol = symbol_short!("clawback");
+const EVENT_ACCRUAL_MODE_SET: Symbol = symbol_short!("acc_mode");
+const EVENT_ACCRUED_CLAIM: Symbol = symbol_short!("acc_claim");
+const EVENT_STAKE: Symbol = symbol_short!("stake");
+const EVENT_UNSTAKE: Symbol = symbol_short!("unstake");
+const EVENT_COOLDOWN_SET: Symbol = symbol_short!("cooldown");
+const EVENT_UNSTAKE_REQ: Symbol = symbol_short!("unstk_req");
+const EVENT_UNSTAKE_WD: Symbol = symbol_short!("unstk_wd");
+const EVENT_MANAGER_SET: Symbol = symbol_short!("mgr_set");
+const EVENT_PAYOUT_ADDR_SET: Symbol = symbol_short!("payout_to");
+const EVENT_AMEND_PROPOSED: Symbol = symbol_short!("amend_new");
+const EVENT_AMEND_VOTE: Symbol = symbol_short!("amend_vot");
+const EVENT_AMEND_APPLIED: Symbol = symbol_short!("amend_app");
+const EVENT_AMEND_REJECTED: Symbol = symbol_short!("amend_rej");
+const EVENT_ARBITER_SET: Symbol = symbol_short!("arb_set");
+const EVENT_RULING: Symbol = symbol_short!("ruling");
+const EVENT_PENALTY_TERMS_SET: Symbol = symbol_short!("pen_terms");
+const EVENT_PENALTY_PAID: Symbol = symbol_short!("penalty");
+const EVENT_PERIOD_STATE: Symbol = symbol_short!("per_state");
+const EVENT_ESCROW_REFUND: Symbol = symbol_short!("esc_rfnd");
+const EVENT_FINALIZED_SHORT: Symbol = symbol_short!("fin_short");
+const EVENT_FUNDING_SOURCE_SET: Symbol = symbol_short!("fund_set");
+const EVENT_PAYOUT_RECORDED: Symbol = symbol_short!("payout");
+const EVENT_ENGINE_ACK: Symbol = symbol_short!("eng_ack");
+const EVENT_ENGINE_STALE: Symbol = symbol_short!("eng_stale");
+const EVENT_PAYOUT_ROOT: Symbol = symbol_short!("pay_root");
+const EVENT_PAYOUT_CHALLENGED: Symbol = symbol_short!("pay_chal");
+const EVENT_PAYOUT_CHALLENGE_RESOLVED: Symbol = symbol_short!("chal_res");
+const EVENT_COMPLAINT_FILED: Symbol = symbol_short!("complaint");
+const EVENT_COMPLAINT_CLOSED: Symbol = symbol_short!("cmp_close");
+const EVENT_TOKENS_RESCUED: Symbol = symbol_short!("rescue");
+const EVENT_ADMIN_SET: Symbol = symbol_short!("admin_set");
+const EVENT_CONC_LIMIT_SET: Symbol = symbol_short!("conc_set");
+const EVENT_ROUNDING_SET: Symbol = symbol_short!("rnd_set");
+const EVENT_STALE_SECS_SET: Symbol = symbol_short!("stale_set");
+const EVENT_REPORTERS_SET: Symbol = symbol_short!("rptrs_set");
+const EVENT_REPORT_VOTE: Symbol = symbol_short!("rep_vote");
+const EVENT_REPORTER_ROTATION: Symbol = symbol_short!("rptr_rot");
+const EVENT_CHECKPOINT: Symbol = symbol_short!("checkpt");
+const EVENT_CHILD_WASM_SET: Symbol = symbol_short!("child_set");
+const EVENT_CHILD_DEPLOYED: Symbol = symbol_short!("child_dep");
+const EVENT_BRIDGE_SET: Symbol = symbol_short!("brdg_set");
+const EVENT_BRIDGE_REPORT: Symbol = symbol_short!("rev_brdg");
+const EVENT_VERBOSITY_DEFAULT_SET: Symbol = symbol_short!("verb_dflt");
+const EVENT_VERBOSITY_SET: Symbol = symbol_short!("verb_set");
+const EVENT_ZERO_POLICY_SET: Symbol = symbol_short!("zero_set");
+const EVENT_OFFERING_CLOSED: Symbol = symbol_short!("offer_cls");
+const EVENT_MIGRATION_PROPOSED: Symbol = symbol_short!("mig_prop");
+const EVENT_MIGRATION_CANCELLED: Symbol = symbol_short!("mig_cncl");
+const EVENT_TOKEN_MIGRATED: Symbol = symbol_short!("tok_mig");
+const EVENT_HOLDERS_MIGRATED: Symbol = symbol_short!("mig_hold");
+const EVENT_OFFERINGS_MERGED: Symbol = symbol_short!("off_merge");
+const EVENT_OFFERING_SPLIT: Symbol = symbol_short!("off_split");
+const EVENT_PERIOD_FROZEN: Symbol = symbol_short!("prd_frz");
+const EVENT_PERIOD_UNFROZEN: Symbol = symbol_short!("prd_unfrz");
+const EVENT_RELEASE_SET: Symbol = symbol_short!("rel_set");
+const EVENT_SHORTFALL_RATE_SET: Symbol = symbol_short!("sf_rate");
+const EVENT_SHORTFALL_FUNDED: Symbol = symbol_short!("sf_fund");
+
+/// Event catalog: (event symbol, topic names after the symbol, data field names), comma
+/// separated. Served by `get_event_catalog`.
+#[rustfmt::skip]
+const EVENT_CATALOG: &[(&str, &str, &str)] = &[
+    ("offer_reg", "issuer,offering_id", "token,revenue_share_bps"),
+    ("rev_rep", "issuer,token,offering_id", "amount,period_id,blacklist_vec"),
+    ("bl_add", "token,caller,offering_id", "investor"),
+    ("bl_rem", "token,caller,offering_id", "investor"),
+    ("conc_warn", "issuer,token,offering_id", "concentration_bps,limit_bps"),
+    ("test_mode", "admin", "enabled"),
+    ("sched_set", "issuer,token,offering_id", "start_time,period_secs,grace_secs"),
+    ("rev_late", "issuer,token,offering_id", "period_id,deadline"),
+    ("susp_set", "issuer,token,offering_id", "max_missed_periods"),
+    ("off_stat", "token,offering_id", "previous_status,new_status"),
+    ("delinq", "token,kind,offering_id", "missed_periods,period_id"),
+    ("wh_set", "issuer,token,offering_id", "jurisdiction,bps"),
+    ("juris_set", "issuer,token,offering_id", "investor,jurisdiction"),
+    ("claim", "holder,token,offering_id", "net_payout,claimed_periods,withheld"),
+    ("wh_rcpt", "issuer,token,offering_id", "jurisdiction_code,recipient"),
+    ("wh_remit", "token,jurisdiction_code,offering_id", "recipient,payment_token,amount"),
+    ("adm_prop", "admin", "op_id,kind,eta"),
+    ("adm_exec", "admin", "op_id,kind"),
+    ("adm_cncl", "admin", "op_id,kind"),
+    ("upgraded", "admin", "new_wasm_hash,from_version"),
+    ("migrated", "admin", "from_version,to_version"),
+    ("limit_set", "admin", "issuer,max"),
+    ("iss_mode", "admin", "enabled"),
+    ("iss_allow", "admin,issuer", "allowed"),
+    ("treas_set", "admin", "treasury"),
+    ("fee_set", "admin", "fee_token,amount"),
+    ("fee_waive", "admin,issuer", "waived"),
+    ("fee_paid", "issuer,token", "fee_token,amount,treasury"),
+    ("oper_set", "issuer,token,offering_id", "operator"),
+    ("wl_root", "token,caller,offering_id", "root"),
+    ("wl_claim", "token,offering_id", "investor"),
+    ("bl_import", "token,caller,offering_id", "chunk_index,total_chunks,chunk_len"),
+    ("compl_set", "token,rule,offering_id", "data"),
+    ("compl_mod", "token,caller,offering_id", "module"),
+    ("strat_set", "issuer,token,offering_id", "strategy"),
+    ("rep_final", "issuer,token,offering_id", "period_id,amount"),
+    ("lsn_add", "token,offering_id", "listener"),
+    ("lsn_rem", "token,offering_id", "listener"),
+    ("rev_part", "issuer,token,offering_id", "amount,period_id,running_total"),
+    ("aud_set", "issuer,token,offering_id", "auditor,require_attestation"),
+    ("attested", "auditor,token,offering_id", "period_id,amount"),
+    ("acct_key", "issuer,token,offering_id", "public_key"),
+    ("sig_att", "token,offering_id", "period_id,amount"),
+    ("fiat_set", "issuer,token,offering_id", "config"),
+    ("ded_cap", "issuer,token,offering_id", "max_bps"),
+    ("review", "issuer,token,offering_id", "period_id,deduction_bps,cap_bps"),
+    ("bond_set", "admin", "bond_token,amount"),
+    ("bond_post", "issuer,token", "bond_token,amount"),
+    ("role_set", "admin,account", "role,granted"),
+    ("disp_open", "claimant,token,offering_id", "period_id"),
+    ("disp_res", "arbiter,token,offering_id", "period_id,status"),
+    ("slashed", "token,offering_id", "period_id,amount,appeal_deadline"),
+    ("slash_rev", "arbiter,token,offering_id", "period_id,amount"),
+    ("slash_exe", "token,offering_id", "period_id,amount,treasury"),
+    ("appl_win", "admin", "window_secs"),
+    ("ins_bps", "admin", "bps"),
+    ("ins_fund", "asset", "amount"),
+    ("ins_comp", "token,offering_id", "period_id,count,total"),
+    ("nft_class", "issuer,token,offering_id", "collection,first_id,supply"),
+    ("nft_snap", "token,offering_id", "period_id,start_id,recorded"),
+    ("nft_claim", "holder,token,offering_id", "period_id,nft_ids,amount"),
+    ("cls_share", "issuer,token,offering_id", "holder,class_id,share_bps"),
+    ("conv_set", "issuer,token,offering_id", "from_class,to_class,rule"),
+    ("cls_conv", "investor,token,offering_id", "from_class,to_class,amount,received"),
+    ("forced_tx", "agent,token,offering_id", "from,to,share_bps,reason"),
+    ("clawback", "agent,token,offering_id", "from,issuer,share_bps,reason"),
+    ("acc_mode", "issuer,token,offering_id", "mode"),
+    ("acc_claim", "investor,token,offering_id", "net,withheld"),
+    ("stake", "investor,token,offering_id", "amount,staked"),
+    ("unstake", "investor,token,offering_id", "amount,remaining"),
+    ("cooldown", "issuer,token,offering_id", "cooldown_secs"),
+    ("unstk_req", "investor,token,offering_id", "amount,available_at"),
+    ("unstk_wd", "investor,token,offering_id", "amount"),
+    ("mgr_set", "investor,token,offering_id", "manager"),
+    ("payout_to", "caller,token,offering_id", "investor,payout"),
+    ("amend_new", "issuer,token,offering_id", "id,new_bps,new_payment_token,deadline"),
+    ("amend_vot", "investor,token,offering_id", "id,approve,weight"),
+    ("amend_app", "token,offering_id", "id,new_bps,new_payment_token"),
+    ("amend_rej", "token,offering_id", "id"),
+    ("arb_set", "admin,token,offering_id", "arbiter"),
+    ("ruling", "arbiter,token,offering_id", "period_id,ruling,previous_amount"),
+    ("pen_terms", "admin,token,offering_id", "terms"),
+    ("penalty", "issuer,token,offering_id", "period_id,penalty"),
+    ("per_state", "caller,token,offering_id", "period_id,state"),
+    ("esc_rfnd", "issuer,token,offering_id", "period_id,amount,reason"),
+    ("fin_short", "issuer,token,offering_id", "period_id,shortfall"),
+    ("fund_set", "issuer,token,offering_id", "funder"),
+    ("payout", "engine,token,offering_id", "period_id,investor,amount"),
+    ("eng_ack", "engine,token,offering_id", "period_id"),
+    ("eng_stale", "engine", "last_seen"),
+    ("pay_root", "engine,token,offering_id", "period_id,root,total_paid"),
+    ("pay_chal", "investor,token,offering_id", "period_id,received,entitled"),
+    ("chal_res", "arbiter,token,offering_id", "period_id,investor,status"),
+    ("complaint", "investor,token,offering_id", "id,period_id,reason_hash"),
+    ("cmp_close", "arbiter,token,offering_id", "complaint_id"),
+    ("rescue", "admin,token_contract", "to,amount"),
+    ("rev_init", "issuer,token,offering_id", "amount,period_id,blacklist"),
+    ("rev_ovrd", "issuer,token,offering_id", "amount,period_id,previous_amount,blacklist"),
+    ("rev_rej", "issuer,token,offering_id", "amount,period_id,existing_amount,blacklist"),
+    ("rev_dep", "payer,token,offering_id", "payment_token,amount,period_id"),
+    ("share_set", "issuer,token,offering_id", "holder,share_bps"),
+    ("delay_set", "issuer,token,offering_id", "delay_secs"),
+    ("init", "admin", "safety"),
+    ("paused", "caller", ""),
+    ("unpaused", "caller", ""),
+    ("freeze", "admin", "frozen"),
+    ("admin_set", "admin", ""),
+    ("conc_set", "issuer,token,offering_id", "max_bps,enforce"),
+    ("rnd_set", "issuer,token,offering_id", "mode"),
+    ("stale_set", "admin", "stale_secs"),
+    ("rptrs_set", "issuer,token,offering_id", "reporters,threshold"),
+    ("rep_vote", "reporter,token,offering_id", "period_id,amount,matching"),
+    ("rptr_rot", "issuer,token,offering_id", "reporter,new_reporter,effective_period"),
+    ("checkpt", "token,offering_id", "seq,offering_seq,period_id,state_hash"),
+    ("child_set", "admin", "wasm_hash"),
+    ("child_dep", "issuer,token,offering_id", "child"),
+    ("brdg_set", "issuer,token,offering_id", "bridge"),
+    ("rev_brdg", "token,offering_id", "source_chain,message_id,period_id,amount"),
+    ("verb_dflt", "admin", "verbosity"),
+    ("verb_set", "issuer,token,offering_id", "verbosity"),
+    ("zero_set", "issuer,token,offering_id", "policy"),
+    ("offer_cls", "issuer,token,offering_id", ""),
+    ("mig_prop", "issuer,old_token,offering_id", "new_token,eta"),
+    ("mig_cncl", "issuer,old_token,offering_id", "new_token"),
+    ("tok_mig", "issuer,old_token,offering_id", "new_token"),
+    ("mig_hold", "issuer,new_token,offering_id", "count"),
+    ("off_merge", "issuer,source_token,offering_id", "target_token,holders"),
+    ("off_split", "issuer,token,offering_id", "new_token,ratio_bps,moved"),
+    ("prd_frz", "caller,token,offering_id", "period_id"),
+    ("prd_unfrz", "caller,token,offering_id", "period_id"),
+    ("rel_set", "issuer,token,offering_id", "period_id,tranches"),
+    ("sf_rate", "admin,token,offering_id", "bps_per_day"),
+    ("sf_fund", "issuer,token,offering_id", "period_id,amount,interest"),
+];
+
+/// Layout version of newly written `Offering` records.
+pub const OFFERING_VERSION: u32 = 2;
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Offering {
+    /// Layout version (`OFFERING_VERSION`); older records are upgraded when read.
+    pub version: u32,
+    pub issuer: Address,
+    pub token: Address,
+    pub revenue_share_bps: u32,
+    /// Ledger timestamp of registration.
+    pub created_at: u64,
+    /// Ledger sequence of registration.
+    pub created_ledger: u32,
+    /// Ledger timestamp of the last change to the offering's terms or status.
+    pub updated_at: u64,
+}
+
+/// One entry of `get_event_catalog`: an event's symbol (first topic), the names of its
+/// remaining topics and the names of its data fields, in order.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventSpec {
+    pub name: Symbol,
+    pub topics: Vec<Symbol>,
+    pub data: Vec<Symbol>,
+}
+
+/// Offering layout before versioning and timestamps (version 0).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingV0 {
+    pub issuer: Address,
+    pub token: Address,
+    pub revenue_share_bps: u32,
+}
+
+/// Offering layout with timestamps but no version field (version 1).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingV1 {
     pub issuer: Address,
     pub token: Address,
     pub revenue_share_bps: u32,
+    pub created_at: u64,
+    pub created_ledger: u32,
+    pub updated_at: u64,
+}
+
+impl Offering {
+    /// Decode a stored offering of any known layout, upgrading it to the current one.
+    /// Returns the record and whether it was upgraded.
+    fn from_stored(env: &Env, val: &Val) -> (Offering, bool) {
+        // Decoding a struct map of another arity traps, so dispatch on the field count
+        let fields = Map::<Symbol, Val>::try_from_val(env, val).map_or(0, |m| m.len());
+        if fields == 7 {
+            return (Offering::try_from_val(env, val).unwrap(), false);
+        }
+        if fields == 6 {
+            let v1 = OfferingV1::try_from_val(env, val).unwrap();
+            let offering = Offering {
+                version: OFFERING_VERSION,
+                issuer: v1.issuer,
+                token: v1.token,
+                revenue_share_bps: v1.revenue_share_bps,
+                created_at: v1.created_at,
+                created_ledger: v1.created_ledger,
+                updated_at: v1.updated_at,
+            };
+            return (offering, true);
+        }
+        let v0 = OfferingV0::try_from_val(env, val).unwrap();
+        let offering = Offering {
+            version: OFFERING_VERSION,
+            issuer: v0.issuer,
+            token: v0.token,
+            revenue_share_bps: v0.revenue_share_bps,
+            created_at: 0,
+            created_ledger: 0,
+            updated_at: 0,
+        };
+        (offering, true)
+    }
 }
 
 /// Per-offering concentration guardrail config (#26).
@@ -69,6 +481,57 @@ pub struct ConcentrationLimitConfig {
     pub enforce: bool,
 }
 
+/// Canonical audit record of a finalized period. `fee_taken` and `distributed` are
+/// stored as of finalization and brought up to date by `get_period_summary`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodSummary {
+    /// Finalized amount, in the payout token.
+    pub amount: i128,
+    /// Blacklist hash (`get_blacklist_hash`) at finalization.
+    pub blacklist_hash: BytesN<32>,
+    /// Investors counted towards the offering's cap at finalization.
+    pub holder_count: u32,
+    /// Tax withheld from holders' payouts of the period, at each holder's rate.
+    pub fee_taken: i128,
+    /// Amount paid out to holders from the period's deposit (before withholding).
+    pub distributed: i128,
+    pub finalized_at: u64,
+}
+
+/// Operation accepted by `batch`. Each runs exactly as the entrypoint of the same name
+/// with the batch caller as `caller`/`issuer`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchOp {
+    /// (token, investor)
+    BlacklistAdd(Address, Address),
+    /// (token, investor)
+    BlacklistRemove(Address, Address),
+    /// (token, investor, verified)
+    SetKycStatus(Address, Address, bool),
+    /// (token, investor, frozen)
+    SetInvestorFrozen(Address, Address, bool),
+    /// (token, holder, share_bps)
+    SetHolderShare(Address, Address, u32),
+    /// (token, mode)
+    SetRoundingMode(Address, RoundingMode),
+}
+
+/// Protocol-wide counters returned by `get_protocol_metrics`.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProtocolMetrics {
+    /// Sum of currently stored revenue reports across offerings (mixed currencies).
+    pub total_reported: i128,
+    /// Gross amount paid out to investors by claims, before withholding.
+    pub total_distributed: i128,
+    /// Offerings in `Active` status.
+    pub active_offerings: u32,
+    /// Distinct investors ever admitted to or paid by any offering.
+    pub unique_investors: u32,
+}
+
 /// Per-offering audit log summary (#34).
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -97,13 +560,453 @@ pub enum RoundingMode {
     RoundHalfUp = 1,
 }
 
-/// Storage keys: offerings use OfferCount/OfferItem; blacklist uses Blacklist(token).
+/// How much of the blacklist revenue report events embed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventVerbosity {
+    /// Full blacklist (`Vec<Address>`) in the payload.
+    Full = 0,
+    /// Blacklist commitment hash (see `get_blacklist_hash`) in its place.
+    Compact = 1,
+}
+
+/// A closed series of an offering, archived when the (issuer, token) pair is registered
+/// again. `offering` is the record as it stood when the series was closed; deposited
+/// periods `start_index..end_index` (see `get_period_count`) belong to this series.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeriesRecord {
+    pub offering: Offering,
+    pub offering_id: u64,
+    pub start_index: u32,
+    pub end_index: u32,
+}
+
+/// Per-offering handling of zero-revenue periods in reports.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZeroReportPolicy {
+    /// No policy: any amount is accepted (legacy behaviour).
+    Unrestricted = 0,
+    /// Zero reports are expected: every period must be reported in order, with a zero
+    /// amount for periods without revenue. Negative amounts are rejected.
+    Required = 1,
+    /// Only positive amounts are accepted.
+    Forbidden = 2,
+}
+
+/// Per-offering reporting schedule. Period `p` covers
+/// `[start_time + p * period_secs, start_time + (p + 1) * period_secs)`.
+/// A report filed more than `grace_secs` after its period ends is flagged late.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportingSchedule {
+    pub start_time: u64,
+    pub period_secs: u64,
+    pub grace_secs: u64,
+}
+
+/// Lifecycle status of an offering. Offerings start Active.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OfferingStatus {
+    Active = 0,
+    /// Automatically set by `check_delinquency` after too many consecutive missed periods.
+    /// Deposits and reports past the overdue periods are rejected until reinstated;
+    /// funded periods still pay out.
+    Suspended = 1,
+    /// Wound down by the issuer via `close_offering`; may be registered again as a new series.
+    Closed = 2,
+}
+
+/// Outcome of `is_eligible`: `Eligible`, or the first rule that failed
+/// (checked in declaration order).
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EligibilityResult {
+    Eligible = 0,
+    /// The offering is Closed.
+    OfferingClosed = 1,
+    Blacklisted = 2,
+    InvestorFrozen = 3,
+    NotWhitelisted = 4,
+    KycMissing = 5,
+    JurisdictionBlocked = 6,
+    /// The offering's external compliance module rejected (or failed to answer).
+    ModuleRejected = 7,
+}
+
+/// One `export_eligibility` row: `reason` is the `is_eligible` outcome, and `eligible`
+/// is true exactly when it is `Eligible`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EligibilityRow {
+    pub investor: Address,
+    pub eligible: bool,
+    pub reason: EligibilityResult,
+}
+
+/// Non-fungible share class: one equal share per NFT id in `first_id..first_id + supply`
+/// of `collection`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NftShareClass {
+    pub collection: Address,
+    pub first_id: u32,
+    pub supply: u32,
+}
+
+/// Issuer-defined conversion from one share class to another: `ratio_bps` of the target
+/// class per 10000 bps converted, allowed while `opens_at <= now < closes_at`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassConversion {
+    pub ratio_bps: u32,
+    pub opens_at: u64,
+    pub closes_at: u64,
+}
+
+/// How an offering's deposited revenue accrues to investors.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccrualMode {
+    /// Per-period claims: each deposited period pays `revenue * share_bps / 10000`.
+    Periods = 0,
+    /// Accumulator over holder shares: deposits raise `acc_revenue_per_share`; holders
+    /// claim lazily via `claim_accrued`.
+    Shares = 1,
+    /// Accumulator over offering tokens staked in this contract (`stake` / `unstake`).
+    Staked = 2,
+}
+
+/// Per-offering accumulator: revenue per unit of weight (scaled by ACC_SCALE), total
+/// weight, and revenue deposited while no weight existed (credited on the next deposit).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccumulatorState {
+    pub acc_per_share: i128,
+    pub total_weight: i128,
+    pub undistributed: i128,
+}
+
+/// An investor's accumulator position: weight, reward debt (`weight * acc / ACC_SCALE`
+/// at the last settlement) and settled but unclaimed revenue.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccrualPosition {
+    pub weight: i128,
+    pub reward_debt: i128,
+    pub accrued: i128,
+}
+
+/// Unstaked tokens waiting out the offering's cooldown.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingUnstake {
+    pub amount: i128,
+    pub available_at: u64,
+}
+
+/// Lifecycle of a term amendment.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmendmentStatus {
+    Pending = 0,
+    Applied = 1,
+    Rejected = 2,
+}
+
+/// Pending replacement of an offering's share token, executable from `eta`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenMigration {
+    pub new_token: Address,
+    pub proposed_at: u64,
+    pub eta: u64,
+}
+
+/// Proposed change to an offering's material terms, voted on by holders weighted by
+/// share bps. Passes once `votes_for` exceeds 5000 (half of all shares).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Amendment {
+    pub new_bps: u32,
+    /// New payout currency; None keeps the current payment token.
+    pub new_payment_token: Option<Address>,
+    pub proposed_at: u64,
+    pub deadline: u64,
+    /// Period index whose holder shares weight the votes: the last period deposited
+    /// before the proposal.
+    pub snapshot_index: u32,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub status: AmendmentStatus,
+}
+
+/// Offering terms in effect for a range of periods.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingTerms {
+    pub revenue_share_bps: u32,
+    pub payment_token: Option<Address>,
+}
+
+/// Late-funding penalty: once `window_secs` have passed since declaration without a
+/// deposit, `bps_per_day` of the declared amount accrues per full day, owed to investors.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PenaltyTerms {
+    pub window_secs: u64,
+    pub bps_per_day: u32,
+}
+
+/// Lifecycle of a revenue period. Transitions only move forward:
+/// Declared -> Deposited -> Finalized -> Distributed -> Settled. A deposit for an
+/// undeclared period enters at Deposited.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeriodState {
+    /// Revenue reported; no funds in escrow yet.
+    Declared = 0,
+    /// Funds for the period are in escrow.
+    Deposited = 1,
+    /// Amount locked; no further overrides.
+    Finalized = 2,
+    /// Payouts for the period have been made available.
+    Distributed = 3,
+    /// Period reconciled and closed.
+    Settled = 4,
+}
+
+/// Escrow returned to the issuer for a period whose distribution never completed.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscrowRefund {
+    pub amount: i128,
+    pub reason: Symbol,
+    pub refunded_at: u64,
+}
+
+/// One tranche of a period's release schedule: `bps` of each holder's payout unlocks
+/// `delay_secs` after the period's deposit.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseTranche {
+    pub delay_secs: u64,
+    pub bps: u32,
+}
+
+/// Funding of a period: the declared payout, deposits so far, and any shortfall recorded
+/// by `finalize_report_short`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodFunding {
+    pub declared: i128,
+    pub deposited: i128,
+    pub shortfall: i128,
+}
+
+/// A distribution engine's acknowledgement that it picked up a period's report.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EngineAck {
+    pub engine: Address,
+    pub acked_at: u64,
+}
+
+/// Whether a period has actually paid out. Moves forward only.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DistributionStatus {
+    /// Funded; nothing paid yet.
+    Pending = 0,
+    /// First claims or engine payouts made.
+    InProgress = 1,
+    /// Engine marked the period distributed.
+    Distributed = 2,
+    /// Issuer settled the distributed period.
+    Verified = 3,
+}
+
+/// K-of-M reporter committee of an offering: a period's report is accepted once
+/// `threshold` reporters submit the same amount.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReporterSet {
+    pub reporters: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Scheduled replacement of a committee reporter's key: the old key reports periods
+/// before `effective_period`, `new_reporter` reports from it on.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReporterRotation {
+    pub new_reporter: Address,
+    pub effective_period: u64,
+}
+
+/// Merkle commitment to a period's actual payouts, submitted by the engine that
+/// distributed it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayoutRoot {
+    pub engine: Address,
+    pub root: BytesN<32>,
+    pub total_paid: i128,
+    pub submitted_at: u64,
+}
+
+/// An investor's challenge that the payout root paid them less than their entitlement.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayoutChallenge {
+    /// Amount the payout root records for the investor.
+    pub received: i128,
+    /// On-chain entitlement from the investor's share at the period.
+    pub entitled: i128,
+    /// Bond token posted, if a bond was required.
+    pub bond_token: Option<Address>,
+    /// Bond amount posted (0 if none).
+    pub bond_amount: i128,
+    pub opened_at: u64,
+    pub status: DisputeStatus,
+}
+
+/// An investor grievance about a period, for arbiters and reputation scoring.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Complaint {
+    pub investor: Address,
+    pub period_id: u64,
+    pub reason_hash: BytesN<32>,
+    pub filed_at: u64,
+    pub open: bool,
+}
+
+/// Interface for external distribution strategies (tiered, waterfall, ...) registered
+/// per offering. Returns the holder's payout from one period's deposited revenue;
+/// results are clamped to `0..=revenue`.
+#[contractclient(name = "DistributionStrategyClient")]
+pub trait DistributionStrategy {
+    fn compute_payout(
+        env: Env,
+        token: Address,
+        holder: Address,
+        share_bps: u32,
+        period_id: u64,
+        revenue: i128,
+    ) -> i128;
+}
+
+/// Interface for price oracles used by fiat-denominated reporting. Returns `asset`
+/// units per one unit of `currency`, scaled by PRICE_SCALE (1e7).
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn price(env: Env, currency: Symbol, asset: Address) -> i128;
+}
+
+/// Interface for contracts notified when a period's report is finalized.
+#[contractclient(name = "ReportListenerClient")]
+pub trait ReportListener {
+    fn notify(env: Env, token: Address, period_id: u64, amount: i128);
+}
+
+/// Interface for external compliance modules registered per offering.
+/// `context` is the offering token being checked.
+#[contractclient(name = "ComplianceModuleClient")]
+pub trait ComplianceModule {
+    fn check(env: Env, investor: Address, context: Address) -> bool;
+}
+
+/// Minimal ownership interface of an NFT collection used as a share class.
+#[contractclient(name = "NftCollectionClient")]
+pub trait NftCollection {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+}
+
+/// Revenue earned on another chain, relayed by the offering's registered bridge.
+/// `message_id` is the bridge's unique id for the message and is accepted once.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BridgeReport {
+    pub source_chain: Symbol,
+    pub message_id: BytesN<32>,
+    pub period_id: u64,
+    pub amount: i128,
+    pub override_existing: bool,
+}
+
+/// Entry point of per-offering child contracts deployed in factory mode. The child
+/// holds the offering's escrow and serves its claims; this contract stays the registry.
+#[contractclient(name = "OfferingChildClient")]
+pub trait OfferingChild {
+    fn init(env: Env, registry: Address, issuer: Address, token: Address, offering_id: u64);
+}
+
+/// Dashboard view of an offering returned by `get_offering_health`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingHealth {
+    pub status: OfferingStatus,
+    /// Highest period_id with a stored report.
+    pub last_report_period: Option<u64>,
+    /// Ledger timestamp of the report for `last_report_period`.
+    pub last_report_time: Option<u64>,
+    /// Consecutive overdue periods without a report.
+    pub missed_periods: u32,
+    /// Deposited revenue not yet paid out to holders.
+    pub escrow_balance: i128,
+    pub blacklist_size: u32,
+    /// Open disputes against this offering's reports.
+    pub pending_disputes: u32,
+}
+
+/// One entry in an investor's persisted claim history.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimRecord {
+    /// Amount paid out by the claim (net of withholding).
+    pub amount: i128,
+    /// Tax withheld from the claim.
+    pub withheld: i128,
+    /// Number of periods covered by the claim.
+    pub period_count: u32,
+    /// Last period_id covered by the claim.
+    pub last_period_id: u64,
+    /// Ledger timestamp of the claim.
+    pub timestamp: u64,
+}
+
+/// Account statement for an investor on an offering, returned by `get_investor_statement`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvestorStatement {
+    /// Lifetime entitlement: claimed + pending + frozen.
+    pub total_earned: i128,
+    /// Lifetime gross amount claimed, including any tax withheld.
+    pub total_claimed: i128,
+    /// Amount claimable right now.
+    pub pending: i128,
+    /// Unclaimed amount that cannot be claimed yet (claim delay) or at all (ineligible).
+    pub frozen: i128,
+    /// Holder share (tier) in basis points.
+    pub share_bps: u32,
+    pub blacklisted: bool,
+    /// True if the investor may claim (has a share and passes `is_eligible`).
+    pub can_claim: bool,
+}
+
+/// Storage keys: offerings use OfferCount/OfferItem; the blacklist lives under `ComplianceKey`
+/// (Blacklist(token) holds maps written by earlier layouts).
 /// Multi-period claim keys use PeriodRevenue/PeriodEntry/PeriodCount for per-offering
 /// period tracking, HolderShare for holder allocations, LastClaimedIdx for claim progress,
 /// and PaymentToken for the token used to pay out revenue.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
+    /// Blacklist map of offering_token from earlier layouts; folded into the
+    /// `ComplianceKey` entries when the token's blacklist next changes.
     Blacklist(Address),
     OfferCount(Address),
     OfferItem(Address, u32),
@@ -143,51 +1046,754 @@ pub enum DataKey {
     Safety,
     /// Global pause flag; when true, state-mutating ops are disabled (#7).
     Paused,
+    /// Reporting schedule (period length and grace window) for an offering token.
+    ReportingSchedule(Address),
+    /// Set when (offering_token, period_id) had no report filed within the grace window.
+    LateReport(Address, u64),
+    /// Lifecycle status of an offering token (defaults to Active).
+    OfferingStatus(Address),
+    /// Highest period_id with a stored report for an offering token.
+    LastReportedPeriod(Address),
+    /// Consecutive missed periods that trigger auto-suspension (0 = disabled).
+    SuspendAfterMissed(Address),
+    /// Latest overdue period already announced via a `delinq` event for an offering token.
+    DelinquencyNotified(Address),
+    /// Deposited revenue held by the contract and not yet claimed, per offering token.
+    EscrowBalance(Address),
+    /// Number of open disputes against an offering token's reports.
+    OpenDisputeCount(Address),
+    /// Page of claim records for (offering_token, investor, page_index).
+    ClaimHistory(Address, Address, u32),
+    /// Total claim records stored for (offering_token, investor).
+    ClaimHistoryCount(Address, Address),
+    /// Lifetime amount claimed by (offering_token, investor).
+    TotalClaimed(Address, Address),
+    /// Withholding rate in bps for (offering_token, jurisdiction_code); code 0 is the offering default.
+    WithholdingBps(Address, u32),
+    /// Jurisdiction code assigned to (offering_token, investor); 0 if unassigned.
+    InvestorJurisdiction(Address, Address),
+    /// Withheld tax accumulated for (offering_token, jurisdiction_code), awaiting remittance.
+    WithheldBalance(Address, u32),
+    /// Issuer of an offering token (first registration wins).
+    OfferingIssuer(Address),
+    /// Remittance address for (offering_token, jurisdiction_code) withholdings.
+    WithholdingRecipient(Address, u32),
+    /// Lifetime withholding remitted for (offering_token, jurisdiction_code).
+    WithholdingRemitted(Address, u32),
+    /// Secondary operational admin for offering_token (blacklist and reporting only).
+    OfferingOperator(Address),
+    /// External distribution strategy contract for offering_token (None = pro-rata).
+    DistributionStrategy(Address),
+    /// Audit summary of (offering_token, period_id) written at finalization.
+    PeriodSummary(Address, u64),
 }
 
-/// Maximum number of offerings returned in a single page.
-const MAX_PAGE_LIMIT: u32 = 20;
+/// Storage keys for secondary offering indexes (status, ids). Separate from `DataKey`
+/// for the same 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum IndexKey {
+    /// Offering tokens of (issuer, status), in transition order.
+    OfferingsByStatus(Address, OfferingStatus),
+    /// Last assigned global offering id (ids start at 1).
+    LastOfferingId,
+    /// Global offering id -> (issuer, token).
+    OfferingById(u64),
+    /// Offering token -> global offering id.
+    OfferingIdOf(Address),
+    /// Child contract deployed for an offering in factory mode.
+    OfferingChild(Address),
+    /// Number of archived series of offering_token.
+    SeriesCount(Address),
+    /// Archived series of offering_token by series number.
+    SeriesArchive(Address, u32),
+    /// Offering token a merged offering_token was folded into.
+    MergedInto(Address),
+    /// Offering token that offering_token was split from.
+    SplitFrom(Address),
+}
 
-/// Maximum number of periods that can be claimed in a single transaction.
-/// Keeps compute costs predictable within Soroban limits.
-const MAX_CLAIM_PERIODS: u32 = 50;
+/// Storage keys for per-offering compliance lists (whitelist, bulk blacklist imports).
+/// Separate from `DataKey` for the same 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum ComplianceKey {
+    /// Committed Merkle root of approved investors for offering_token.
+    WhitelistRoot(Address),
+    /// Root under which (offering_token, investor) claimed its whitelist slot.
+    WhitelistedUnder(Address, Address),
+    /// Progress of the latest chunked blacklist import for offering_token.
+    BlacklistImport(Address),
+    /// XOR of `sha256(address XDR)` over current blacklist members of offering_token.
+    BlacklistHash(Address),
+    /// Number of blacklisted addresses for offering_token.
+    BlacklistCount(Address),
+    /// Slot of a blacklisted (offering_token, investor) in the member list.
+    BlacklistMember(Address, Address),
+    /// Blacklisted address at (offering_token, slot); slots are dense from 0.
+    BlacklistSlot(Address, u32),
+    /// Whether offering_token requires KYC-verified investors.
+    KycRequired(Address),
+    /// KYC verification flag for (offering_token, investor).
+    KycVerified(Address, Address),
+    /// Jurisdiction code excluded from offering_token.
+    JurisdictionBlocked(Address, u32),
+    /// Per-investor freeze on offering_token.
+    InvestorFrozen(Address, Address),
+    /// External compliance contract consulted by `is_eligible` for offering_token.
+    ComplianceModule(Address),
+    /// Maximum distinct investors in offering_token (0 or unset = unlimited).
+    InvestorCap(Address),
+    /// Distinct investors currently admitted to offering_token.
+    InvestorCount(Address),
+    /// Whether (offering_token, investor) counts towards the investor cap.
+    InvestorAdmitted(Address, Address),
+    /// Number of investors ever admitted to the offering (roster length).
+    RosterLen(Address),
+    /// Roster entry: offering token, position -> investor.
+    RosterEntry(Address, u32),
+    /// Roster position of an investor; present once it has been enrolled.
+    RosterSlot(Address, Address),
+    /// Non-zero jurisdiction codes offering_token has configured or assigned (Vec<u32>),
+    /// so per-jurisdiction entries can be moved with the offering.
+    JurisdictionCodes(Address),
+}
 
-#[contract]
-pub struct RevoraRevenueShare;
+/// Storage keys for the reporting lifecycle (finalization, listeners). Separate from
+/// `DataKey` for the same 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum ReportKey {
+    /// Finalized (offering_token, period_id) -> finalized amount.
+    FinalizedPeriod(Address, u64),
+    /// Listener contracts notified on `finalize_report` for offering_token.
+    ReportListeners(Address),
+    /// Designated auditor configuration for offering_token.
+    AuditorConfig(Address),
+    /// Amount the auditor attested for (offering_token, period_id).
+    AuditorAttestation(Address, u64),
+    /// Registered accounting-system ed25519 public key for offering_token.
+    AccountingKey(Address),
+    /// Signed accounting-system attestation for (offering_token, period_id).
+    SignedAttestation(Address, u64),
+    /// Fiat reporting configuration for offering_token.
+    FiatReporting(Address),
+    /// Conversion applied when (offering_token, period_id) was finalized.
+    FiatConversion(Address, u64),
+    /// Maximum deductions as bps of gross for offering_token.
+    DeductionCapBps(Address),
+    /// Gross/deductions breakdown of a structured report for (offering_token, period_id).
+    StructuredReport(Address, u64),
+    /// Borderline-deduction flag awaiting auditor review for (offering_token, period_id).
+    ReviewFlag(Address, u64),
+    /// Ledger timestamp of (offering_token, period_id)'s first declaration.
+    DeclaredAt(Address, u64),
+    /// Late-funding penalty terms of offering_token.
+    PenaltyTerms(Address),
+    /// Penalty paid with (offering_token, period_id)'s deposit.
+    PenaltyPaid(Address, u64),
+    /// Lifecycle state of (offering_token, period_id).
+    PeriodState(Address, u64),
+    /// One past the highest period index any holder of offering_token has claimed from.
+    ClaimFrontier(Address),
+    /// Escrow refund of (offering_token, period_id).
+    EscrowRefund(Address, u64),
+    /// Unfunded remainder of (offering_token, period_id) accepted at finalization.
+    Shortfall(Address, u64),
+    /// Number of periods of offering_token with an unfunded shortfall.
+    OpenShortfallCount(Address),
+    /// Address allowed to fund offering_token's periods alongside the issuer.
+    FundingSource(Address),
+    /// Payouts an engine recorded to investor for (offering_token, period_id).
+    PayoutRecord(Address, u64, Address),
+    /// Total payouts recorded for (offering_token, period_id).
+    PayoutTotal(Address, u64),
+    /// Engine acknowledgement of (offering_token, period_id)'s report.
+    EngineAck(Address, u64),
+    /// Recorded Distributed or Verified status of (offering_token, period_id).
+    DistStatus(Address, u64),
+    /// Payout Merkle root of (offering_token, period_id).
+    PayoutRoot(Address, u64),
+    /// Reporter committee of offering_token.
+    ReporterSet(Address),
+    /// Amount a committee reporter submitted for (offering_token, period_id).
+    ReporterVote(Address, u64, Address),
+    /// Scheduled key rotation of a committee seat (offering_token, reporter).
+    ReporterRotation(Address, Address),
+    /// Most recently finalized period of offering_token.
+    LastFinalized(Address),
+    /// State commitment of offering_token (`get_state_hash`).
+    StateHash(Address),
+    /// Checkpoints emitted for offering_token.
+    OfferingCheckpoints(Address),
+    /// Bridge/messaging contract allowed to report cross-chain revenue.
+    Bridge(Address),
+    /// Period recorded for a consumed bridge message id.
+    BridgeMessage(Address, BytesN<32>),
+    /// Per-offering event verbosity override.
+    EventVerbosity(Address),
+    /// Highest finalized period of offering_token.
+    MaxFinalized(Address),
+    /// Zero-revenue report policy of offering_token.
+    ZeroReportPolicy(Address),
+    /// Auditor or arbiter who froze (offering_token, period_id) pending audit.
+    FrozenPeriod(Address, u64),
+    /// Number of frozen periods of offering_token.
+    FrozenPeriodCount(Address),
+    /// Period release schedule: offering token, period_id -> Vec<ReleaseTranche>.
+    ReleaseSchedule(Address, u64),
+    /// Number of periods of offering_token with a release schedule.
+    ScheduledPeriodCount(Address),
+    /// Daily interest rate (bps) on finalized shortfalls, per offering token.
+    ShortfallRate(Address),
+    /// Shortfall interest accrual start: offering token, period_id -> timestamp.
+    InterestFrom(Address, u64),
+    /// Shortfall interest collected: offering token, period_id -> amount.
+    InterestPaid(Address, u64),
+}
 
-#[contractimpl]
-impl RevoraRevenueShare {
-    /// Returns error if contract is frozen (#32). Call at start of state-mutating entrypoints.
-    fn require_not_frozen(env: &Env) -> Result<(), RevoraError> {
-        let key = DataKey::Frozen;
-        if env
-            .storage()
-            .persistent()
-            .get::<DataKey, bool>(&key)
-            .unwrap_or(false)
-        {
-            return Err(RevoraError::ContractFrozen);
-        }
-        Ok(())
-    }
+/// Storage keys for issuer bonds and their enforcement. Separate from `DataKey` for the
+/// same 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum BondKey {
+    /// Bond held for offering_token.
+    OfferingBond(Address),
+    /// Dispute against (offering_token, period_id)'s report.
+    Dispute(Address, u64),
+    /// Slash executed for (offering_token, period_id)'s lost dispute.
+    Slash(Address, u64),
+    /// Insurance pool balance held by this contract, per asset.
+    InsurancePool(Address),
+    /// Total compensation paid for (offering_token, period_id).
+    Compensated(Address, u64),
+    /// Per-offering arbiter of offering_token (in addition to global Arbiter role holders).
+    OfferingArbiter(Address),
+    /// Arbiter ruling on (offering_token, period_id)'s report.
+    Ruling(Address, u64),
+    /// Investor's challenge of (offering_token, period_id)'s payout root.
+    PayoutChallenge(Address, u64, Address),
+    /// Complaint on offering_token by id.
+    Complaint(Address, u32),
+    /// Next complaint id of offering_token.
+    NextComplaintId(Address),
+    /// Ids of offering_token's open complaints.
+    OpenComplaints(Address),
+    /// Complaints filed by investor on (offering_token, period_id).
+    ComplaintsFiled(Address, u64, Address),
+}
 
-    /// Initialize admin and optional safety role for emergency pause (#7).
-    /// Can only be called once; panics if already initialized.
-    pub fn initialize(env: Env, admin: Address, safety: Option<Address>) {
-        if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("already initialized");
-        }
-        env.storage()
-            .persistent()
-            .set(&DataKey::Admin, &admin.clone());
-        if let Some(s) = safety.clone() {
-            env.storage().persistent().set(&DataKey::Safety, &s);
+/// Storage keys for share classes. Separate from `DataKey` for the same 50-variant
+/// reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum ShareKey {
+    /// NFT share class of offering_token (None = bps holder shares).
+    NftClass(Address),
+    /// Snapshotted owner of NFT id for (offering_token, period_id, nft_id).
+    OwnerAt(Address, u64, u32),
+    /// Set once NFT id's share of (offering_token, period_id) has been claimed.
+    NftClaimed(Address, u64, u32),
+    /// Total paid to NFT holders from (offering_token, period_id).
+    NftPaid(Address, u64),
+    /// Holder share (bps) held in a class for (offering_token, class_id, holder).
+    ClassShare(Address, u32, Address),
+    /// Conversion rule for (offering_token, from_class, to_class).
+    Conversion(Address, u32, u32),
+    /// Share checkpoints for (offering_token, holder): (first period index, share_bps),
+    /// ascending; one entry per change, collapsed when no period was deposited between.
+    ShareHistory(Address, Address),
+    /// Sequential deposit index of (offering_token, period_id).
+    PeriodIndex(Address, u64),
+    /// Claim manager approved by (offering_token, investor).
+    ClaimManager(Address, Address),
+    /// Address receiving (offering_token, investor)'s payouts (defaults to the investor).
+    PayoutAddress(Address, Address),
+    /// Amount a holder already took from the partially released period at its claim index.
+    ReleasedPaid(Address, Address),
+    /// Payouts a holder received from periods while they were short: token, holder ->
+    /// Map<period_id, amount>.
+    ShortPaid(Address, Address),
+    /// Share each claimed period index was paid at for (offering_token, holder):
+    /// (end index, share_bps) ranges, ascending and contiguous from index 0.
+    ClaimedShares(Address, Address),
+}
+
+/// Storage keys for investor-approved term amendments. Separate from `DataKey` for the
+/// same 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum GovKey {
+    /// Last amendment id of offering_token (ids start at 1).
+    LastAmendmentId(Address),
+    /// Amendment (offering_token, amendment_id).
+    Amendment(Address, u64),
+    /// Set once (offering_token, amendment_id, investor) has voted.
+    AmendmentVote(Address, u64, Address),
+    /// Superseded terms of offering_token: (first period index under the next terms, terms).
+    TermsHistory(Address),
+    /// Pending share token migration of offering_token.
+    PendingMigration(Address),
+    /// Replacement token of a migrated offering_token.
+    MigratedTo(Address),
+}
+
+/// Storage keys for the accumulator accrual engine. Separate from `DataKey` for the same
+/// 50-variant reason as `AdminKey`.
+#[contracttype]
+#[derive(Clone)]
+pub enum AccrualKey {
+    /// Accrual mode of offering_token (defaults to Periods).
+    Mode(Address),
+    /// Accumulator state of offering_token.
+    State(Address),
+    /// Accumulator position of (offering_token, investor).
+    Position(Address, Address),
+    /// Seconds between `request_unstake` and withdrawal for offering_token (0 = none).
+    UnstakeCooldown(Address),
+    /// Unstaked tokens awaiting withdrawal for (offering_token, investor).
+    PendingUnstake(Address, Address),
+}
+
+/// Storage keys for admin-managed protocol configuration (timelock queue, limits, fees).
+/// Kept separate from `DataKey` because contracttype unions are limited to 50 variants;
+/// variant names must stay unique across both enums.
+#[contracttype]
+#[derive(Clone)]
+pub enum AdminKey {
+    /// Next timelocked admin operation id.
+    NextAdminOpId,
+    /// Queued admin operation by id.
+    AdminOp(u64),
+    /// Ids of queued (not yet executed or cancelled) admin operations.
+    PendingAdminOps,
+    /// Minimum timelock delay in seconds per admin operation kind.
+    AdminOpDelay(u32),
+    /// Storage layout version of the installed code (missing = 0, the layout before
+    /// versioning). Recorded by `set_admin`/`initialize` on fresh deployments, set by
+    /// `upgrade` to the version it replaced, and raised by `post_upgrade`.
+    ContractVersion,
+    /// Set by the 0 -> 1 migration: `DataKey::Blacklist` maps may still exist and are read.
+    LegacyBlacklists,
+    /// Default maximum offerings per issuer (0 = unlimited).
+    MaxOfferingsPerIssuer,
+    /// Per-issuer override of the maximum offerings.
+    IssuerOfferingLimit(Address),
+    /// When true, only allowlisted issuers may register offerings.
+    IssuerAllowlistEnabled,
+    /// Issuer allowlist membership.
+    AllowedIssuer(Address),
+    /// Protocol treasury that receives fees.
+    Treasury,
+    /// Registration fee charged by `register_offering`.
+    RegistrationFee,
+    /// Issuers exempt from the registration fee.
+    FeeWaiver(Address),
+    /// Bond issuers must stake when registering an offering.
+    IssuerBondConfig,
+    /// Holders of a protocol role.
+    RoleMember(Role, Address),
+    /// Enumerable members of a role, in grant order.
+    RoleMembers(Role),
+    /// Enumerable roles held by an address.
+    RolesOf(Address),
+    /// Appeal window after `slash_bond`, in seconds.
+    AppealWindowSecs,
+    /// Share of protocol fees (bps) routed to the insurance pool.
+    InsuranceBps,
+    /// Bond investors post with `challenge_payout`.
+    ChallengeBond,
+    /// Balance of an asset this contract owes (escrow, withholding, bonds, stakes),
+    /// excluding the insurance pool.
+    HeldBalance(Address),
+    /// Ledger timestamp of a distribution engine's latest action.
+    EngineLastSeen(Address),
+    /// Inactivity after which a distribution engine may be flagged stale (0 = never).
+    EngineStaleSecs,
+    /// Protocol-wide aggregate counters.
+    ProtocolMetrics,
+    /// Whether an investor is counted in `unique_investors`.
+    InvestorSeen(Address),
+    /// Protocol-wide checkpoint sequence (`checkpt` events).
+    CheckpointSeq,
+    /// WASM hash deployed per offering in factory mode (unset = disabled).
+    ChildWasmHash,
+    /// Protocol default event verbosity.
+    DefaultVerbosity,
+    /// Last sequence number used by a distribution engine.
+    EngineSeq(Address),
+}
+
+/// Admin-level operation that can be queued behind a timelock. Each also has a direct
+/// entry point, which is rejected with `TimelockNotElapsed` once its kind has a delay.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    /// Enable or disable testnet mode.
+    SetTestnetMode(bool),
+    /// Freeze the contract.
+    Freeze,
+    /// Set the minimum delay (secs) for an operation kind.
+    SetOpDelay(u32, u64),
+    /// Install new contract WASM by hash.
+    Upgrade(BytesN<32>),
+    /// Set the registration fee (token, amount).
+    SetRegistrationFee(Address, i128),
+    /// Set the protocol treasury.
+    SetTreasury(Address),
+    /// Set the share of protocol fees routed to the insurance pool (bps).
+    SetInsuranceBps(u32),
+    /// Set the default offerings-per-issuer limit (0 = unlimited).
+    SetMaxOfferingsPerIssuer(u32),
+    /// Set or clear (`None`) an issuer's offering limit override.
+    SetIssuerOfferingLimit(Address, Option<u32>),
+    /// Switch between permissionless (false) and permissioned (true) registration.
+    SetIssuerAllowlistEnabled(bool),
+    /// Add (true) or remove (false) an issuer on the allowlist.
+    SetIssuerAllowed(Address, bool),
+    /// Grant (true) or remove (false) an issuer's registration fee waiver.
+    SetFeeWaiver(Address, bool),
+    /// Set the issuer bond required at registration (token, amount).
+    SetIssuerBond(Address, i128),
+    /// Grant (true) or revoke (false) a protocol role for an account.
+    SetRole(Role, Address, bool),
+    /// Set the slash appeal window (secs).
+    SetAppealWindow(u64),
+    /// Pay investors from the insurance pool (token, period_id, investors, amounts).
+    Compensate(Address, u64, Vec<Address>, Vec<i128>),
+    /// Remove a report listener (token, listener).
+    RemoveListener(Address, Address),
+    /// Set or clear (`None`) an offering's arbiter.
+    SetOfferingArbiter(Address, Option<Address>),
+    /// Set late-funding penalty terms (token, window_secs, bps_per_day).
+    SetPenaltyTerms(Address, u64, u32),
+    /// Set the daily shortfall interest rate (token, bps_per_day).
+    SetShortfallInterest(Address, u32),
+    /// Set the inactivity after which engines may be flagged stale (secs).
+    SetEngineStaleSecs(u64),
+    /// Set the payout challenge bond (token, amount).
+    SetChallengeBond(Address, i128),
+    /// Enable factory mode with a child WASM hash.
+    SetChildWasmHash(BytesN<32>),
+    /// Disable factory mode.
+    ClearChildWasmHash,
+    /// Set the protocol default event verbosity.
+    SetDefaultEventVerbosity(EventVerbosity),
+    /// Register a distribution engine.
+    AddEngine(Address),
+}
+
+impl AdminAction {
+    /// Stable kind code used to look up per-operation minimum delays.
+    pub fn kind(&self) -> u32 {
+        match self {
+            AdminAction::SetTestnetMode(_) => 0,
+            AdminAction::Freeze => 1,
+            AdminAction::SetOpDelay(_, _) => 2,
+            AdminAction::Upgrade(_) => 3,
+            AdminAction::SetRegistrationFee(_, _) => 4,
+            AdminAction::SetTreasury(_) => 5,
+            AdminAction::SetInsuranceBps(_) => 6,
+            AdminAction::SetMaxOfferingsPerIssuer(_) => 7,
+            AdminAction::SetIssuerOfferingLimit(_, _) => 8,
+            AdminAction::SetIssuerAllowlistEnabled(_) => 9,
+            AdminAction::SetIssuerAllowed(_, _) => 10,
+            AdminAction::SetFeeWaiver(_, _) => 11,
+            AdminAction::SetIssuerBond(_, _) => 12,
+            AdminAction::SetRole(_, _, _) => 13,
+            AdminAction::SetAppealWindow(_) => 14,
+            AdminAction::Compensate(_, _, _, _) => 15,
+            AdminAction::RemoveListener(_, _) => 16,
+            AdminAction::SetOfferingArbiter(_, _) => 17,
+            AdminAction::SetPenaltyTerms(_, _, _) => 18,
+            AdminAction::SetShortfallInterest(_, _) => 19,
+            AdminAction::SetEngineStaleSecs(_) => 20,
+            AdminAction::SetChallengeBond(_, _) => 21,
+            AdminAction::SetChildWasmHash(_) | AdminAction::ClearChildWasmHash => 22,
+            AdminAction::SetDefaultEventVerbosity(_) => 23,
+            AdminAction::AddEngine(_) => 24,
         }
-        env.storage().persistent().set(&DataKey::Paused, &false);
-        env.events().publish((EVENT_INIT, admin.clone()), (safety,));
     }
+}
 
-    /// Pause the contract (admin only). Idempotent.
+/// An amount of `token` (registration fee, issuer bond, challenge bond).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeConfig {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Progress of a chunked blacklist import (`blacklist_import`).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlacklistImport {
+    pub total_chunks: u32,
+    /// Next expected chunk index; equals `total_chunks` once complete.
+    pub next_chunk: u32,
+    /// Addresses submitted so far (including ones already blacklisted).
+    pub imported: u32,
+    pub complete: bool,
+}
+
+/// Per-offering auditor; when `require_attestation` is set, `finalize_report` needs the
+/// auditor's `attest_report` of the current amount.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditorConfig {
+    pub auditor: Address,
+    pub require_attestation: bool,
+}
+
+/// Accounting-system attestation stored alongside a period's report.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedAttestation {
+    pub amount: i128,
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+    pub timestamp: u64,
+}
+
+/// Fiat-denominated reporting: amounts are reported in `currency` and converted to
+/// `payout_token` units at the oracle rate when the period is finalized.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiatReportingConfig {
+    pub oracle: Address,
+    pub currency: Symbol,
+    pub payout_token: Address,
+}
+
+/// Conversion recorded at finalization for a fiat-reported period.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiatConversion {
+    pub fiat_amount: i128,
+    /// `payout_token` units per fiat unit, scaled by PRICE_SCALE (1e7).
+    pub rate: i128,
+    pub payout_amount: i128,
+}
+
+/// Gross revenue and deductions behind a structured report (net = gross - deductions).
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuredReport {
+    pub gross: i128,
+    pub deductions: i128,
+}
+
+/// Issuer bond held by the contract for an offering; slashable after a lost dispute.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingBond {
+    pub issuer: Address,
+    pub bond_token: Address,
+    /// Amount still held.
+    pub amount: i128,
+    /// Amount slashed so far.
+    pub slashed: i128,
+}
+
+/// Protocol roles granted by the admin.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Resolves disputes and may reverse slashes during the appeal window.
+    Arbiter = 0,
+    /// Executes regulator-mandated forced transfers and clawbacks of holder shares.
+    TransferAgent = 1,
+    /// Off-chain distribution engine: records payouts and marks periods distributed.
+    DistributionEngine = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Open = 0,
+    /// Arbiter ruled against the issuer; the bond may be slashed.
+    Upheld = 1,
+    Rejected = 2,
+}
+
+/// Arbiter ruling on a disputed report.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ruling {
+    /// The report stands; the dispute is rejected.
+    Uphold,
+    /// The report amount is replaced; the dispute is upheld.
+    Amend(i128),
+    /// The report is removed; the dispute is upheld.
+    Void,
+}
+
+/// A stored arbiter ruling.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RulingRecord {
+    pub arbiter: Address,
+    pub ruling: Ruling,
+    /// Report amount before the ruling.
+    pub previous_amount: i128,
+    pub ruled_at: u64,
+}
+
+/// A challenge to a period's report.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dispute {
+    pub claimant: Address,
+    /// Hash of off-chain evidence.
+    pub evidence: BytesN<32>,
+    pub opened_at: u64,
+    pub status: DisputeStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlashStatus {
+    /// Within the appeal window; an arbiter may still reverse it.
+    Pending = 0,
+    Reversed = 1,
+    /// Slashed funds transferred to the treasury.
+    Executed = 2,
+}
+
+/// Bond slash for a lost dispute.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slash {
+    pub amount: i128,
+    pub slashed_at: u64,
+    pub appeal_deadline: u64,
+    pub status: SlashStatus,
+}
+
+/// A queued admin operation. Executable once the ledger timestamp reaches `eta`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminOp {
+    pub id: u64,
+    pub action: AdminAction,
+    pub proposed_at: u64,
+    pub eta: u64,
+}
+
+/// Storage layout version of this code. Bump when a release changes the storage layout.
+const CONTRACT_VERSION: u32 = 1;
+
+/// Maximum addresses accepted in one `blacklist_import` chunk.
+const MAX_IMPORT_CHUNK: u32 = 100;
+
+/// How `record_revenue_report` treats an existing report for the period.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReportMode {
+    /// Whole-period report; an existing one is replaced only if `override_existing`.
+    Full { override_existing: bool },
+    /// Added to the period's running total.
+    Partial,
+}
+
+/// Fixed-point scale of oracle rates (7 decimals, as Stellar assets).
+const PRICE_SCALE: i128 = 10_000_000;
+
+/// Default appeal window after `slash_bond` (7 days).
+const DEFAULT_APPEAL_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Maximum investors paid in one `compensate` call.
+const MAX_COMPENSATION_BATCH: u32 = 50;
+
+/// Maximum NFT ids snapshotted or claimed in one call.
+const MAX_NFT_BATCH: u32 = 50;
+
+/// Fixed-point scale of `acc_per_share`.
+const ACC_SCALE: i128 = 1_000_000_000_000;
+
+/// Voting window for term amendments (7 days).
+const AMENDMENT_VOTING_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Delay between proposing and executing a share token migration (7 days).
+const TOKEN_MIGRATION_DELAY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Time after a deposit before undistributed escrow may be refunded (180 days).
+const ESCROW_REFUND_TIMEOUT_SECS: u64 = 180 * 24 * 60 * 60;
+
+/// Maximum payouts recorded in one `record_payouts` call.
+const MAX_PAYOUT_BATCH: u32 = 50;
+
+/// Maximum complaints an investor may file about one period.
+const MAX_COMPLAINTS_PER_PERIOD: u32 = 3;
+
+/// Maximum open complaints per offering.
+const MAX_OPEN_COMPLAINTS: u32 = 100;
+
+/// Maximum reporters in an offering's reporter committee.
+const MAX_REPORTERS: u32 = 10;
+
+/// Maximum operations in one `batch` call.
+const MAX_BATCH_OPS: u32 = 20;
+
+/// Maximum tranches in a period release schedule.
+const MAX_RELEASE_TRANCHES: u32 = 10;
+
+/// Maximum report listeners per offering.
+const MAX_REPORT_LISTENERS: u32 = 10;
+
+/// Maximum number of offerings returned in a single page.
+const MAX_PAGE_LIMIT: u32 = 20;
+
+/// Maximum distinct non-zero jurisdiction codes per offering.
+const MAX_JURISDICTION_CODES: u32 = 50;
+
+/// Maximum number of periods that can be claimed in a single transaction.
+/// Keeps compute costs predictable within Soroban limits.
+const MAX_CLAIM_PERIODS: u32 = 50;
+
+/// Number of claim records stored per claim history page.
+const CLAIM_HISTORY_PAGE_SIZE: u32 = 20;
+
+#[contract]
+pub struct RevoraRevenueShare;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Returns error if contract is frozen (#32) or a storage migration is pending.
+    /// Call at start of state-mutating entrypoints.
+    fn require_not_frozen(env: &Env) -> Result<(), RevoraError> {
+        let key = DataKey::Frozen;
+        if env
+            .storage()
+            .persistent()
+            .get::<DataKey, bool>(&key)
+            .unwrap_or(false)
+        {
+            return Err(RevoraError::ContractFrozen);
+        }
+        if Self::is_migration_pending(env.clone()) {
+            return Err(RevoraError::MigrationPending);
+        }
+        Ok(())
+    }
+
+    /// Initialize admin and optional safety role for emergency pause (#7).
+    /// Can only be called once; panics if already initialized.
+    pub fn initialize(env: Env, admin: Address, safety: Option<Address>) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Admin, &admin.clone());
+        Self::record_fresh_version(&env);
+        if let Some(s) = safety.clone() {
+            env.storage().persistent().set(&DataKey::Safety, &s);
+        }
+        env.storage().persistent().set(&DataKey::Paused, &false);
+        env.events().publish((EVENT_INIT, admin.clone()), (safety,));
+    }
+
+    /// Pause the contract (admin only). Idempotent.
     pub fn pause_admin(env: Env, caller: Address) {
         caller.require_auth();
         let admin: Address = env
@@ -269,16 +1875,28 @@ impl RevoraRevenueShare {
 
     /// Register a new revenue-share offering.
     /// Returns `Err(RevoraError::InvalidRevenueShareBps)` if revenue_share_bps > 10000.
+    /// A Closed offering may be registered again by its issuer: the previous series is
+    /// archived (`get_archived_series`) and the offering reopens under a new offering id.
+    /// Any other already-registered token fails with `OfferingAlreadyExists`.
     /// In testnet mode, bps validation is skipped to allow flexible testing.
     pub fn register_offering(
         env: Env,
         issuer: Address,
         token: Address,
         revenue_share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        issuer.require_auth();
+        Self::apply_register_offering(env, issuer, token, revenue_share_bps)
+    }
+
+    fn apply_register_offering(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        revenue_share_bps: u32,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
-        issuer.require_auth();
 
         // Skip bps validation in testnet mode
         let testnet_mode = Self::is_testnet_mode(env.clone());
@@ -289,18 +1907,125 @@ impl RevoraRevenueShare {
         let count_key = DataKey::OfferCount(issuer.clone());
         let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-        let offering = Offering {
-            issuer: issuer.clone(),
-            token: token.clone(),
-            revenue_share_bps,
-        };
+        if !Self::is_issuer_allowed(env.clone(), issuer.clone()) {
+            return Err(RevoraError::IssuerNotAllowed);
+        }
 
-        let item_key = DataKey::OfferItem(issuer.clone(), count);
-        env.storage().persistent().set(&item_key, &offering);
-        env.storage().persistent().set(&count_key, &(count + 1));
+        let registered_issuer: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OfferingIssuer(token.clone()));
+        if registered_issuer.is_none()
+            && env
+                .storage()
+                .persistent()
+                .has(&GovKey::MigratedTo(token.clone()))
+        {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+
+        // A Closed offering is registered again in place as its next series; any other
+        // registered token is taken
+        let reopening = registered_issuer.is_some()
+            && Self::get_offering_status(env.clone(), token.clone()) == OfferingStatus::Closed;
+        if registered_issuer.is_some() && !reopening {
+            return Err(RevoraError::OfferingAlreadyExists);
+        }
+        if reopening && registered_issuer != Some(issuer.clone()) {
+            return Err(RevoraError::Unauthorized);
+        }
+
+        let limit = Self::get_offering_limit(env.clone(), issuer.clone());
+        if !reopening && limit > 0 && count >= limit {
+            return Err(RevoraError::LimitReached);
+        }
+
+        // Registration fee (skipped for waived issuers and in testnet mode)
+        if !testnet_mode && !Self::has_fee_waiver(env.clone(), issuer.clone()) {
+            if let Some(fee) = Self::get_registration_fee(env.clone()) {
+                if fee.amount > 0 {
+                    let treasury = Self::route_protocol_fee(&env, &fee.token, &issuer, fee.amount)?;
+                    env.events().publish(
+                        (EVENT_FEE_PAID, issuer.clone(), token.clone()),
+                        (fee.token, fee.amount, treasury),
+                    );
+                }
+            }
+        }
+
+        // Issuer bond (deployment config; skipped in testnet mode), posted once per offering
+        if !testnet_mode && !reopening {
+            if let Some(bond) = Self::get_issuer_bond_config(env.clone()) {
+                if bond.amount > 0 {
+                    token::Client::new(&env, &bond.token).transfer(
+                        &issuer,
+                        &env.current_contract_address(),
+                        &bond.amount,
+                    );
+                    Self::track_held(&env, &bond.token, bond.amount);
+                    env.storage().persistent().set(
+                        &BondKey::OfferingBond(token.clone()),
+                        &OfferingBond {
+                            issuer: issuer.clone(),
+                            bond_token: bond.token.clone(),
+                            amount: bond.amount,
+                            slashed: 0,
+                        },
+                    );
+                    env.events().publish(
+                        (EVENT_BOND_POSTED, issuer.clone(), token.clone()),
+                        (bond.token, bond.amount),
+                    );
+                }
+            }
+        }
+
+        if reopening {
+            Self::open_next_series(&env, &issuer, &token, revenue_share_bps);
+        } else {
+            let now = env.ledger().timestamp();
+            let offering = Offering {
+                version: OFFERING_VERSION,
+                issuer: issuer.clone(),
+                token: token.clone(),
+                revenue_share_bps,
+                created_at: now,
+                created_ledger: env.ledger().sequence(),
+                updated_at: now,
+            };
+
+            let item_key = DataKey::OfferItem(issuer.clone(), count);
+            env.storage().persistent().set(&item_key, &offering);
+            env.storage().persistent().set(&count_key, &(count + 1));
+
+            env.storage()
+                .persistent()
+                .set(&DataKey::OfferingIssuer(token.clone()), &issuer);
+            // Status changes need a registered issuer, so a new offering starts Active
+            Self::status_index_insert(&env, &issuer, OfferingStatus::Active, &token);
+            Self::update_metrics(&env, |m| m.active_offerings += 1);
 
+            let offering_id: u64 = env
+                .storage()
+                .persistent()
+                .get(&IndexKey::LastOfferingId)
+                .unwrap_or(0)
+                + 1;
+            let store = env.storage().persistent();
+            store.set(&IndexKey::LastOfferingId, &offering_id);
+            store.set(
+                &IndexKey::OfferingById(offering_id),
+                &(issuer.clone(), token.clone()),
+            );
+            store.set(&IndexKey::OfferingIdOf(token.clone()), &offering_id);
+            // Nothing is deposited or finalized before registration
+            Self::write_state_hash(&env, &token, revenue_share_bps, None, None);
+            Self::deploy_offering_child(&env, &issuer, &token, offering_id);
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
         env.events().publish(
-            (symbol_short!("offer_reg"), issuer),
+            (symbol_short!("offer_reg"), issuer, offering_id),
             (token, revenue_share_bps),
         );
         Ok(())
@@ -310,8 +2035,7 @@ impl RevoraRevenueShare {
     pub fn get_offering(env: Env, issuer: Address, token: Address) -> Option<Offering> {
         let count = Self::get_offering_count(env.clone(), issuer.clone());
         for i in 0..count {
-            let item_key = DataKey::OfferItem(issuer.clone(), i);
-            let offering: Offering = env.storage().persistent().get(&item_key).unwrap();
+            let offering = Self::load_offering(&env, &issuer, i);
             if offering.token == token {
                 return Some(offering);
             }
@@ -319,6 +2043,16 @@ impl RevoraRevenueShare {
         None
     }
 
+    /// Fetch several offerings by (issuer, token) in one call; None for entries that are
+    /// not registered. Results are in the order of `entries`.
+    pub fn get_offerings_bulk(env: Env, entries: Vec<(Address, Address)>) -> Vec<Option<Offering>> {
+        let mut results = Vec::new(&env);
+        for (issuer, token) in entries.iter() {
+            results.push_back(Self::get_offering(env.clone(), issuer, token));
+        }
+        results
+    }
+
     /// List all offering tokens for an issuer.
     pub fn list_offerings(env: Env, issuer: Address) -> Vec<Address> {
         let (page, _) = Self::get_offerings_page(env.clone(), issuer.clone(), 0, MAX_PAGE_LIMIT);
@@ -329,729 +2063,8567 @@ impl RevoraRevenueShare {
         tokens
     }
 
-    /// Record a revenue report for an offering. Updates audit summary (#34).
-    /// Fails with `ConcentrationLimitExceeded` (#26) if concentration enforcement is on and current concentration exceeds limit.
-    /// In testnet mode, concentration enforcement is skipped.
-    /// `override_existing`: if true, allows overwriting a previously reported period.
-    pub fn report_revenue(
+    /// Designate (or clear with `None`) the offering's operator: a secondary admin who may
+    /// manage the blacklist and file reports, but cannot change terms or ownership.
+    /// Only the issuer may call.
+    pub fn set_offering_operator(
         env: Env,
         issuer: Address,
         token: Address,
-        amount: i128,
-        period_id: u64,
-        override_existing: bool,
+        operator: Option<Address>,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
-        Self::require_not_paused(&env);
         issuer.require_auth();
-
-        // Skip concentration enforcement in testnet mode
-        let testnet_mode = Self::is_testnet_mode(env.clone());
-        if !testnet_mode {
-            // Holder concentration guardrail (#26): reject if enforce and over limit
-            let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-            if let Some(config) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
-            {
-                if config.enforce && config.max_bps > 0 {
-                    let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
-                    let current: u32 = env.storage().persistent().get(&curr_key).unwrap_or(0);
-                    if current > config.max_bps {
-                        return Err(RevoraError::ConcentrationLimitExceeded);
-                    }
-                }
-            }
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
         }
+        let key = DataKey::OfferingOperator(token.clone());
+        match &operator {
+            Some(op) => env.storage().persistent().set(&key, op),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_OPERATOR_SET, issuer, token, offering_id), operator);
+        Ok(())
+    }
 
-        let blacklist = Self::get_blacklist(env.clone(), token.clone());
-
-        let key = DataKey::RevenueReports(issuer.clone(), token.clone());
-        let mut reports: Map<u64, (i128, u64)> = env
-            .storage()
+    /// Get the offering's operator, if any.
+    pub fn get_offering_operator(env: Env, token: Address) -> Option<Address> {
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
-        let current_timestamp = env.ledger().timestamp();
-
-        match reports.get(period_id) {
-            Some((existing_amount, _timestamp)) => {
-                if override_existing {
-                    reports.set(period_id, (amount, current_timestamp));
-                    env.storage().persistent().set(&key, &reports);
+            .get(&DataKey::OfferingOperator(token))
+    }
 
-                    env.events().publish(
-                        (EVENT_REVENUE_REPORT_OVERRIDE, issuer.clone(), token.clone()),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
-                } else {
-                    env.events().publish(
-                        (EVENT_REVENUE_REPORT_REJECTED, issuer.clone(), token.clone()),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
-                }
-            }
-            None => {
-                reports.set(period_id, (amount, current_timestamp));
-                env.storage().persistent().set(&key, &reports);
+    /// Commit the Merkle root of approved investors for an offering. Replacing the root
+    /// revokes slots claimed under the previous one. Issuer or operator only.
+    /// Leaves are `sha256(investor.to_xdr())`; parents hash the sorted pair of children.
+    pub fn set_whitelist_root(
+        env: Env,
+        caller: Address,
+        token: Address,
+        root: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_compliance_manager(&env, &caller, &token)?;
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::WhitelistRoot(token.clone()), &root);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_WL_ROOT, token, caller, offering_id), root);
+        Ok(())
+    }
 
-                env.events().publish(
-                    (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
-                    (amount, period_id, blacklist.clone()),
-                );
-            }
-        }
+    /// Get the committed whitelist root, if any.
+    pub fn get_whitelist_root(env: Env, token: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::WhitelistRoot(token))
+    }
 
-        // Backward-compatible event
-        env.events().publish(
-            (EVENT_REVENUE_REPORTED, issuer.clone(), token.clone()),
-            (amount, period_id, blacklist),
+    /// Register `investor` on the offering whitelist with a Merkle proof against the
+    /// committed root. Permissionless: the proof is the authorization, so an engine may
+    /// submit on the investor's behalf. Idempotent.
+    pub fn whitelist_claim_slot(
+        env: Env,
+        token: Address,
+        investor: Address,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        let root = Self::get_whitelist_root(env.clone(), token.clone())
+            .ok_or(RevoraError::InvalidProof)?;
+        let leaf = Self::address_leaf(&env, &investor);
+        if Self::merkle_root_from_proof(&env, leaf, &proof) != root {
+            return Err(RevoraError::InvalidProof);
+        }
+        Self::admit_investor(&env, &token, &investor)?;
+        env.storage().persistent().set(
+            &ComplianceKey::WhitelistedUnder(token.clone(), investor.clone()),
+            &root,
         );
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_WL_CLAIM, token, offering_id), investor);
+        Ok(())
+    }
 
-        // Audit log summary (#34): maintain per-offering total revenue and report count
-        let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
-        let mut summary: AuditSummary =
-            env.storage()
-                .persistent()
-                .get(&summary_key)
-                .unwrap_or(AuditSummary {
-                    total_revenue: 0,
-                    report_count: 0,
-                });
-        summary.total_revenue = summary.total_revenue.saturating_add(amount);
-        summary.report_count = summary.report_count.saturating_add(1);
-        env.storage().persistent().set(&summary_key, &summary);
+    /// Return true if `investor` claimed a slot under the offering's current root.
+    pub fn is_whitelisted(env: Env, token: Address, investor: Address) -> bool {
+        let root = match Self::get_whitelist_root(env.clone(), token.clone()) {
+            Some(r) => r,
+            None => return false,
+        };
+        env.storage()
+            .persistent()
+            .get::<_, BytesN<32>>(&ComplianceKey::WhitelistedUnder(token, investor))
+            .is_some_and(|claimed| claimed == root)
+    }
 
-        Ok(())
+    /// Global offering id of `token`, assigned sequentially (from 1) at first registration.
+    pub fn get_offering_id(env: Env, token: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&IndexKey::OfferingIdOf(token))
     }
 
-    /// Return the total number of offerings registered by `issuer`.
-    pub fn get_offering_count(env: Env, issuer: Address) -> u32 {
-        let count_key = DataKey::OfferCount(issuer);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+    /// Resolve a global offering id to its offering.
+    pub fn get_offering_by_id(env: Env, offering_id: u64) -> Option<Offering> {
+        let (issuer, token): (Address, Address) = env
+            .storage()
+            .persistent()
+            .get(&IndexKey::OfferingById(offering_id))?;
+        Self::get_offering(env, issuer, token)
     }
 
-    /// Return a page of offerings for `issuer`. Limit capped at MAX_PAGE_LIMIT (20).
-    pub fn get_offerings_page(
+    /// List full offering structs for an issuer, starting at `cursor`.
+    /// Limit capped at MAX_PAGE_LIMIT (20); 0 means the cap. Use `get_offering_count`
+    /// (or `get_offerings_page`) to discover whether more remain.
+    pub fn list_offerings_detailed(
         env: Env,
         issuer: Address,
-        start: u32,
+        cursor: u32,
         limit: u32,
-    ) -> (Vec<Offering>, Option<u32>) {
-        let count = Self::get_offering_count(env.clone(), issuer.clone());
+    ) -> Vec<Offering> {
+        let (page, _) = Self::get_offerings_page(env, issuer, cursor, limit);
+        page
+    }
 
+    /// Return a page of offering tokens for `issuer` currently in `status`.
+    /// Backed by per-status indexes updated on every status transition.
+    /// Limit capped at MAX_PAGE_LIMIT (20); 0 means the cap.
+    pub fn list_offerings_by_status(
+        env: Env,
+        issuer: Address,
+        status: OfferingStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let key = IndexKey::OfferingsByStatus(issuer, status);
+        let tokens: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let count = tokens.len();
+        if cursor >= count {
+            return (Vec::new(&env), None);
+        }
         let effective_limit = if limit == 0 || limit > MAX_PAGE_LIMIT {
             MAX_PAGE_LIMIT
         } else {
             limit
         };
-
-        if start >= count {
-            return (Vec::new(&env), None);
-        }
-
-        let end = core::cmp::min(start + effective_limit, count);
-        let mut results = Vec::new(&env);
-
-        for i in start..end {
-            let item_key = DataKey::OfferItem(issuer.clone(), i);
-            let offering: Offering = env.storage().persistent().get(&item_key).unwrap();
-            results.push_back(offering);
-        }
-
+        let end = core::cmp::min(cursor + effective_limit, count);
         let next_cursor = if end < count { Some(end) } else { None };
-        (results, next_cursor)
+        (tokens.slice(cursor..end), next_cursor)
     }
 
-    /// Add `investor` to the per-offering blacklist for `token`. Idempotent.
-    pub fn blacklist_add(
+    /// Record a revenue report for an offering. Updates audit summary (#34).
+    /// Fails with `ConcentrationLimitExceeded` (#26) if concentration enforcement is on and current concentration exceeds limit.
+    /// In testnet mode, concentration enforcement is skipped.
+    /// `override_existing`: if true, allows overwriting a previously reported period.
+    pub fn report_revenue(
         env: Env,
-        caller: Address,
+        issuer: Address,
         token: Address,
-        investor: Address,
+        amount: i128,
+        period_id: u64,
+        override_existing: bool,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
-        caller.require_auth();
+        issuer.require_auth();
+        Self::require_direct_reporting(&env, &token)?;
+        Self::record_revenue_report(
+            env,
+            issuer,
+            token,
+            amount,
+            period_id,
+            ReportMode::Full { override_existing },
+        )
+    }
+
+    /// Add a partial amount to a period's report (e.g. weekly figures for a monthly
+    /// period). Partials accumulate until `finalize_report` locks the sum.
+    /// Same guards and audit-summary effects as `report_revenue`.
+    pub fn report_partial_revenue(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        issuer.require_auth();
+        Self::require_direct_reporting(&env, &token)?;
+        Self::record_revenue_report(env, issuer, token, amount, period_id, ReportMode::Partial)
+    }
 
-        let key = DataKey::Blacklist(token.clone());
-        let mut map: Map<Address, bool> = env
+    /// Current (possibly partial) reported amount for a period, if any.
+    pub fn get_period_report(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Option<i128> {
+        let reports: Map<u64, (i128, u64)> = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&DataKey::RevenueReports(issuer, token))?;
+        reports.get(period_id).map(|(amount, _)| amount)
+    }
 
-        map.set(investor.clone(), true);
-        env.storage().persistent().set(&key, &map);
+    /// Lock a reported, fully funded period's amount and notify the offering's listeners.
+    /// For fiat-reported offerings the locked (and returned) amount is the payout-token
+    /// equivalent at the oracle rate; the rate used is stored (`get_fiat_conversion`).
+    /// Listener failures are ignored so one broken subscriber cannot block finalization.
+    pub fn finalize_report(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::finalize_period(env, issuer, token, period_id, false)
+    }
 
-        env.events()
-            .publish((EVENT_BL_ADD, token, caller), investor);
-        Ok(())
+    /// Finalize a period that is only partly funded, recording the unfunded remainder as
+    /// its shortfall (`get_period_funding`). Claims then pay from what was deposited.
+    pub fn finalize_report_short(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::finalize_period(env, issuer, token, period_id, true)
     }
 
-    /// Remove `investor` from the per-offering blacklist for `token`. Idempotent.
-    pub fn blacklist_remove(
+    fn finalize_period(
         env: Env,
-        caller: Address,
+        issuer: Address,
         token: Address,
-        investor: Address,
-    ) -> Result<(), RevoraError> {
+        period_id: u64,
+        allow_short: bool,
+    ) -> Result<i128, RevoraError> {
         Self::require_not_frozen(&env)?;
         Self::require_not_paused(&env);
-        caller.require_auth();
-
-        let key = DataKey::Blacklist(token.clone());
-        let mut map: Map<Address, bool> = env
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if Self::is_period_finalized(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodFinalized);
+        }
+        let reports: Map<u64, (i128, u64)> = env
             .storage()
             .persistent()
-            .get(&key)
+            .get(&DataKey::RevenueReports(issuer.clone(), token.clone()))
             .unwrap_or_else(|| Map::new(&env));
+        let (amount, _) = reports.get(period_id).ok_or(RevoraError::ReportNotFound)?;
+        if Self::get_period_state(env.clone(), token.clone(), period_id)
+            != Some(PeriodState::Deposited)
+        {
+            return Err(RevoraError::InvalidPeriodState);
+        }
+        if let Some(config) = Self::get_auditor(env.clone(), token.clone()) {
+            if config.require_attestation
+                && Self::get_attestation(env.clone(), token.clone(), period_id) != Some(amount)
+            {
+                return Err(RevoraError::AttestationRequired);
+            }
+        }
+        let amount = match Self::get_fiat_reporting(env.clone(), token.clone()) {
+            Some(config) => {
+                let rate = PriceOracleClient::new(&env, &config.oracle)
+                    .price(&config.currency, &config.payout_token);
+                if rate <= 0 {
+                    return Err(RevoraError::InvalidOraclePrice);
+                }
+                let conversion = FiatConversion {
+                    fiat_amount: amount,
+                    rate,
+                    payout_amount: amount.saturating_mul(rate) / PRICE_SCALE,
+                };
+                env.storage().persistent().set(
+                    &ReportKey::FiatConversion(token.clone(), period_id),
+                    &conversion,
+                );
+                conversion.payout_amount
+            }
+            None => amount,
+        };
+        let deposited: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        if deposited < amount {
+            if !allow_short {
+                return Err(RevoraError::InsufficientFunds);
+            }
+            let shortfall = amount - deposited;
+            let shortfall_key = ReportKey::Shortfall(token.clone(), period_id);
+            if !env.storage().persistent().has(&shortfall_key) {
+                Self::adjust_period_count(&env, ReportKey::OpenShortfallCount(token.clone()), true);
+            }
+            env.storage().persistent().set(&shortfall_key, &shortfall);
+            let offering_id = Self::offering_id_of(&env, &token);
+            env.events().publish(
+                (
+                    EVENT_FINALIZED_SHORT,
+                    issuer.clone(),
+                    token.clone(),
+                    offering_id,
+                ),
+                (period_id, shortfall),
+            );
+        }
+        env.storage().persistent().set(
+            &ReportKey::FinalizedPeriod(token.clone(), period_id),
+            &amount,
+        );
+        let (distributed, fee_taken) = Self::period_payouts(&env, &token, period_id);
+        env.storage().persistent().set(
+            &DataKey::PeriodSummary(token.clone(), period_id),
+            &PeriodSummary {
+                amount,
+                blacklist_hash: Self::get_blacklist_hash(env.clone(), token.clone()),
+                holder_count: Self::get_investor_count(env.clone(), token.clone()),
+                fee_taken,
+                distributed,
+                finalized_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&ReportKey::LastFinalized(token.clone()), &period_id);
+        let max_key = ReportKey::MaxFinalized(token.clone());
+        let highest: Option<u64> = env.storage().persistent().get(&max_key);
+        if highest.is_none_or(|h| period_id > h) {
+            env.storage().persistent().set(&max_key, &period_id);
+        }
+        Self::refresh_state_hash(&env, &token);
+        Self::set_period_state(&env, &token, period_id, PeriodState::Finalized);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REPORT_FINALIZED, issuer, token.clone(), offering_id),
+            (period_id, amount),
+        );
+        Self::emit_checkpoint(&env, &token, period_id);
+        for listener in Self::get_report_listeners(env.clone(), token.clone()).iter() {
+            let _ =
+                ReportListenerClient::new(&env, &listener).try_notify(&token, &period_id, &amount);
+        }
+        Ok(amount)
+    }
 
-        map.remove(investor.clone());
-        env.storage().persistent().set(&key, &map);
+    /// Audit summary persisted when a period was finalized, with payouts and withholding
+    /// to date. Removed if a ruling reopens the period.
+    pub fn get_period_summary(env: Env, token: Address, period_id: u64) -> Option<PeriodSummary> {
+        let mut summary: PeriodSummary = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodSummary(token.clone(), period_id))?;
+        (summary.distributed, summary.fee_taken) = Self::period_payouts(&env, &token, period_id);
+        Some(summary)
+    }
 
+    /// Configure (or clear with `None`) fiat-denominated reporting for an offering.
+    /// Only the issuer may call.
+    pub fn set_fiat_reporting(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        config: Option<FiatReportingConfig>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let key = ReportKey::FiatReporting(token.clone());
+        match &config {
+            Some(c) => env.storage().persistent().set(&key, c),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
         env.events()
-            .publish((EVENT_BL_REM, token, caller), investor);
+            .publish((EVENT_FIAT_CONFIG_SET, issuer, token, offering_id), config);
         Ok(())
     }
 
-    /// Returns `true` if `investor` is blacklisted for `token`'s offering.
-    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
-        let key = DataKey::Blacklist(token);
+    /// Get the offering's fiat reporting configuration, if any.
+    pub fn get_fiat_reporting(env: Env, token: Address) -> Option<FiatReportingConfig> {
         env.storage()
             .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.get(investor).unwrap_or(false))
-            .unwrap_or(false)
+            .get(&ReportKey::FiatReporting(token))
     }
 
-    /// Return all blacklisted addresses for `token`'s offering.
-    pub fn get_blacklist(env: Env, token: Address) -> Vec<Address> {
-        let key = DataKey::Blacklist(token);
+    /// Conversion applied when a fiat-reported period was finalized.
+    pub fn get_fiat_conversion(env: Env, token: Address, period_id: u64) -> Option<FiatConversion> {
         env.storage()
             .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.keys())
-            .unwrap_or_else(|| Vec::new(&env))
+            .get(&ReportKey::FiatConversion(token, period_id))
     }
 
-    // ── Holder concentration guardrail (#26) ───────────────────
-
-    /// Set per-offering concentration limit. Caller must be the offering issuer.
-    /// `max_bps`: max allowed single-holder share in basis points (0 = disable).
-    /// `enforce`: if true, report_revenue will fail when reported concentration exceeds max_bps.
-    pub fn set_concentration_limit(
+    /// Designate (or clear with `None`) the offering's auditor and whether finalization
+    /// requires their attestation. Only the issuer may call.
+    pub fn set_auditor(
         env: Env,
         issuer: Address,
         token: Address,
-        max_bps: u32,
-        enforce: bool,
+        auditor: Option<Address>,
+        require_attestation: bool,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         issuer.require_auth();
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::LimitReached); // reuse: "offering not found" semantics
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
         }
-        let key = DataKey::ConcentrationLimit(issuer, token);
+        let key = ReportKey::AuditorConfig(token.clone());
+        match &auditor {
+            Some(a) => env.storage().persistent().set(
+                &key,
+                &AuditorConfig {
+                    auditor: a.clone(),
+                    require_attestation,
+                },
+            ),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_AUDITOR_SET, issuer, token, offering_id),
+            (auditor, require_attestation),
+        );
+        Ok(())
+    }
+
+    /// Get the offering's auditor configuration, if any.
+    pub fn get_auditor(env: Env, token: Address) -> Option<AuditorConfig> {
         env.storage()
             .persistent()
-            .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
-        Ok(())
+            .get(&ReportKey::AuditorConfig(token))
     }
 
-    /// Report current top-holder concentration in bps. Emits warning event if over configured limit.
-    pub fn report_concentration(
+    /// Auditor co-signs the current reported amount for a period. A later change to the
+    /// report invalidates the attestation. Only the designated auditor may call.
+    pub fn attest_report(
         env: Env,
-        issuer: Address,
+        auditor: Address,
         token: Address,
-        concentration_bps: u32,
-    ) -> Result<(), RevoraError> {
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
         Self::require_not_frozen(&env)?;
-        issuer.require_auth();
-        let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
+        auditor.require_auth();
+        let config =
+            Self::get_auditor(env.clone(), token.clone()).ok_or(RevoraError::Unauthorized)?;
+        if config.auditor != auditor {
+            return Err(RevoraError::Unauthorized);
+        }
+        let issuer = Self::offering_issuer(&env, &token)?;
+        let amount = Self::get_period_report(env.clone(), issuer, token.clone(), period_id)
+            .ok_or(RevoraError::ReportNotFound)?;
+        env.storage().persistent().set(
+            &ReportKey::AuditorAttestation(token.clone(), period_id),
+            &amount,
+        );
         env.storage()
             .persistent()
-            .set(&curr_key, &concentration_bps);
+            .remove(&ReportKey::ReviewFlag(token.clone(), period_id));
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REPORT_ATTESTED, auditor, token, offering_id),
+            (period_id, amount),
+        );
+        Ok(amount)
+    }
 
-        let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-        if let Some(config) = env
-            .storage()
+    /// Amount the auditor attested for a period, if any.
+    pub fn get_attestation(env: Env, token: Address, period_id: u64) -> Option<i128> {
+        env.storage()
             .persistent()
-            .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
-        {
-            if config.max_bps > 0 && concentration_bps > config.max_bps {
-                env.events().publish(
-                    (EVENT_CONCENTRATION_WARNING, issuer, token),
-                    (concentration_bps, config.max_bps),
-                );
-            }
-        }
-        Ok(())
+            .get(&ReportKey::AuditorAttestation(token, period_id))
     }
 
-    /// Get concentration limit config for an offering.
-    pub fn get_concentration_limit(
+    /// Set the maximum deductions as bps of gross for structured reports. Part of the
+    /// offering's terms: only the issuer may call, and only before the first report.
+    pub fn set_deduction_cap(
         env: Env,
         issuer: Address,
         token: Address,
-    ) -> Option<ConcentrationLimitConfig> {
-        let key = DataKey::ConcentrationLimit(issuer, token);
-        env.storage().persistent().get(&key)
-    }
-
-    /// Get last reported concentration in bps for an offering.
-    pub fn get_current_concentration(env: Env, issuer: Address, token: Address) -> Option<u32> {
-        let key = DataKey::CurrentConcentration(issuer, token);
-        env.storage().persistent().get(&key)
+        max_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if max_bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        if Self::get_audit_summary(env.clone(), issuer.clone(), token.clone())
+            .is_some_and(|s| s.report_count > 0)
+        {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        env.storage()
+            .persistent()
+            .set(&ReportKey::DeductionCapBps(token.clone()), &max_bps);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_DEDUCTION_CAP_SET, issuer, token, offering_id),
+            max_bps,
+        );
+        Ok(())
     }
 
-    // ── Audit log summary (#34) ────────────────────────────────
-
-    /// Get per-offering audit summary (total revenue and report count).
-    pub fn get_audit_summary(env: Env, issuer: Address, token: Address) -> Option<AuditSummary> {
-        let key = DataKey::AuditSummary(issuer, token);
-        env.storage().persistent().get(&key)
+    /// Maximum deductions as bps of gross (10000 when not configured).
+    pub fn get_deduction_cap(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::DeductionCapBps(token))
+            .unwrap_or(10_000)
     }
 
-    // ── Configurable rounding (#44) ───────────────────────────
-
-    /// Set rounding mode for an offering's share calculations. Caller must be issuer.
-    pub fn set_rounding_mode(
+    /// Report gross revenue and deductions for a period; the net amount is recorded as
+    /// with `report_revenue`. Deductions above the cap are rejected; those within 10% of
+    /// the cap are flagged for auditor review (cleared by `attest_report`).
+    pub fn report_structured_revenue(
         env: Env,
         issuer: Address,
         token: Address,
-        mode: RoundingMode,
+        gross: i128,
+        deductions: i128,
+        period_id: u64,
+        override_existing: bool,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
         issuer.require_auth();
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::LimitReached);
+        Self::require_direct_reporting(&env, &token)?;
+        if gross < 0 || deductions < 0 || deductions > gross {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let cap_bps = Self::get_deduction_cap(env.clone(), token.clone()) as i128;
+        let deduction_bps = if gross == 0 {
+            0
+        } else {
+            deductions * 10_000 / gross
+        };
+        if deduction_bps > cap_bps {
+            return Err(RevoraError::DeductionCapExceeded);
+        }
+        Self::record_revenue_report(
+            env.clone(),
+            issuer.clone(),
+            token.clone(),
+            gross - deductions,
+            period_id,
+            ReportMode::Full { override_existing },
+        )?;
+        env.storage().persistent().set(
+            &ReportKey::StructuredReport(token.clone(), period_id),
+            &StructuredReport { gross, deductions },
+        );
+        let flag_key = ReportKey::ReviewFlag(token.clone(), period_id);
+        if cap_bps < 10_000 && deductions > 0 && deduction_bps * 10 >= cap_bps * 9 {
+            env.storage().persistent().set(&flag_key, &true);
+            let offering_id = Self::offering_id_of(&env, &token);
+            env.events().publish(
+                (EVENT_REVIEW_FLAGGED, issuer, token, offering_id),
+                (period_id, deduction_bps as u32, cap_bps as u32),
+            );
+        } else {
+            env.storage().persistent().remove(&flag_key);
         }
-        let key = DataKey::RoundingMode(issuer, token);
-        env.storage().persistent().set(&key, &mode);
         Ok(())
     }
 
-    /// Get rounding mode for an offering. Defaults to Truncation if not set.
-    pub fn get_rounding_mode(env: Env, issuer: Address, token: Address) -> RoundingMode {
-        let key = DataKey::RoundingMode(issuer, token);
+    /// Gross/deductions breakdown of a structured report, if any.
+    pub fn get_structured_report(
+        env: Env,
+        token: Address,
+        period_id: u64,
+    ) -> Option<StructuredReport> {
         env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(RoundingMode::Truncation)
+            .get(&ReportKey::StructuredReport(token, period_id))
     }
 
-    /// Compute share of `amount` at `revenue_share_bps` using the given rounding mode.
-    /// Guarantees: result between 0 and amount (inclusive); no loss of funds when summing shares if caller uses same mode.
-    pub fn compute_share(
-        _env: Env,
-        amount: i128,
-        revenue_share_bps: u32,
-        mode: RoundingMode,
-    ) -> i128 {
-        if revenue_share_bps > 10_000 {
-            return 0;
-        }
-        let bps = revenue_share_bps as i128;
-        let raw = amount.checked_mul(bps).unwrap_or(0);
-        let share = match mode {
-            RoundingMode::Truncation => raw.checked_div(10_000).unwrap_or(0),
-            RoundingMode::RoundHalfUp => {
-                let half = 5_000_i128;
-                let adjusted = if raw >= 0 {
-                    raw.saturating_add(half)
-                } else {
-                    raw.saturating_sub(half)
-                };
-                adjusted.checked_div(10_000).unwrap_or(0)
-            }
-        };
-        // Clamp to [min(0, amount), max(0, amount)] to avoid overflow semantics affecting bounds
-        let lo = core::cmp::min(0, amount);
-        let hi = core::cmp::max(0, amount);
-        core::cmp::min(core::cmp::max(share, lo), hi)
+    /// Return true if the period's deductions are flagged for auditor review.
+    pub fn is_flagged_for_review(env: Env, token: Address, period_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ReportKey::ReviewFlag(token, period_id))
     }
 
-    // ── Multi-period aggregated claims ───────────────────────────
-
-    /// Deposit revenue for a specific period of an offering.
-    ///
-    /// Transfers `amount` of `payment_token` from `issuer` to the contract.
-    /// The payment token is locked per offering on first deposit; subsequent
-    /// deposits must use the same payment token.
-    pub fn deposit_revenue(
+    /// Register (or clear with `None`) the ed25519 public key of the offering's off-chain
+    /// accounting system. Only the issuer may call.
+    pub fn set_accounting_key(
         env: Env,
         issuer: Address,
         token: Address,
-        payment_token: Address,
-        amount: i128,
-        period_id: u64,
+        public_key: Option<BytesN<32>>,
     ) -> Result<(), RevoraError> {
         Self::require_not_frozen(&env)?;
         issuer.require_auth();
-
-        // Verify offering exists
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::OfferingNotFound);
-        }
-
-        // Check period not already deposited
-        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-        if env.storage().persistent().has(&rev_key) {
-            return Err(RevoraError::PeriodAlreadyDeposited);
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
         }
-
-        // Store or validate payment token for this offering
-        let pt_key = DataKey::PaymentToken(token.clone());
-        if let Some(existing_pt) = env.storage().persistent().get::<DataKey, Address>(&pt_key) {
-            if existing_pt != payment_token {
-                return Err(RevoraError::PaymentTokenMismatch);
-            }
-        } else {
-            env.storage().persistent().set(&pt_key, &payment_token);
+        let key = ReportKey::AccountingKey(token.clone());
+        match &public_key {
+            Some(pk) => env.storage().persistent().set(&key, pk),
+            None => env.storage().persistent().remove(&key),
         }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_ACCOUNTING_KEY_SET, issuer, token, offering_id),
+            public_key,
+        );
+        Ok(())
+    }
 
-        // Transfer tokens from issuer to contract
-        let contract_addr = env.current_contract_address();
-        token::Client::new(&env, &payment_token).transfer(&issuer, &contract_addr, &amount);
-
-        // Store period revenue
-        env.storage().persistent().set(&rev_key, &amount);
-
-        // Store deposit timestamp for time-delayed claims (#27)
-        let deposit_time = env.ledger().timestamp();
-        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-        env.storage().persistent().set(&time_key, &deposit_time);
+    /// Get the offering's registered accounting-system key, if any.
+    pub fn get_accounting_key(env: Env, token: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::AccountingKey(token))
+    }
 
-        // Append to indexed period list
-        let count_key = DataKey::PeriodCount(token.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let entry_key = DataKey::PeriodEntry(token.clone(), count);
-        env.storage().persistent().set(&entry_key, &period_id);
-        env.storage().persistent().set(&count_key, &(count + 1));
+    /// Store an accounting-system attestation of a period's reported amount.
+    /// The signature covers `token.to_xdr() || period_id (u64 BE) || amount (i128 BE)`;
+    /// an invalid signature traps in the host. Permissionless: the signature is the
+    /// authorization. `amount` must equal the current report.
+    pub fn submit_signed_attestation(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        amount: i128,
+        signature: BytesN<64>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let public_key = Self::get_accounting_key(env.clone(), token.clone())
+            .ok_or(RevoraError::Unauthorized)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        let reported = Self::get_period_report(env.clone(), issuer, token.clone(), period_id)
+            .ok_or(RevoraError::ReportNotFound)?;
+        if reported != amount {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let message = Self::attestation_message(&env, &token, period_id, amount);
+        env.crypto()
+            .ed25519_verify(&public_key, &message, &signature);
 
+        let attestation = SignedAttestation {
+            amount,
+            public_key,
+            signature,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(
+            &ReportKey::SignedAttestation(token.clone(), period_id),
+            &attestation,
+        );
+        let offering_id = Self::offering_id_of(&env, &token);
         env.events().publish(
-            (EVENT_REV_DEPOSIT, issuer, token),
-            (payment_token, amount, period_id),
+            (EVENT_SIGNED_ATTESTATION, token, offering_id),
+            (period_id, amount),
         );
         Ok(())
     }
 
-    /// Set a holder's revenue share (in basis points) for an offering.
-    ///
-    /// Only the offering issuer may call this. `share_bps` must be <= 10000.
-    pub fn set_holder_share(
+    /// Stored accounting-system attestation for a period, if any.
+    pub fn get_signed_attestation(
         env: Env,
-        issuer: Address,
         token: Address,
-        holder: Address,
-        share_bps: u32,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-        issuer.require_auth();
+        period_id: u64,
+    ) -> Option<SignedAttestation> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::SignedAttestation(token, period_id))
+    }
 
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::OfferingNotFound);
+    /// Return true if the period's report has been finalized.
+    pub fn is_period_finalized(env: Env, token: Address, period_id: u64) -> bool {
+        if Self::get_highest_finalized_period(env.clone(), token.clone())
+            .is_none_or(|h| period_id > h)
+        {
+            return false;
         }
+        env.storage()
+            .persistent()
+            .has(&ReportKey::FinalizedPeriod(token, period_id))
+    }
 
-        if share_bps > 10_000 {
-            return Err(RevoraError::InvalidShareBps);
+    /// Subscribe a listener contract to `finalize_report` for an offering. The listener
+    /// authorizes its own registration. At most MAX_REPORT_LISTENERS (10). Idempotent.
+    pub fn register_listener(
+        env: Env,
+        listener: Address,
+        token: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        listener.require_auth();
+        Self::offering_issuer(&env, &token)?;
+        let key = ReportKey::ReportListeners(token.clone());
+        let mut listeners = Self::get_report_listeners(env.clone(), token.clone());
+        if listeners.contains(&listener) {
+            return Ok(());
         }
-
-        let key = DataKey::HolderShare(token.clone(), holder.clone());
-        env.storage().persistent().set(&key, &share_bps);
-
+        if listeners.len() >= MAX_REPORT_LISTENERS {
+            return Err(RevoraError::LimitReached);
+        }
+        listeners.push_back(listener.clone());
+        env.storage().persistent().set(&key, &listeners);
+        let offering_id = Self::offering_id_of(&env, &token);
         env.events()
-            .publish((EVENT_SHARE_SET, issuer, token), (holder, share_bps));
+            .publish((EVENT_LISTENER_ADDED, token, offering_id), listener);
         Ok(())
     }
 
-    /// Return a holder's share in basis points for an offering (0 if unset).
-    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
-        let key = DataKey::HolderShare(token, holder);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Remove a listener from an offering. Only admin may call. Idempotent. Timelocked as
+    /// kind 16.
+    pub fn remove_listener(env: Env, token: Address, listener: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::RemoveListener(token, listener))
     }
 
-    /// Claim aggregated revenue across multiple unclaimed periods.
-    ///
-    /// `max_periods` controls how many periods to process in one call
-    /// (0 = up to MAX_CLAIM_PERIODS). Returns the total payout amount.
-    ///
-    /// Aggregation semantics:
-    /// - Periods are processed in deposit order (sequential index).
-    /// - Each holder's payout per period = `period_revenue * share_bps / 10000`.
-    /// - The holder's claim index advances regardless of zero-value periods.
-    /// - Capped at MAX_CLAIM_PERIODS (50) per transaction for gas safety.
-    pub fn claim(
+    /// Listener contracts subscribed to an offering's finalized reports.
+    pub fn get_report_listeners(env: Env, token: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ReportListeners(token))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Record a revenue report on the issuer's behalf. Only the offering's operator may call.
+    /// Same semantics and events as `report_revenue` (events carry the issuer).
+    pub fn operator_report_revenue(
         env: Env,
-        holder: Address,
+        operator: Address,
         token: Address,
-        max_periods: u32,
-    ) -> Result<i128, RevoraError> {
-        holder.require_auth();
-
-        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
-            return Err(RevoraError::HolderBlacklisted);
+        amount: i128,
+        period_id: u64,
+        override_existing: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        let issuer = Self::offering_issuer(&env, &token)?;
+        if Self::get_offering_operator(env.clone(), token.clone()) != Some(operator) {
+            return Err(RevoraError::Unauthorized);
         }
+        Self::require_direct_reporting(&env, &token)?;
+        Self::record_revenue_report(
+            env,
+            issuer,
+            token,
+            amount,
+            period_id,
+            ReportMode::Full { override_existing },
+        )
+    }
 
-        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
-            return Err(RevoraError::NoPendingClaims);
+    fn record_revenue_report(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+        period_id: u64,
+        mode: ReportMode,
+    ) -> Result<(), RevoraError> {
+        // Skip concentration enforcement in testnet mode
+        let testnet_mode = Self::is_testnet_mode(env.clone());
+        if !testnet_mode {
+            // Holder concentration guardrail (#26): reject if enforce and over limit
+            let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
+            if let Some(config) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
+            {
+                if config.enforce && config.max_bps > 0 {
+                    let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
+                    let current: u32 = env.storage().persistent().get(&curr_key).unwrap_or(0);
+                    if current > config.max_bps {
+                        return Err(RevoraError::ConcentrationLimitExceeded);
+                    }
+                }
+            }
         }
 
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let blacklist = Self::blacklist_payload(&env, &token);
+        let offering_id = Self::offering_id_of(&env, &token);
 
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+        let key = DataKey::RevenueReports(issuer.clone(), token.clone());
+        let mut reports: Map<u64, (i128, u64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        let current_timestamp = env.ledger().timestamp();
 
-        if start_idx >= period_count {
-            return Err(RevoraError::NoPendingClaims);
+        if Self::is_period_finalized(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodFinalized);
+        }
+        match Self::get_offering_status(env.clone(), token.clone()) {
+            OfferingStatus::Active => {}
+            // A Suspended offering only takes the overdue reports reinstatement needs
+            OfferingStatus::Suspended
+                if Self::missed_periods(&env, &token)
+                    .1
+                    .is_some_and(|due| period_id <= due) => {}
+            _ => return Err(RevoraError::InvalidOfferingStatus),
         }
+        let schedule = Self::get_reporting_schedule(env.clone(), token.clone());
+        if let Some(schedule) = &schedule {
+            let period_start = schedule
+                .start_time
+                .saturating_add(schedule.period_secs.saturating_mul(period_id));
+            if period_start > current_timestamp {
+                return Err(RevoraError::FuturePeriod);
+            }
+        }
+        Self::require_monotonic_period(&env, &token, period_id)?;
+        Self::check_zero_report_policy(&env, &token, amount, period_id)?;
 
-        let effective_max = if max_periods == 0 || max_periods > MAX_CLAIM_PERIODS {
-            MAX_CLAIM_PERIODS
-        } else {
-            max_periods
+        let previous = reports.get(period_id);
+        let previous_amount = previous.map_or(0, |(a, _)| a);
+        let first_declaration = previous.is_none();
+        let stored = match reports.get(period_id) {
+            existing if mode == ReportMode::Partial => {
+                let total = existing.map_or(0, |(a, _)| a).saturating_add(amount);
+                reports.set(period_id, (total, current_timestamp));
+                env.storage().persistent().set(&key, &reports);
+                env.events().publish(
+                    (
+                        EVENT_REVENUE_REPORT_PARTIAL,
+                        issuer.clone(),
+                        token.clone(),
+                        offering_id,
+                    ),
+                    (amount, period_id, total),
+                );
+                true
+            }
+            Some((existing_amount, _timestamp)) => {
+                if mode
+                    == (ReportMode::Full {
+                        override_existing: true,
+                    })
+                {
+                    reports.set(period_id, (amount, current_timestamp));
+                    env.storage().persistent().set(&key, &reports);
+                    env.events().publish(
+                        (
+                            EVENT_REVENUE_REPORT_OVERRIDE,
+                            issuer.clone(),
+                            token.clone(),
+                            offering_id,
+                        ),
+                        (amount, period_id, existing_amount, blacklist),
+                    );
+                    true
+                } else {
+                    env.events().publish(
+                        (
+                            EVENT_REVENUE_REPORT_REJECTED,
+                            issuer.clone(),
+                            token.clone(),
+                            offering_id,
+                        ),
+                        (amount, period_id, existing_amount, blacklist),
+                    );
+                    false
+                }
+            }
+            None => {
+                reports.set(period_id, (amount, current_timestamp));
+                env.storage().persistent().set(&key, &reports);
+                env.events().publish(
+                    (
+                        EVENT_REVENUE_REPORT_INITIAL,
+                        issuer.clone(),
+                        token.clone(),
+                        offering_id,
+                    ),
+                    (amount, period_id, blacklist),
+                );
+                true
+            }
         };
-        let end_idx = core::cmp::min(start_idx + effective_max, period_count);
-
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
-        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
-        let now = env.ledger().timestamp();
 
-        let mut total_payout: i128 = 0;
-        let mut claimed_periods = Vec::new(&env);
-        let mut last_claimed_idx = start_idx;
+        // Late-report flag: reports past period end + grace window are recorded as late
+        if stored {
+            let reported = reports.get(period_id).map_or(0, |(a, _)| a);
+            Self::update_metrics(&env, |m| {
+                m.total_reported = m
+                    .total_reported
+                    .saturating_add(reported.saturating_sub(previous_amount))
+            });
+            if first_declaration && Self::get_penalty_terms(env.clone(), token.clone()).is_some() {
+                env.storage().persistent().set(
+                    &ReportKey::DeclaredAt(token.clone(), period_id),
+                    &current_timestamp,
+                );
+            }
 
-        for i in start_idx..end_idx {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
-                break;
+            let last_key = DataKey::LastReportedPeriod(token.clone());
+            let last: Option<u64> = env.storage().persistent().get(&last_key);
+            if last.is_none_or(|p| period_id > p) {
+                env.storage().persistent().set(&last_key, &period_id);
             }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+
+            if let Some(schedule) = schedule {
+                let deadline = Self::report_deadline(&schedule, period_id);
+                // A late override of an on-time report leaves the period on time
+                let filed_on_time = previous.is_some_and(|(_, filed_at)| filed_at <= deadline);
+                if current_timestamp > deadline && !filed_on_time {
+                    let late_key = DataKey::LateReport(token.clone(), period_id);
+                    env.storage().persistent().set(&late_key, &true);
+                    env.events().publish(
+                        (
+                            EVENT_REPORT_LATE,
+                            issuer.clone(),
+                            token.clone(),
+                            offering_id,
+                        ),
+                        (period_id, deadline),
+                    );
+                }
+            }
+        }
+
+        // Backward-compatible event
+        env.events().publish(
+            (
+                EVENT_REVENUE_REPORTED,
+                issuer.clone(),
+                token.clone(),
+                offering_id,
+            ),
+            (amount, period_id, blacklist),
+        );
+
+        // Audit log summary (#34): maintain per-offering total revenue and report count
+        let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
+        let mut summary: AuditSummary =
+            env.storage()
+                .persistent()
+                .get(&summary_key)
+                .unwrap_or(AuditSummary {
+                    total_revenue: 0,
+                    report_count: 0,
+                });
+        summary.total_revenue = summary.total_revenue.saturating_add(amount);
+        summary.report_count = summary.report_count.saturating_add(1);
+        env.storage().persistent().set(&summary_key, &summary);
+
+        Ok(())
+    }
+
+    /// Return the total number of offerings registered by `issuer`.
+    pub fn get_offering_count(env: Env, issuer: Address) -> u32 {
+        let count_key = DataKey::OfferCount(issuer);
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    /// Return a page of offerings for `issuer`. Limit capped at MAX_PAGE_LIMIT (20).
+    pub fn get_offerings_page(
+        env: Env,
+        issuer: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Offering>, Option<u32>) {
+        let count = Self::get_offering_count(env.clone(), issuer.clone());
+
+        let effective_limit = if limit == 0 || limit > MAX_PAGE_LIMIT {
+            MAX_PAGE_LIMIT
+        } else {
+            limit
+        };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+
+        for i in start..end {
+            results.push_back(Self::load_offering(&env, &issuer, i));
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Add `investor` to the per-offering blacklist for `token`. Idempotent.
+    pub fn blacklist_add(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::apply_blacklist_add(env, caller, token, investor)
+    }
+
+    fn apply_blacklist_add(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+
+        let folded = Self::fold_legacy_blacklist(&env, &token);
+        if Self::blacklist_insert(&env, &token, &investor) || folded {
+            Self::refresh_state_hash(&env, &token);
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_BL_ADD, token, caller, offering_id), investor);
+        Ok(())
+    }
+
+    /// Remove `investor` from the per-offering blacklist for `token`. Idempotent.
+    pub fn blacklist_remove(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::apply_blacklist_remove(env, caller, token, investor)
+    }
+
+    fn apply_blacklist_remove(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+
+        let folded = Self::fold_legacy_blacklist(&env, &token);
+        if Self::blacklist_delete(&env, &token, &investor) || folded {
+            Self::refresh_state_hash(&env, &token);
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_BL_REM, token, caller, offering_id), investor);
+        Ok(())
+    }
+
+    /// Load a large blacklist across transactions. Chunks must arrive in order starting at 0;
+    /// chunk 0 (re)starts an import of `total_chunks`. Each chunk holds at most
+    /// MAX_IMPORT_CHUNK (100) addresses. Issuer or operator only on registered offerings.
+    pub fn blacklist_import(
+        env: Env,
+        caller: Address,
+        token: Address,
+        chunk: Vec<Address>,
+        chunk_index: u32,
+        total_chunks: u32,
+    ) -> Result<BlacklistImport, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+
+        if total_chunks == 0 || chunk_index >= total_chunks || chunk.len() > MAX_IMPORT_CHUNK {
+            return Err(RevoraError::InvalidChunk);
+        }
+        let progress_key = ComplianceKey::BlacklistImport(token.clone());
+        let mut progress = if chunk_index == 0 {
+            BlacklistImport {
+                total_chunks,
+                next_chunk: 0,
+                imported: 0,
+                complete: false,
+            }
+        } else {
+            match env
+                .storage()
+                .persistent()
+                .get::<_, BlacklistImport>(&progress_key)
+            {
+                Some(p) if !p.complete && p.total_chunks == total_chunks => p,
+                _ => return Err(RevoraError::InvalidChunk),
+            }
+        };
+        if chunk_index != progress.next_chunk {
+            return Err(RevoraError::InvalidChunk);
+        }
+
+        let mut added = Self::fold_legacy_blacklist(&env, &token);
+        for investor in chunk.iter() {
+            added |= Self::blacklist_insert(&env, &token, &investor);
+        }
+        if added {
+            Self::refresh_state_hash(&env, &token);
+        }
+
+        progress.next_chunk += 1;
+        progress.imported += chunk.len();
+        progress.complete = progress.next_chunk == total_chunks;
+        env.storage().persistent().set(&progress_key, &progress);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_BL_IMPORT, token, caller, offering_id),
+            (chunk_index, total_chunks, chunk.len()),
+        );
+        Ok(progress)
+    }
+
+    /// Order-independent commitment to the current blacklist: XOR of `sha256(address XDR)`
+    /// over all members (all zeros when empty). Updated on every add/remove/import, so
+    /// engines can verify a cached copy before a distribution run.
+    pub fn get_blacklist_hash(env: Env, token: Address) -> BytesN<32> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::BlacklistHash(token))
+            .unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32]))
+    }
+
+    /// Single eligibility check used by `claim` and off-chain engines. Rules, in order:
+    /// offering closed, blacklist, investor freeze, whitelist (when a root is committed),
+    /// KYC (when required), blocked jurisdiction, and the external compliance module.
+    /// A Suspended offering takes no new reports or deposits but still pays out funded
+    /// periods.
+    pub fn is_eligible(env: Env, token: Address, investor: Address) -> EligibilityResult {
+        let store = env.storage().persistent();
+        if Self::get_offering_status(env.clone(), token.clone()) == OfferingStatus::Closed {
+            return EligibilityResult::OfferingClosed;
+        }
+        if Self::is_blacklisted(env.clone(), token.clone(), investor.clone()) {
+            return EligibilityResult::Blacklisted;
+        }
+        let frozen_key = ComplianceKey::InvestorFrozen(token.clone(), investor.clone());
+        if store.get(&frozen_key).unwrap_or(false) {
+            return EligibilityResult::InvestorFrozen;
+        }
+        if Self::get_whitelist_root(env.clone(), token.clone()).is_some()
+            && !Self::is_whitelisted(env.clone(), token.clone(), investor.clone())
+        {
+            return EligibilityResult::NotWhitelisted;
+        }
+        if store
+            .get(&ComplianceKey::KycRequired(token.clone()))
+            .unwrap_or(false)
+            && !store
+                .get(&ComplianceKey::KycVerified(token.clone(), investor.clone()))
+                .unwrap_or(false)
+        {
+            return EligibilityResult::KycMissing;
+        }
+        let jurisdiction =
+            Self::get_investor_jurisdiction(env.clone(), token.clone(), investor.clone());
+        if store
+            .get(&ComplianceKey::JurisdictionBlocked(
+                token.clone(),
+                jurisdiction,
+            ))
+            .unwrap_or(false)
+        {
+            return EligibilityResult::JurisdictionBlocked;
+        }
+        if let Some(module) = Self::get_compliance_module(env.clone(), token.clone()) {
+            let client = ComplianceModuleClient::new(&env, &module);
+            if !matches!(client.try_check(&investor, &token), Ok(Ok(true))) {
+                return EligibilityResult::ModuleRejected;
+            }
+        }
+        EligibilityResult::Eligible
+    }
+
+    /// Page through the offering roster (every investor ever admitted via whitelist slot
+    /// or holder share, in admission order) with each one's `is_eligible` outcome at the
+    /// current ledger. Limit capped at MAX_PAGE_LIMIT (20); 0 means the cap.
+    pub fn export_eligibility(
+        env: Env,
+        token: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<EligibilityRow>, Option<u32>) {
+        let store = env.storage().persistent();
+        let count: u32 = store
+            .get(&ComplianceKey::RosterLen(token.clone()))
+            .unwrap_or(0);
+        let mut rows = Vec::new(&env);
+        if cursor >= count {
+            return (rows, None);
+        }
+        let effective_limit = if limit == 0 || limit > MAX_PAGE_LIMIT {
+            MAX_PAGE_LIMIT
+        } else {
+            limit
+        };
+        let end = core::cmp::min(cursor + effective_limit, count);
+        for i in cursor..end {
+            let investor: Address = store
+                .get(&ComplianceKey::RosterEntry(token.clone(), i))
+                .unwrap();
+            let reason = Self::is_eligible(env.clone(), token.clone(), investor.clone());
+            rows.push_back(EligibilityRow {
+                investor,
+                eligible: reason == EligibilityResult::Eligible,
+                reason,
+            });
+        }
+        let next_cursor = if end < count { Some(end) } else { None };
+        (rows, next_cursor)
+    }
+
+    /// Number of investors on the offering roster (see `export_eligibility`).
+    pub fn get_roster_len(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::RosterLen(token))
+            .unwrap_or(0)
+    }
+
+    /// Require KYC-verified investors on an offering. Issuer or operator only.
+    pub fn set_kyc_required(
+        env: Env,
+        caller: Address,
+        token: Address,
+        required: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_compliance_manager(&env, &caller, &token)?;
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::KycRequired(token.clone()), &required);
+        Self::emit_compliance_set(&env, token, symbol_short!("kyc_req"), (required,));
+        Ok(())
+    }
+
+    /// Cap the number of distinct investors (whitelisted or holding a share) in an
+    /// offering; 0 removes the cap. Lowering it below the current count only blocks new
+    /// admissions. Issuer or operator only.
+    pub fn set_investor_cap(
+        env: Env,
+        caller: Address,
+        token: Address,
+        cap: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_compliance_manager(&env, &caller, &token)?;
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::InvestorCap(token.clone()), &cap);
+        Self::emit_compliance_set(&env, token, symbol_short!("inv_cap"), (cap,));
+        Ok(())
+    }
+
+    /// Investor cap of an offering (0 = unlimited).
+    pub fn get_investor_cap(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::InvestorCap(token))
+            .unwrap_or(0)
+    }
+
+    /// Distinct investors counted towards the offering's cap.
+    pub fn get_investor_count(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::InvestorCount(token))
+            .unwrap_or(0)
+    }
+
+    /// Count `investor` towards the offering's cap; no-op if already counted.
+    fn admit_investor(env: &Env, token: &Address, investor: &Address) -> Result<(), RevoraError> {
+        let admitted_key = ComplianceKey::InvestorAdmitted(token.clone(), investor.clone());
+        if env.storage().persistent().has(&admitted_key) {
+            return Ok(());
+        }
+        let count = Self::get_investor_count(env.clone(), token.clone());
+        let cap = Self::get_investor_cap(env.clone(), token.clone());
+        if cap > 0 && count >= cap {
+            return Err(RevoraError::InvestorCapReached);
+        }
+        env.storage().persistent().set(&admitted_key, &true);
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::InvestorCount(token.clone()), &(count + 1));
+        Self::enroll_investor(env, token, investor);
+        Self::note_investor(env, investor);
+        Ok(())
+    }
+
+    /// Append `investor` to the offering roster read by `export_eligibility`.
+    /// Append-only: released investors keep their position.
+    fn enroll_investor(env: &Env, token: &Address, investor: &Address) {
+        let store = env.storage().persistent();
+        let slot_key = ComplianceKey::RosterSlot(token.clone(), investor.clone());
+        if store.has(&slot_key) {
+            return;
+        }
+        let len_key = ComplianceKey::RosterLen(token.clone());
+        let len: u32 = store.get(&len_key).unwrap_or(0);
+        store.set(&ComplianceKey::RosterEntry(token.clone(), len), investor);
+        store.set(&slot_key, &len);
+        store.set(&len_key, &(len + 1));
+    }
+
+    /// Stop counting `investor` towards the offering's cap; no-op if not counted.
+    fn release_investor(env: &Env, token: &Address, investor: &Address) {
+        let admitted_key = ComplianceKey::InvestorAdmitted(token.clone(), investor.clone());
+        if !env.storage().persistent().has(&admitted_key) {
+            return;
+        }
+        env.storage().persistent().remove(&admitted_key);
+        let count = Self::get_investor_count(env.clone(), token.clone());
+        env.storage().persistent().set(
+            &ComplianceKey::InvestorCount(token.clone()),
+            &count.saturating_sub(1),
+        );
+    }
+
+    /// Record an investor's KYC verification. Issuer or operator only.
+    pub fn set_kyc_status(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        verified: bool,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::apply_set_kyc_status(env, caller, token, investor, verified)
+    }
+
+    fn apply_set_kyc_status(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        verified: bool,
+    ) -> Result<(), RevoraError> {
+        Self::check_compliance_manager(&env, &caller, &token)?;
+        env.storage().persistent().set(
+            &ComplianceKey::KycVerified(token.clone(), investor.clone()),
+            &verified,
+        );
+        Self::emit_compliance_set(&env, token, symbol_short!("kyc"), (investor, verified));
+        Ok(())
+    }
+
+    /// Exclude (or re-admit) a jurisdiction code from an offering. Issuer or operator only.
+    pub fn set_jurisdiction_blocked(
+        env: Env,
+        caller: Address,
+        token: Address,
+        jurisdiction: u32,
+        blocked: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_compliance_manager(&env, &caller, &token)?;
+        Self::note_jurisdiction(&env, &token, jurisdiction)?;
+        env.storage().persistent().set(
+            &ComplianceKey::JurisdictionBlocked(token.clone(), jurisdiction),
+            &blocked,
+        );
+        Self::emit_compliance_set(&env, token, symbol_short!("juris"), (jurisdiction, blocked));
+        Ok(())
+    }
+
+    /// Freeze (or unfreeze) a single investor on an offering. Issuer or operator only.
+    pub fn set_investor_frozen(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        frozen: bool,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::apply_set_investor_frozen(env, caller, token, investor, frozen)
+    }
+
+    fn apply_set_investor_frozen(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        frozen: bool,
+    ) -> Result<(), RevoraError> {
+        Self::check_compliance_manager(&env, &caller, &token)?;
+        env.storage().persistent().set(
+            &ComplianceKey::InvestorFrozen(token.clone(), investor.clone()),
+            &frozen,
+        );
+        Self::emit_compliance_set(&env, token, symbol_short!("freeze"), (investor, frozen));
+        Ok(())
+    }
+
+    /// Register (or clear with `None`) an external compliance contract implementing
+    /// `ComplianceModule::check`. Issuer or operator only.
+    pub fn set_compliance_module(
+        env: Env,
+        caller: Address,
+        token: Address,
+        module: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_compliance_manager(&env, &caller, &token)?;
+        let key = ComplianceKey::ComplianceModule(token.clone());
+        match &module {
+            Some(m) => env.storage().persistent().set(&key, m),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_COMPLIANCE_MODULE, token, caller, offering_id),
+            module,
+        );
+        Ok(())
+    }
+
+    /// Get the offering's external compliance module, if any.
+    pub fn get_compliance_module(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::ComplianceModule(token))
+    }
+
+    /// Progress of the latest chunked blacklist import, if any.
+    pub fn get_blacklist_import(env: Env, token: Address) -> Option<BlacklistImport> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::BlacklistImport(token))
+    }
+
+    /// Returns `true` if `investor` is blacklisted for `token`'s offering.
+    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ComplianceKey::BlacklistMember(
+                token.clone(),
+                investor.clone(),
+            ))
+            || Self::legacy_blacklist(&env, &token).is_some_and(|m| m.contains_key(investor))
+    }
+
+    /// Return all blacklisted addresses for `token`'s offering.
+    pub fn get_blacklist(env: Env, token: Address) -> Vec<Address> {
+        let store = env.storage().persistent();
+        let mut members = Vec::new(&env);
+        for slot in 0..Self::blacklist_len(&env, &token) {
+            if let Some(investor) = store.get(&ComplianceKey::BlacklistSlot(token.clone(), slot)) {
+                members.push_back(investor);
+            }
+        }
+        if let Some(legacy) = Self::legacy_blacklist(&env, &token) {
+            members.append(&legacy.keys());
+        }
+        members
+    }
+
+    // ── Holder concentration guardrail (#26) ───────────────────
+
+    /// Set per-offering concentration limit. Caller must be the offering issuer.
+    /// `max_bps`: max allowed single-holder share in basis points (0 = disable).
+    /// `enforce`: if true, report_revenue will fail when reported concentration exceeds max_bps.
+    pub fn set_concentration_limit(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        max_bps: u32,
+        enforce: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::LimitReached); // reuse: "offering not found" semantics
+        }
+        let key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CONC_LIMIT_SET, issuer, token, offering_id),
+            (max_bps, enforce),
+        );
+        Ok(())
+    }
+
+    /// Report current top-holder concentration in bps. Emits warning event if over configured limit.
+    pub fn report_concentration(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        concentration_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
+        env.storage()
+            .persistent()
+            .set(&curr_key, &concentration_bps);
+
+        let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
+        if let Some(config) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
+        {
+            if config.max_bps > 0 && concentration_bps > config.max_bps {
+                let offering_id = Self::offering_id_of(&env, &token);
+                env.events().publish(
+                    (EVENT_CONCENTRATION_WARNING, issuer, token, offering_id),
+                    (concentration_bps, config.max_bps),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Get concentration limit config for an offering.
+    pub fn get_concentration_limit(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Option<ConcentrationLimitConfig> {
+        let key = DataKey::ConcentrationLimit(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get last reported concentration in bps for an offering.
+    pub fn get_current_concentration(env: Env, issuer: Address, token: Address) -> Option<u32> {
+        let key = DataKey::CurrentConcentration(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    // ── Audit log summary (#34) ────────────────────────────────
+
+    /// Get per-offering audit summary (total revenue and report count).
+    pub fn get_audit_summary(env: Env, issuer: Address, token: Address) -> Option<AuditSummary> {
+        let key = DataKey::AuditSummary(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    // ── Configurable rounding (#44) ───────────────────────────
+
+    /// Set rounding mode for an offering's share calculations. Caller must be issuer.
+    pub fn set_rounding_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        mode: RoundingMode,
+    ) -> Result<(), RevoraError> {
+        issuer.require_auth();
+        Self::apply_set_rounding_mode(env, issuer, token, mode)
+    }
+
+    fn apply_set_rounding_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        mode: RoundingMode,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::LimitReached);
+        }
+        let key = DataKey::RoundingMode(issuer.clone(), token.clone());
+        env.storage().persistent().set(&key, &mode);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_ROUNDING_SET, issuer, token, offering_id), mode);
+        Ok(())
+    }
+
+    /// Get rounding mode for an offering. Defaults to Truncation if not set.
+    pub fn get_rounding_mode(env: Env, issuer: Address, token: Address) -> RoundingMode {
+        let key = DataKey::RoundingMode(issuer, token);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(RoundingMode::Truncation)
+    }
+
+    /// Compute share of `amount` at `revenue_share_bps` using the given rounding mode.
+    /// Guarantees: result between 0 and amount (inclusive); no loss of funds when summing shares if caller uses same mode.
+    pub fn compute_share(
+        _env: Env,
+        amount: i128,
+        revenue_share_bps: u32,
+        mode: RoundingMode,
+    ) -> i128 {
+        if revenue_share_bps > 10_000 {
+            return 0;
+        }
+        let bps = revenue_share_bps as i128;
+        let raw = amount.checked_mul(bps).unwrap_or(0);
+        let share = match mode {
+            RoundingMode::Truncation => raw.checked_div(10_000).unwrap_or(0),
+            RoundingMode::RoundHalfUp => {
+                let half = 5_000_i128;
+                let adjusted = if raw >= 0 {
+                    raw.saturating_add(half)
+                } else {
+                    raw.saturating_sub(half)
+                };
+                adjusted.checked_div(10_000).unwrap_or(0)
+            }
+        };
+        // Clamp to [min(0, amount), max(0, amount)] to avoid overflow semantics affecting bounds
+        let lo = core::cmp::min(0, amount);
+        let hi = core::cmp::max(0, amount);
+        core::cmp::min(core::cmp::max(share, lo), hi)
+    }
+
+    // ── Multi-period aggregated claims ───────────────────────────
+
+    /// Deposit revenue for a specific period of an offering.
+    ///
+    /// Transfers `amount` of `payment_token` from `issuer` to the contract.
+    /// The payment token is locked per offering on first deposit; subsequent
+    /// deposits must use the same payment token. A declared period may be funded in
+    /// installments up to its declared amount; its claims open once fully funded.
+    /// Any SEP-41 token works, including the native XLM Stellar Asset Contract: only
+    /// `transfer` is used, never admin functions.
+    pub fn deposit_revenue(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        payment_token: Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+
+        // Verify offering exists
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        Self::deposit_from(&env, &issuer, &token, &payment_token, amount, period_id)
+    }
+
+    /// Fund a period from the offering's funding source (e.g. a treasury multisig) rather
+    /// than the issuer. Same rules and events as `deposit_revenue`, with `funder` as the
+    /// payer; declaration stays with the issuer (`report_revenue`).
+    pub fn fund_period(
+        env: Env,
+        funder: Address,
+        token: Address,
+        payment_token: Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        funder.require_auth();
+        Self::offering_issuer(&env, &token)?;
+        if Self::get_funding_source(env.clone(), token.clone()) != Some(funder.clone()) {
+            return Err(RevoraError::Unauthorized);
+        }
+        Self::deposit_from(&env, &funder, &token, &payment_token, amount, period_id)
+    }
+
+    /// Designate (or clear with `None`) the address allowed to call `fund_period`.
+    /// Only the issuer may call.
+    pub fn set_funding_source(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        funder: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let key = ReportKey::FundingSource(token.clone());
+        match &funder {
+            Some(f) => env.storage().persistent().set(&key, f),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_FUNDING_SOURCE_SET, issuer, token, offering_id),
+            funder,
+        );
+        Ok(())
+    }
+
+    /// The offering's funding source, if any.
+    pub fn get_funding_source(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::FundingSource(token))
+    }
+
+    /// Move a period's funds from `payer` into escrow.
+    fn deposit_from(
+        env: &Env,
+        payer: &Address,
+        token: &Address,
+        payment_token: &Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        let env = env.clone();
+        let payer = payer.clone();
+        let token = token.clone();
+        let payment_token = payment_token.clone();
+        if Self::get_offering_status(env.clone(), token.clone()) != OfferingStatus::Active {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+
+        // Check period not already deposited, unless it is an installment towards the
+        // declared amount
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let deposited: Option<i128> = env.storage().persistent().get(&rev_key);
+        if let Some(deposited) = deposited {
+            if !Self::awaiting_installments(&env, &token, period_id, deposited) {
+                return Err(RevoraError::PeriodAlreadyDeposited);
+            }
+            let remaining = Self::funding_target(&env, &token, period_id)
+                .map_or(i128::MAX, |target| target - deposited);
+            if amount <= 0 || amount > remaining {
+                return Err(RevoraError::InvalidAmount);
+            }
+        }
+
+        // Store or validate payment token for this offering
+        let pt_key = DataKey::PaymentToken(token.clone());
+        if let Some(existing_pt) = env.storage().persistent().get::<DataKey, Address>(&pt_key) {
+            if existing_pt != payment_token {
+                return Err(RevoraError::PaymentTokenMismatch);
+            }
+        } else {
+            env.storage().persistent().set(&pt_key, &payment_token);
+            Self::refresh_state_hash(&env, &token);
+        }
+
+        if let Some(deposited) = deposited {
+            token::Client::new(&env, &payment_token).transfer(
+                &payer,
+                &env.current_contract_address(),
+                &amount,
+            );
+            env.storage()
+                .persistent()
+                .set(&rev_key, &deposited.saturating_add(amount));
+            Self::adjust_escrow(&env, &token, amount);
+            if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods {
+                Self::acc_credit(&env, &token, amount);
+            }
+            let offering_id = Self::offering_id_of(&env, &token);
+            env.events().publish(
+                (EVENT_REV_DEPOSIT, payer, token, offering_id),
+                (payment_token, amount, period_id),
+            );
+            return Ok(());
+        }
+
+        Self::set_period_state(&env, &token, period_id, PeriodState::Deposited);
+
+        // Late-funding penalty is collected on top of the principal and paid out with it
+        let penalty = Self::get_penalty_owed(env.clone(), token.clone(), period_id);
+        let amount = amount.saturating_add(penalty);
+        if penalty > 0 {
+            env.storage()
+                .persistent()
+                .set(&ReportKey::PenaltyPaid(token.clone(), period_id), &penalty);
+            let offering_id = Self::offering_id_of(&env, &token);
+            env.events().publish(
+                (
+                    EVENT_PENALTY_PAID,
+                    payer.clone(),
+                    token.clone(),
+                    offering_id,
+                ),
+                (period_id, penalty),
+            );
+        }
+
+        // Transfer tokens from payer to contract
+        let contract_addr = env.current_contract_address();
+        token::Client::new(&env, &payment_token).transfer(&payer, &contract_addr, &amount);
+
+        // Store period revenue
+        env.storage().persistent().set(&rev_key, &amount);
+        Self::adjust_escrow(&env, &token, amount);
+
+        // Store deposit timestamp for time-delayed claims (#27)
+        let deposit_time = env.ledger().timestamp();
+        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+        env.storage().persistent().set(&time_key, &deposit_time);
+
+        // Append to indexed period list
+        let count_key = DataKey::PeriodCount(token.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let entry_key = DataKey::PeriodEntry(token.clone(), count);
+        env.storage().persistent().set(&entry_key, &period_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage()
+            .persistent()
+            .set(&ShareKey::PeriodIndex(token.clone(), period_id), &count);
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods {
+            Self::acc_credit(&env, &token, amount);
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REV_DEPOSIT, payer, token, offering_id),
+            (payment_token, amount, period_id),
+        );
+        Ok(())
+    }
+
+    /// Set a holder's revenue share (in basis points) for an offering.
+    ///
+    /// Only the offering issuer may call this. `share_bps` must be <= 10000.
+    pub fn set_holder_share(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        issuer.require_auth();
+        Self::apply_set_holder_share(env, issuer, token, holder, share_bps)
+    }
+
+    fn apply_set_holder_share(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        if share_bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+
+        Self::write_holder_share(&env, &token, &holder, share_bps)?;
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SHARE_SET, issuer, token, offering_id),
+            (holder, share_bps),
+        );
+        Ok(())
+    }
+
+    /// Return a holder's share in basis points for an offering (0 if unset).
+    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
+        let key = DataKey::HolderShare(token, holder);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Claim aggregated revenue across multiple unclaimed periods.
+    ///
+    /// `max_periods` controls how many periods to process in one call
+    /// (0 = up to MAX_CLAIM_PERIODS). Returns the payout amount net of tax withholding.
+    ///
+    /// Aggregation semantics:
+    /// - Periods are processed in deposit order (sequential index).
+    /// - Each holder's payout per period = `period_revenue * share_bps / 10000`.
+    /// - The holder's claim index advances regardless of zero-value periods.
+    /// - A period with a release schedule pays only its unlocked tranches; the claim
+    ///   index stays on it until the last tranche unlocks.
+    /// - Capped at MAX_CLAIM_PERIODS (50) per transaction for gas safety.
+    /// - Withholding for the holder's jurisdiction is deducted from the total and
+    ///   accumulated in the offering's withholding balance.
+    /// - The holder authorizes `(token, max_periods)`, so contract-account policies can
+    ///   scope approvals to specific offerings.
+    pub fn claim(
+        env: Env,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth_for_args((token.clone(), max_periods).into_val(&env));
+        Self::claim_periods(&env, &holder, &token, max_periods)
+    }
+
+    /// Claim body shared by `claim` and `claim_for`; authorization is done by the caller.
+    fn claim_periods(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        let env = env.clone();
+        let holder = holder.clone();
+        let token = token.clone();
+
+        match Self::is_eligible(env.clone(), token.clone(), holder.clone()) {
+            EligibilityResult::Eligible => {}
+            EligibilityResult::Blacklisted => return Err(RevoraError::HolderBlacklisted),
+            _ => return Err(RevoraError::NotEligible),
+        }
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods
+            || Self::is_nft_class(&env, &token)
+        {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        if share_bps == 0 {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+
+        if start_idx >= period_count {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let effective_max = if max_periods == 0 || max_periods > MAX_CLAIM_PERIODS {
+            MAX_CLAIM_PERIODS
+        } else {
+            max_periods
+        };
+        let end_idx = core::cmp::min(start_idx + effective_max, period_count);
+
+        let delay_key = DataKey::ClaimDelaySecs(token.clone());
+        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
+        let now = env.ledger().timestamp();
+
+        let mut total_payout: i128 = 0;
+        let mut claimed_periods = Vec::new(&env);
+        let mut last_claimed_idx = start_idx;
+        let mut frozen = false;
+        let mut released = false;
+        let paid_key = ShareKey::ReleasedPaid(token.clone(), holder.clone());
+        let mut released_paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+        let mut short_paid: Option<Map<u64, i128>> = None;
+
+        for i in start_idx..end_idx {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            if delay_secs > 0 {
+                let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+                let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+                if now < deposit_time.saturating_add(delay_secs) {
+                    break;
+                }
+            }
+            if Self::is_period_frozen(env.clone(), token.clone(), period_id) {
+                frozen = true;
+                break;
+            }
+            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
             let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            let payout = revenue * (share_bps as i128) / 10_000;
-            total_payout += payout;
+            if Self::awaiting_installments(&env, &token, period_id, revenue) {
+                break;
+            }
+            let payout = Self::period_payout(&env, &token, &holder, share_bps, period_id, revenue);
+            let (due, complete) =
+                Self::released_payout(&env, &token, period_id, payout, released_paid, now);
+            total_payout += due;
+            if !complete {
+                // Stay on this period until its last tranche unlocks
+                if due > 0 {
+                    env.storage()
+                        .persistent()
+                        .set(&paid_key, &(released_paid + due));
+                    claimed_periods.push_back(period_id);
+                    released = true;
+                }
+                break;
+            }
+            if released_paid > 0 {
+                env.storage().persistent().remove(&paid_key);
+                released_paid = 0;
+            }
+            if Self::has_shortfall(&env, &token, period_id) {
+                short_paid
+                    .get_or_insert_with(|| Self::get_short_paid(&env, &token, &holder))
+                    .set(period_id, payout);
+            }
             claimed_periods.push_back(period_id);
             last_claimed_idx = i + 1;
         }
-
-        if last_claimed_idx == start_idx {
-            return Err(RevoraError::ClaimDelayNotElapsed);
+
+        if last_claimed_idx == start_idx && !released {
+            if frozen {
+                return Err(RevoraError::PeriodFrozen);
+            }
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+
+        // Advance claim index only for periods actually claimed (respecting delay)
+        env.storage().persistent().set(&idx_key, &last_claimed_idx);
+        if let Some(short_paid) = short_paid {
+            env.storage().persistent().set(
+                &ShareKey::ShortPaid(token.clone(), holder.clone()),
+                &short_paid,
+            );
+        }
+        Self::note_claimed(
+            &env,
+            &token,
+            &holder,
+            start_idx,
+            last_claimed_idx,
+            share_bps,
+            released,
+        );
+
+        Ok(Self::pay_claim(
+            &env,
+            &holder,
+            &token,
+            total_payout,
+            claimed_periods,
+        ))
+    }
+
+    /// Pay out a claim: withhold tax, transfer the net amount, release escrow, and record
+    /// the claim in the holder's totals, history and `claim` event. Returns the net payout.
+    fn pay_claim(
+        env: &Env,
+        holder: &Address,
+        token: &Address,
+        total_payout: i128,
+        claimed_periods: Vec<u64>,
+    ) -> i128 {
+        let env = env.clone();
+        let holder = holder.clone();
+        let token = token.clone();
+        let now = env.ledger().timestamp();
+
+        // Tax withholding: deduct the jurisdiction's rate and hold it for remittance
+        let withheld = Self::withhold(&env, &token, &holder, total_payout);
+        let net_payout = total_payout - withheld;
+
+        // Transfer only if there is a positive payout
+        if net_payout > 0 {
+            let pt_key = DataKey::PaymentToken(token.clone());
+            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+            let contract_addr = env.current_contract_address();
+            let payout_to = Self::get_payout_address(env.clone(), token.clone(), holder.clone());
+            token::Client::new(&env, &payment_token).transfer(
+                &contract_addr,
+                &payout_to,
+                &net_payout,
+            );
+        }
+        if total_payout > 0 {
+            Self::adjust_escrow(&env, &token, -total_payout);
+        }
+
+        let claimed_key = DataKey::TotalClaimed(token.clone(), holder.clone());
+        let claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&claimed_key, &claimed.saturating_add(total_payout));
+        Self::record_distributed(&env, &holder, total_payout);
+
+        Self::append_claim_record(
+            &env,
+            &token,
+            &holder,
+            ClaimRecord {
+                amount: net_payout,
+                withheld,
+                period_count: claimed_periods.len(),
+                last_period_id: claimed_periods.last().unwrap_or(0),
+                timestamp: now,
+            },
+        );
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CLAIM, holder.clone(), token, offering_id),
+            (net_payout, claimed_periods, withheld),
+        );
+
+        net_payout
+    }
+
+    /// Return unclaimed period IDs for a holder on an offering.
+    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder);
+        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+
+        let mut periods = Vec::new(&env);
+        for i in start_idx..period_count {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            periods.push_back(period_id);
+        }
+        periods
+    }
+
+    /// Preview the total claimable amount for a holder without claiming.
+    /// Respects per-offering claim delay (#27): only sums periods past the delay, and only
+    /// the unlocked tranches of a period with a release schedule. Stops at a frozen period,
+    /// as `claim` does.
+    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        if share_bps == 0
+            || Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods
+            || Self::is_nft_class(&env, &token)
+        {
+            return 0;
+        }
+
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
+        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+
+        let delay_key = DataKey::ClaimDelaySecs(token.clone());
+        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
+        let now = env.ledger().timestamp();
+        let mut released_paid: i128 = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::ReleasedPaid(token.clone(), holder.clone()))
+            .unwrap_or(0);
+
+        let mut total: i128 = 0;
+        for i in start_idx..period_count {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
+                break;
+            }
+            if Self::is_period_frozen(env.clone(), token.clone(), period_id) {
+                break;
+            }
+            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
+            if Self::awaiting_installments(&env, &token, period_id, revenue) {
+                break;
+            }
+            let payout = Self::period_payout(&env, &token, &holder, share_bps, period_id, revenue);
+            let (due, complete) =
+                Self::released_payout(&env, &token, period_id, payout, released_paid, now);
+            total += due;
+            if !complete {
+                break;
+            }
+            released_paid = 0;
+        }
+        total
+    }
+
+    /// Return one page (up to 20 records, oldest first) of an investor's claim history.
+    pub fn get_claim_history(
+        env: Env,
+        token: Address,
+        investor: Address,
+        page: u32,
+    ) -> Vec<ClaimRecord> {
+        let key = DataKey::ClaimHistory(token, investor, page);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Total number of claim records stored for an investor on an offering.
+    pub fn get_claim_history_count(env: Env, token: Address, investor: Address) -> u32 {
+        let key = DataKey::ClaimHistoryCount(token, investor);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Account statement for an investor: lifetime earned and claimed, pending and
+    /// frozen amounts, share tier, and eligibility flags. Unclaimed amounts cover the
+    /// investor's next MAX_PAGE_LIMIT (20) unclaimed periods; later periods are counted
+    /// once earlier ones are claimed.
+    pub fn get_investor_statement(
+        env: Env,
+        token: Address,
+        investor: Address,
+    ) -> InvestorStatement {
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), investor.clone());
+        let blacklisted = Self::is_blacklisted(env.clone(), token.clone(), investor.clone());
+        let total_claimed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalClaimed(token.clone(), investor.clone()))
+            .unwrap_or(0);
+        let eligible = Self::is_eligible(env.clone(), token.clone(), investor.clone())
+            == EligibilityResult::Eligible;
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), investor.clone());
+        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+        let period_count = Self::get_period_count(env.clone(), token.clone());
+        let end = core::cmp::min(start_idx.saturating_add(MAX_PAGE_LIMIT), period_count);
+
+        // Sum the window's unclaimed periods; those claim would not reach yet are frozen
+        let mut unclaimed: i128 = 0;
+        let mut pending: i128 = 0;
+        if share_bps > 0 && start_idx < end {
+            let delay_secs: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ClaimDelaySecs(token.clone()))
+                .unwrap_or(0);
+            let now = env.ledger().timestamp();
+            let mut released_paid: i128 = env
+                .storage()
+                .persistent()
+                .get(&ShareKey::ReleasedPaid(token.clone(), investor.clone()))
+                .unwrap_or(0);
+            let mut claimable = eligible
+                && Self::get_accrual_mode(env.clone(), token.clone()) == AccrualMode::Periods
+                && !Self::is_nft_class(&env, &token);
+            for i in start_idx..end {
+                let entry_key = DataKey::PeriodEntry(token.clone(), i);
+                let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+                let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+                let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
+                let payout =
+                    Self::period_payout(&env, &token, &investor, share_bps, period_id, revenue);
+                unclaimed = unclaimed.saturating_add(payout - released_paid);
+                if claimable && delay_secs > 0 {
+                    let deposit_time: u64 = env
+                        .storage()
+                        .persistent()
+                        .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+                        .unwrap_or(0);
+                    claimable = now >= deposit_time.saturating_add(delay_secs);
+                }
+                if claimable
+                    && (Self::is_period_frozen(env.clone(), token.clone(), period_id)
+                        || Self::awaiting_installments(&env, &token, period_id, revenue))
+                {
+                    claimable = false;
+                }
+                if claimable {
+                    let (due, complete) =
+                        Self::released_payout(&env, &token, period_id, payout, released_paid, now);
+                    pending = pending.saturating_add(due);
+                    claimable = complete;
+                }
+                released_paid = 0;
+            }
+        }
+
+        InvestorStatement {
+            total_earned: total_claimed.saturating_add(unclaimed),
+            total_claimed,
+            pending,
+            frozen: unclaimed - pending,
+            share_bps,
+            blacklisted,
+            can_claim: share_bps > 0 && eligible,
+        }
+    }
+
+    /// Append a claim record to the investor's current history page.
+    fn append_claim_record(env: &Env, token: &Address, investor: &Address, record: ClaimRecord) {
+        let count_key = DataKey::ClaimHistoryCount(token.clone(), investor.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let page_key = DataKey::ClaimHistory(
+            token.clone(),
+            investor.clone(),
+            count / CLAIM_HISTORY_PAGE_SIZE,
+        );
+        let mut page: Vec<ClaimRecord> = env
+            .storage()
+            .persistent()
+            .get(&page_key)
+            .unwrap_or_else(|| Vec::new(env));
+        page.push_back(record);
+        env.storage().persistent().set(&page_key, &page);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    // ── Tax withholding ─────────────────────────────────────────
+
+    /// Set the withholding rate for a jurisdiction code. Only the issuer may call.
+    /// Jurisdiction 0 is the offering-wide default, used for investors whose
+    /// jurisdiction has no rate of its own.
+    pub fn set_withholding_rate(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        jurisdiction: u32,
+        bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if bps > 10_000 {
+            return Err(RevoraError::InvalidWithholdingBps);
+        }
+        Self::note_jurisdiction(&env, &token, jurisdiction)?;
+        let key = DataKey::WithholdingBps(token.clone(), jurisdiction);
+        env.storage().persistent().set(&key, &bps);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_WITHHOLDING_SET, issuer, token, offering_id),
+            (jurisdiction, bps),
+        );
+        Ok(())
+    }
+
+    /// Get the configured withholding rate for a jurisdiction code (0 if unset).
+    pub fn get_withholding_rate(env: Env, token: Address, jurisdiction: u32) -> u32 {
+        let key = DataKey::WithholdingBps(token, jurisdiction);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Assign an investor to a jurisdiction code for withholding. Only the issuer may call.
+    pub fn set_investor_jurisdiction(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        investor: Address,
+        jurisdiction: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        Self::note_jurisdiction(&env, &token, jurisdiction)?;
+        let key = DataKey::InvestorJurisdiction(token.clone(), investor.clone());
+        env.storage().persistent().set(&key, &jurisdiction);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_JURISDICTION_SET, issuer, token, offering_id),
+            (investor, jurisdiction),
+        );
+        Ok(())
+    }
+
+    /// Get an investor's jurisdiction code (0 if unassigned).
+    pub fn get_investor_jurisdiction(env: Env, token: Address, investor: Address) -> u32 {
+        let key = DataKey::InvestorJurisdiction(token, investor);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Withheld tax accumulated for a jurisdiction code and not yet remitted.
+    pub fn get_withheld_balance(env: Env, token: Address, jurisdiction: u32) -> i128 {
+        let key = DataKey::WithheldBalance(token, jurisdiction);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Set the address that receives remitted withholding for a jurisdiction code.
+    /// Only the offering issuer may call.
+    pub fn set_withholding_recipient(
+        env: Env,
+        token: Address,
+        jurisdiction_code: u32,
+        recipient: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        issuer.require_auth();
+        Self::note_jurisdiction(&env, &token, jurisdiction_code)?;
+        let key = DataKey::WithholdingRecipient(token.clone(), jurisdiction_code);
+        env.storage().persistent().set(&key, &recipient);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_WH_RECIPIENT_SET, issuer, token, offering_id),
+            (jurisdiction_code, recipient),
+        );
+        Ok(())
+    }
+
+    /// Get the remittance address for a jurisdiction code, if configured.
+    pub fn get_withholding_recipient(
+        env: Env,
+        token: Address,
+        jurisdiction_code: u32,
+    ) -> Option<Address> {
+        let key = DataKey::WithholdingRecipient(token, jurisdiction_code);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Forward all accumulated withholding for a jurisdiction code to its configured
+    /// recipient. Permissionless: funds can only go to the recipient set by the issuer.
+    /// Returns the amount remitted.
+    pub fn remit_withholding(
+        env: Env,
+        token: Address,
+        jurisdiction_code: u32,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let recipient =
+            Self::get_withholding_recipient(env.clone(), token.clone(), jurisdiction_code)
+                .ok_or(RevoraError::RecipientNotSet)?;
+        let wh_key = DataKey::WithheldBalance(token.clone(), jurisdiction_code);
+        let amount: i128 = env.storage().persistent().get(&wh_key).unwrap_or(0);
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
+        let contract_addr = env.current_contract_address();
+        token::Client::new(&env, &payment_token).transfer(&contract_addr, &recipient, &amount);
+        Self::track_held(&env, &payment_token, -amount);
+
+        env.storage().persistent().set(&wh_key, &0_i128);
+        let remitted_key = DataKey::WithholdingRemitted(token.clone(), jurisdiction_code);
+        let remitted: i128 = env.storage().persistent().get(&remitted_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&remitted_key, &remitted.saturating_add(amount));
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_WH_REMITTED, token, jurisdiction_code, offering_id),
+            (recipient, payment_token, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Lifetime withholding remitted for a jurisdiction code.
+    pub fn get_remitted_withholding(env: Env, token: Address, jurisdiction_code: u32) -> i128 {
+        let key = DataKey::WithholdingRemitted(token, jurisdiction_code);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Non-zero jurisdiction codes configured or assigned on an offering.
+    fn jurisdiction_codes(env: &Env, token: &Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::JurisdictionCodes(token.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Track a jurisdiction code used by an offering. Fails with `LimitReached` past
+    /// `MAX_JURISDICTION_CODES` distinct codes.
+    fn note_jurisdiction(env: &Env, token: &Address, code: u32) -> Result<(), RevoraError> {
+        if code == 0 {
+            return Ok(());
+        }
+        let mut codes = Self::jurisdiction_codes(env, token);
+        if codes.contains(code) {
+            return Ok(());
+        }
+        if codes.len() >= MAX_JURISDICTION_CODES {
+            return Err(RevoraError::LimitReached);
+        }
+        codes.push_back(code);
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::JurisdictionCodes(token.clone()), &codes);
+        Ok(())
+    }
+
+    /// Resolve the issuer of an offering token via the registration index.
+    fn offering_issuer(env: &Env, token: &Address) -> Result<Address, RevoraError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OfferingIssuer(token.clone()))
+            .ok_or(RevoraError::OfferingNotFound)
+    }
+
+    /// Blacklist changes on a registered offering are limited to its issuer and operator;
+    /// on an unregistered token, to the admin.
+    fn require_blacklist_manager(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        let issuer_key = DataKey::OfferingIssuer(token.clone());
+        let issuer: Address = match env.storage().persistent().get(&issuer_key) {
+            Some(i) => i,
+            None if Self::get_admin(env.clone()).as_ref() == Some(caller) => return Ok(()),
+            None => return Err(RevoraError::Unauthorized),
+        };
+        if *caller == issuer
+            || Self::get_offering_operator(env.clone(), token.clone()).as_ref() == Some(caller)
+        {
+            Ok(())
+        } else {
+            Err(RevoraError::Unauthorized)
+        }
+    }
+
+    /// Hash of an address: sha256 of its XDR. Whitelist leaves and blacklist hash terms.
+    fn address_leaf(env: &Env, investor: &Address) -> BytesN<32> {
+        env.crypto()
+            .sha256(&investor.clone().to_xdr(env))
+            .to_bytes()
+    }
+
+    /// XOR `investor`'s hash into the blacklist commitment (adds or removes it).
+    fn toggle_blacklist_hash(env: &Env, token: &Address, investor: &Address) {
+        let key = ComplianceKey::BlacklistHash(token.clone());
+        let mut acc = Self::get_blacklist_hash(env.clone(), token.clone()).to_array();
+        let leaf = Self::address_leaf(env, investor).to_array();
+        for (a, l) in acc.iter_mut().zip(leaf.iter()) {
+            *a ^= l;
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &BytesN::from_array(env, &acc));
+    }
+
+    /// Blacklist map left by an earlier storage layout, if not yet folded. Only read on
+    /// deployments migrated from layout 0.
+    fn legacy_blacklist(env: &Env, token: &Address) -> Option<Map<Address, bool>> {
+        let store = env.storage().persistent();
+        if !store.has(&AdminKey::LegacyBlacklists) {
+            return None;
+        }
+        store.get(&DataKey::Blacklist(token.clone()))
+    }
+
+    /// Move a legacy blacklist map into per-address entries. Returns true if one existed.
+    fn fold_legacy_blacklist(env: &Env, token: &Address) -> bool {
+        let Some(legacy) = Self::legacy_blacklist(env, token) else {
+            return false;
+        };
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Blacklist(token.clone()));
+        for investor in legacy.keys().iter() {
+            Self::blacklist_insert(env, token, &investor);
+        }
+        true
+    }
+
+    fn blacklist_len(env: &Env, token: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::BlacklistCount(token.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Add `investor` to `token`'s blacklist. Returns false if already a member.
+    fn blacklist_insert(env: &Env, token: &Address, investor: &Address) -> bool {
+        let store = env.storage().persistent();
+        let member_key = ComplianceKey::BlacklistMember(token.clone(), investor.clone());
+        if store.has(&member_key) {
+            return false;
+        }
+        let count = Self::blacklist_len(env, token);
+        store.set(&member_key, &count);
+        store.set(
+            &ComplianceKey::BlacklistSlot(token.clone(), count),
+            investor,
+        );
+        store.set(&ComplianceKey::BlacklistCount(token.clone()), &(count + 1));
+        Self::toggle_blacklist_hash(env, token, investor);
+        true
+    }
+
+    /// Remove `investor` from `token`'s blacklist, moving the last member into its slot.
+    /// Returns false if it was not a member.
+    fn blacklist_delete(env: &Env, token: &Address, investor: &Address) -> bool {
+        let store = env.storage().persistent();
+        let member_key = ComplianceKey::BlacklistMember(token.clone(), investor.clone());
+        let Some(slot) = store.get::<_, u32>(&member_key) else {
+            return false;
+        };
+        store.remove(&member_key);
+        let last = Self::blacklist_len(env, token).saturating_sub(1);
+        let last_key = ComplianceKey::BlacklistSlot(token.clone(), last);
+        if slot != last {
+            if let Some(moved) = store.get::<_, Address>(&last_key) {
+                store.set(&ComplianceKey::BlacklistSlot(token.clone(), slot), &moved);
+                store.set(&ComplianceKey::BlacklistMember(token.clone(), moved), &slot);
+            }
+        }
+        store.remove(&last_key);
+        let count_key = ComplianceKey::BlacklistCount(token.clone());
+        if last == 0 {
+            store.remove(&count_key);
+        } else {
+            store.set(&count_key, &last);
+        }
+        Self::toggle_blacklist_hash(env, token, investor);
+        true
+    }
+
+    /// Bytes signed by accounting systems: token XDR, then big-endian period and amount.
+    fn attestation_message(env: &Env, token: &Address, period_id: u64, amount: i128) -> Bytes {
+        let mut message = token.clone().to_xdr(env);
+        message.append(&Bytes::from_array(env, &period_id.to_be_bytes()));
+        message.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+        message
+    }
+
+    /// Fold a sorted-pair Merkle proof from `leaf` up to its root.
+    fn merkle_root_from_proof(env: &Env, leaf: BytesN<32>, proof: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut node = leaf;
+        for sibling in proof.iter() {
+            let (a, b) = if node.to_array() <= sibling.to_array() {
+                (node, sibling)
+            } else {
+                (sibling, node)
+            };
+            let mut buf = Bytes::from_array(env, &a.to_array());
+            buf.append(&Bytes::from_array(env, &b.to_array()));
+            node = env.crypto().sha256(&buf).to_bytes();
+        }
+        node
+    }
+
+    /// Compliance settings require a registered offering and its issuer or operator.
+    fn require_compliance_manager(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::check_compliance_manager(env, caller, token)
+    }
+
+    /// `require_compliance_manager` for a caller already authorized in this frame.
+    fn check_compliance_manager(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(env)?;
+        Self::offering_issuer(env, token)?;
+        Self::require_blacklist_manager(env, caller, token)
+    }
+
+    fn emit_compliance_set<D>(env: &Env, token: Address, rule: Symbol, data: D)
+    where
+        D: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        let offering_id = Self::offering_id_of(env, &token);
+        env.events()
+            .publish((EVENT_COMPLIANCE_SET, token, rule, offering_id), data);
+    }
+
+    /// Withhold the holder's jurisdiction rate from `gross` into the offering's
+    /// withholding balance. Returns the amount withheld.
+    fn withhold(env: &Env, token: &Address, holder: &Address, gross: i128) -> i128 {
+        if gross <= 0 {
+            return 0;
+        }
+        let jurisdiction =
+            Self::get_investor_jurisdiction(env.clone(), token.clone(), holder.clone());
+        let withholding_bps = Self::effective_withholding_bps(env, token, jurisdiction);
+        let withheld = gross * (withholding_bps as i128) / 10_000;
+        if withheld > 0 {
+            let wh_key = DataKey::WithheldBalance(token.clone(), jurisdiction);
+            let balance: i128 = env.storage().persistent().get(&wh_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&wh_key, &balance.saturating_add(withheld));
+            let payment_token: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone()))
+                .unwrap();
+            Self::track_held(env, &payment_token, withheld);
+        }
+        withheld
+    }
+
+    /// Rate applied at claim time: the jurisdiction's own rate, else the offering default.
+    fn effective_withholding_bps(env: &Env, token: &Address, jurisdiction: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WithholdingBps(token.clone(), jurisdiction))
+            .or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::WithholdingBps(token.clone(), 0))
+            })
+            .unwrap_or(0)
+    }
+
+    // ── Time-delayed claim configuration (#27) ──────────────────
+
+    /// Set per-offering claim delay in seconds. Only issuer may set. 0 = immediate claim.
+    pub fn set_claim_delay(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        delay_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let key = DataKey::ClaimDelaySecs(token.clone());
+        env.storage().persistent().set(&key, &delay_secs);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CLAIM_DELAY_SET, issuer, token, offering_id),
+            delay_secs,
+        );
+        Ok(())
+    }
+
+    /// Get per-offering claim delay in seconds. 0 = immediate claim.
+    pub fn get_claim_delay(env: Env, token: Address) -> u64 {
+        let key = DataKey::ClaimDelaySecs(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Return the total number of deposited periods for an offering token.
+    pub fn get_period_count(env: Env, token: Address) -> u32 {
+        let count_key = DataKey::PeriodCount(token);
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    // ── Reporting schedule and grace period ─────────────────────
+
+    /// Set the reporting schedule for an offering. Only the issuer may call.
+    /// Reports for period `p` are on time until `start_time + (p + 1) * period_secs + grace_secs`.
+    /// Returns `InvalidSchedule` if `period_secs` is 0.
+    pub fn set_reporting_schedule(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        start_time: u64,
+        period_secs: u64,
+        grace_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if period_secs == 0 {
+            return Err(RevoraError::InvalidSchedule);
+        }
+        let schedule = ReportingSchedule {
+            start_time,
+            period_secs,
+            grace_secs,
+        };
+        let key = DataKey::ReportingSchedule(token.clone());
+        env.storage().persistent().set(&key, &schedule);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SCHEDULE_SET, issuer, token, offering_id),
+            (start_time, period_secs, grace_secs),
+        );
+        Ok(())
+    }
+
+    /// Get the reporting schedule for an offering, if configured.
+    pub fn get_reporting_schedule(env: Env, token: Address) -> Option<ReportingSchedule> {
+        let key = DataKey::ReportingSchedule(token);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Return true if `period_id` was reported only after its grace window.
+    pub fn is_report_late(env: Env, token: Address, period_id: u64) -> bool {
+        let key = DataKey::LateReport(token, period_id);
+        env.storage().persistent().get(&key).unwrap_or(false)
+    }
+
+    /// Last on-time timestamp for a period's report: period end plus grace (saturating).
+    fn report_deadline(schedule: &ReportingSchedule, period_id: u64) -> u64 {
+        schedule
+            .start_time
+            .saturating_add(
+                schedule
+                    .period_secs
+                    .saturating_mul(period_id.saturating_add(1)),
+            )
+            .saturating_add(schedule.grace_secs)
+    }
+
+    // ── Delinquency and auto-suspension ─────────────────────────
+
+    /// Set the number of consecutive missed reporting periods after which
+    /// `check_delinquency` suspends the offering. 0 disables auto-suspension.
+    pub fn set_suspension_threshold(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        max_missed_periods: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let key = DataKey::SuspendAfterMissed(token.clone());
+        env.storage().persistent().set(&key, &max_missed_periods);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SUSPENSION_SET, issuer, token, offering_id),
+            max_missed_periods,
+        );
+        Ok(())
+    }
+
+    /// Get the auto-suspension threshold for an offering (0 = disabled).
+    pub fn get_suspension_threshold(env: Env, token: Address) -> u32 {
+        let key = DataKey::SuspendAfterMissed(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Get the lifecycle status of an offering. Defaults to Active.
+    pub fn get_offering_status(env: Env, token: Address) -> OfferingStatus {
+        let key = DataKey::OfferingStatus(token);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(OfferingStatus::Active)
+    }
+
+    /// Number of consecutive periods, counting back from the latest period whose
+    /// grace window has passed, that have no report. 0 if no schedule is set.
+    pub fn get_missed_periods(env: Env, token: Address) -> u32 {
+        Self::missed_periods(&env, &token).0
+    }
+
+    /// Permissionless delinquency check. Suspends an Active offering once its
+    /// consecutive missed periods reach the configured threshold.
+    /// Emits `delinq` events once per newly overdue period and on auto-suspension.
+    /// Returns the current number of consecutive missed periods.
+    pub fn check_delinquency(env: Env, token: Address) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let (missed, latest_due) = Self::missed_periods(&env, &token);
+        let offering_id = Self::offering_id_of(&env, &token);
+        if let (true, Some(period_id)) = (missed > 0, latest_due) {
+            let notified_key = DataKey::DelinquencyNotified(token.clone());
+            let notified: Option<u64> = env.storage().persistent().get(&notified_key);
+            if notified.is_none_or(|p| period_id > p) {
+                env.storage().persistent().set(&notified_key, &period_id);
+                env.events().publish(
+                    (
+                        EVENT_DELINQUENT,
+                        token.clone(),
+                        symbol_short!("missed"),
+                        offering_id,
+                    ),
+                    (missed, period_id),
+                );
+            }
+        }
+        let threshold = Self::get_suspension_threshold(env.clone(), token.clone());
+        if threshold > 0
+            && missed >= threshold
+            && Self::get_offering_status(env.clone(), token.clone()) == OfferingStatus::Active
+        {
+            Self::set_offering_status(&env, &token, OfferingStatus::Suspended);
+            env.events().publish(
+                (
+                    EVENT_DELINQUENT,
+                    token.clone(),
+                    symbol_short!("suspended"),
+                    offering_id,
+                ),
+                (missed, latest_due.unwrap_or(0)),
+            );
+        }
+        Ok(missed)
+    }
+
+    /// Return a Suspended offering to Active. Only the issuer may call.
+    /// Every period whose grace window has passed must be covered: either file the
+    /// missing reports, or a single catch-up report for the latest due period.
+    /// Catch-up amounts are deposited and claimed through the normal flow.
+    pub fn reinstate_offering(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if Self::get_offering_status(env.clone(), token.clone()) != OfferingStatus::Suspended {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        if Self::missed_periods(&env, &token).0 > 0 {
+            return Err(RevoraError::MissedReportsOutstanding);
+        }
+        Self::set_offering_status(&env, &token, OfferingStatus::Active);
+        Ok(())
+    }
+
+    /// Close a settled offering (issuer only): its escrow must be empty. Reports and
+    /// deposits are rejected until the pair is registered again as a new series.
+    pub fn close_offering(env: Env, issuer: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if Self::get_offering_status(env.clone(), token.clone()) == OfferingStatus::Closed {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        if Self::get_escrow_balance(env.clone(), token.clone()) != 0 {
+            return Err(RevoraError::EscrowOutstanding);
+        }
+        Self::set_offering_status(&env, &token, OfferingStatus::Closed);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_OFFERING_CLOSED, issuer, token, offering_id), ());
+        Ok(())
+    }
+
+    /// Number of archived series of an offering (0 until it is first re-registered).
+    pub fn get_series_count(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&IndexKey::SeriesCount(token))
+            .unwrap_or(0)
+    }
+
+    /// Archived series `series` (0-based) of an offering.
+    pub fn get_archived_series(env: Env, token: Address, series: u32) -> Option<SeriesRecord> {
+        env.storage()
+            .persistent()
+            .get(&IndexKey::SeriesArchive(token, series))
+    }
+
+    /// Archive the Closed offering and reopen it in place under a new offering id.
+    /// Only the offering record and id are per series. Reports, deposited periods,
+    /// holder shares, claim cursors, blacklist and compliance lists stay keyed by token
+    /// and carry into the new series; the archive records which deposited periods the
+    /// closed series owned, and with monotonic periods the new series continues after
+    /// the last finalized period.
+    fn open_next_series(env: &Env, issuer: &Address, token: &Address, revenue_share_bps: u32) {
+        let store = env.storage().persistent();
+        let series = Self::get_series_count(env.clone(), token.clone());
+        let previous = Self::get_offering(env.clone(), issuer.clone(), token.clone()).unwrap();
+        let start_index = match series {
+            0 => 0,
+            _ => Self::get_archived_series(env.clone(), token.clone(), series - 1)
+                .map(|r| r.end_index)
+                .unwrap_or(0),
+        };
+        store.set(
+            &IndexKey::SeriesArchive(token.clone(), series),
+            &SeriesRecord {
+                offering: previous,
+                offering_id: Self::offering_id_of(env, token),
+                start_index,
+                end_index: Self::get_period_count(env.clone(), token.clone()),
+            },
+        );
+        store.set(&IndexKey::SeriesCount(token.clone()), &(series + 1));
+
+        let offering_id: u64 = store.get(&IndexKey::LastOfferingId).unwrap_or(0) + 1;
+        store.set(&IndexKey::LastOfferingId, &offering_id);
+        store.set(
+            &IndexKey::OfferingById(offering_id),
+            &(issuer.clone(), token.clone()),
+        );
+        store.set(&IndexKey::OfferingIdOf(token.clone()), &offering_id);
+
+        Self::update_offering(env, issuer, token, |o| {
+            o.revenue_share_bps = revenue_share_bps;
+            o.created_at = env.ledger().timestamp();
+            o.created_ledger = env.ledger().sequence();
+        });
+        Self::set_offering_status(env, token, OfferingStatus::Active);
+        Self::refresh_state_hash(env, token);
+    }
+
+    /// Store a new status for an offering and emit a status change event.
+    fn set_offering_status(env: &Env, token: &Address, status: OfferingStatus) {
+        let previous = Self::get_offering_status(env.clone(), token.clone());
+        let key = DataKey::OfferingStatus(token.clone());
+        env.storage().persistent().set(&key, &status);
+        if previous != status {
+            let issuer_key = DataKey::OfferingIssuer(token.clone());
+            if let Some(issuer) = env.storage().persistent().get::<_, Address>(&issuer_key) {
+                Self::status_index_remove(env, &issuer, previous, token);
+                Self::status_index_insert(env, &issuer, status, token);
+                Self::update_metrics(env, |m| {
+                    if previous == OfferingStatus::Active {
+                        m.active_offerings = m.active_offerings.saturating_sub(1);
+                    }
+                    if status == OfferingStatus::Active {
+                        m.active_offerings += 1;
+                    }
+                });
+                Self::update_offering(env, &issuer, token, |_| {});
+            }
+        }
+        let offering_id = Self::offering_id_of(env, token);
+        env.events().publish(
+            (EVENT_STATUS_CHANGED, token.clone(), offering_id),
+            (previous, status),
+        );
+    }
+
+    /// Offering id used in event topics; 0 when the token was never registered.
+    fn offering_id_of(env: &Env, token: &Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&IndexKey::OfferingIdOf(token.clone()))
+            .unwrap_or(0)
+    }
+
+    fn status_index_insert(env: &Env, issuer: &Address, status: OfferingStatus, token: &Address) {
+        let key = IndexKey::OfferingsByStatus(issuer.clone(), status);
+        let mut tokens: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        tokens.push_back(token.clone());
+        env.storage().persistent().set(&key, &tokens);
+    }
+
+    fn status_index_remove(env: &Env, issuer: &Address, status: OfferingStatus, token: &Address) {
+        let key = IndexKey::OfferingsByStatus(issuer.clone(), status);
+        let tokens: Vec<Address> = match env.storage().persistent().get(&key) {
+            Some(t) => t,
+            None => return,
+        };
+        if let Some(idx) = tokens.first_index_of(token) {
+            let mut tokens = tokens;
+            tokens.remove(idx);
+            env.storage().persistent().set(&key, &tokens);
+        }
+    }
+
+    /// Returns (consecutive missed periods, latest period whose deadline has passed).
+    fn missed_periods(env: &Env, token: &Address) -> (u32, Option<u64>) {
+        let schedule_key = DataKey::ReportingSchedule(token.clone());
+        let schedule: ReportingSchedule = match env.storage().persistent().get(&schedule_key) {
+            Some(s) => s,
+            None => return (0, None),
+        };
+        let now = env.ledger().timestamp();
+        if now <= Self::report_deadline(&schedule, 0) {
+            return (0, None);
+        }
+        // Periods 0..due_count have deadlines strictly before `now`.
+        let due_count =
+            (now - schedule.start_time - schedule.grace_secs - 1) / schedule.period_secs;
+        let due = due_count - 1;
+        let last_key = DataKey::LastReportedPeriod(token.clone());
+        let covered = match env.storage().persistent().get::<DataKey, u64>(&last_key) {
+            Some(p) if p <= due => Some(p),
+            Some(_) => Self::last_report_at_or_before(env, token, due),
+            None => None,
+        };
+        let missed = covered.map_or(due_count, |p| due - p);
+        let missed = core::cmp::min(missed, u32::MAX as u64) as u32;
+        (missed, Some(due))
+    }
+
+    /// Highest period at or before `period_id` that has a stored report.
+    fn last_report_at_or_before(env: &Env, token: &Address, period_id: u64) -> Option<u64> {
+        let issuer: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OfferingIssuer(token.clone()))?;
+        let periods = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Map<u64, (i128, u64)>>(&DataKey::RevenueReports(issuer, token.clone()))?
+            .keys();
+        match periods.binary_search(period_id) {
+            Ok(_) => Some(period_id),
+            Err(0) => None,
+            Err(idx) => periods.get(idx - 1),
+        }
+    }
+
+    // ── Offering health summary ─────────────────────────────────
+
+    /// Single-read dashboard summary for an offering. Returns None if the offering does not exist.
+    pub fn get_offering_health(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Option<OfferingHealth> {
+        Self::get_offering(env.clone(), issuer.clone(), token.clone())?;
+
+        let last_report_period: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastReportedPeriod(token.clone()));
+        let last_report_time = last_report_period.and_then(|period_id| {
+            env.storage()
+                .persistent()
+                .get::<DataKey, Map<u64, (i128, u64)>>(&DataKey::RevenueReports(
+                    issuer.clone(),
+                    token.clone(),
+                ))
+                .and_then(|reports| reports.get(period_id))
+                .map(|(_amount, timestamp)| timestamp)
+        });
+
+        Some(OfferingHealth {
+            status: Self::get_offering_status(env.clone(), token.clone()),
+            last_report_period,
+            last_report_time,
+            missed_periods: Self::missed_periods(&env, &token).0,
+            escrow_balance: Self::get_escrow_balance(env.clone(), token.clone()),
+            blacklist_size: Self::blacklist_len(&env, &token)
+                + Self::legacy_blacklist(&env, &token).map_or(0, |m| m.len()),
+            pending_disputes: env
+                .storage()
+                .persistent()
+                .get(&DataKey::OpenDisputeCount(token))
+                .unwrap_or(0),
+        })
+    }
+
+    /// Deposited revenue held for an offering and not yet paid out.
+    pub fn get_escrow_balance(env: Env, token: Address) -> i128 {
+        let key = DataKey::EscrowBalance(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Holder payout for one period: the offering's strategy if configured, else pro-rata.
+    fn period_payout(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        share_bps: u32,
+        period_id: u64,
+        revenue: i128,
+    ) -> i128 {
+        match Self::get_distribution_strategy(env.clone(), token.clone()) {
+            Some(strategy) => DistributionStrategyClient::new(env, &strategy)
+                .compute_payout(token, holder, &share_bps, &period_id, &revenue)
+                .clamp(0, revenue.max(0)),
+            None => revenue
+                .checked_mul(share_bps as i128)
+                .map_or(revenue / 10_000 * share_bps as i128, |v| v / 10_000),
+        }
+    }
+
+    /// Set (or clear with `None`) the offering's external distribution strategy.
+    /// Claims and previews then delegate per-period payout computation to it.
+    /// Only the issuer may call; the strategy is part of the offering's terms.
+    pub fn set_distribution_strategy(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        strategy: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let key = DataKey::DistributionStrategy(token.clone());
+        match &strategy {
+            Some(s) => env.storage().persistent().set(&key, s),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_STRATEGY_SET, issuer, token, offering_id), strategy);
+        Ok(())
+    }
+
+    /// Get the offering's distribution strategy, if any.
+    pub fn get_distribution_strategy(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DistributionStrategy(token))
+    }
+
+    /// Add `delta` (may be negative) to an offering's escrow balance.
+    fn adjust_escrow(env: &Env, token: &Address, delta: i128) {
+        let key = DataKey::EscrowBalance(token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &balance.saturating_add(delta));
+        if let Some(payment_token) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::PaymentToken(token.clone()))
+        {
+            Self::track_held(env, &payment_token, delta);
+        }
+    }
+
+    // ── On-chain distribution simulation (#29) ────────────────────
+
+    /// Read-only: simulate distribution for sample inputs without mutating state.
+    /// Returns expected payouts per holder and total. Uses offering's rounding mode.
+    /// For integrators to preview outcomes before executing deposit/claim flows.
+    pub fn simulate_distribution(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+        holder_shares: Vec<(Address, u32)>,
+    ) -> SimulateDistributionResult {
+        let mode = Self::get_rounding_mode(env.clone(), issuer, token.clone());
+        let mut total: i128 = 0;
+        let mut payouts = Vec::new(&env);
+        for i in 0..holder_shares.len() {
+            let (holder, share_bps) = holder_shares.get(i).unwrap();
+            let payout = if share_bps > 10_000 {
+                0_i128
+            } else {
+                Self::compute_share(env.clone(), amount, share_bps, mode)
+            };
+            total = total.saturating_add(payout);
+            payouts.push_back((holder.clone(), payout));
+        }
+        SimulateDistributionResult {
+            total_distributed: total,
+            payouts,
+        }
+    }
+
+    // ── Upgradeability guard and freeze (#32) ───────────────────
+
+    /// Set the admin address. May only be called once; caller must authorize as the new admin.
+    pub fn set_admin(env: Env, admin: Address) -> Result<(), RevoraError> {
+        admin.require_auth();
+        let key = DataKey::Admin;
+        if env.storage().persistent().has(&key) {
+            return Err(RevoraError::LimitReached);
+        }
+        env.storage().persistent().set(&key, &admin);
+        Self::record_fresh_version(&env);
+        env.events().publish((EVENT_ADMIN_SET, admin), ());
+        Ok(())
+    }
+
+    /// Get the admin address, if set.
+    pub fn get_admin(env: Env) -> Option<Address> {
+        let key = DataKey::Admin;
+        env.storage().persistent().get(&key)
+    }
+
+    /// Freeze the contract: no further state-changing operations allowed. Only admin may call.
+    /// Emits event. Claim and read-only functions remain allowed. Timelocked as kind 1.
+    pub fn freeze(env: Env) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::Freeze)
+    }
+
+    /// Return true if the contract is frozen.
+    pub fn is_frozen(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, bool>(&DataKey::Frozen)
+            .unwrap_or(false)
+    }
+
+    // ── Testnet mode configuration (#24) ───────────────────────
+
+    /// Enable or disable testnet mode. Only admin may call.
+    /// When enabled, certain validations are relaxed for testnet deployments.
+    /// Emits event with new mode state. Timelocked as kind 0.
+    pub fn set_testnet_mode(env: Env, enabled: bool) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetTestnetMode(enabled))
+    }
+
+    /// Return true if testnet mode is enabled.
+    pub fn is_testnet_mode(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, bool>(&DataKey::TestnetMode)
+            .unwrap_or(false)
+    }
+
+    // ── Timelocked admin operations ─────────────────────────────
+
+    /// Queue an admin operation. It becomes executable after the minimum delay
+    /// configured for its kind. Only admin may call. Returns the operation id.
+    pub fn propose_admin_op(env: Env, action: AdminAction) -> Result<u64, RevoraError> {
+        let admin = Self::require_admin(&env)?;
+        let id_key = AdminKey::NextAdminOpId;
+        let id: u64 = env.storage().persistent().get(&id_key).unwrap_or(0);
+        let now = env.ledger().timestamp();
+        let kind = action.kind();
+        let eta = now.saturating_add(Self::get_admin_op_delay(env.clone(), kind));
+        let op = AdminOp {
+            id,
+            action,
+            proposed_at: now,
+            eta,
+        };
+        env.storage().persistent().set(&AdminKey::AdminOp(id), &op);
+        env.storage().persistent().set(&id_key, &(id + 1));
+
+        let mut pending = Self::pending_admin_op_ids(&env);
+        pending.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&AdminKey::PendingAdminOps, &pending);
+
+        env.events()
+            .publish((EVENT_ADMIN_OP_PROPOSED, admin), (id, kind, eta));
+        Ok(id)
+    }
+
+    /// Execute a queued admin operation whose delay has elapsed. Only admin may call.
+    pub fn execute_admin_op(env: Env, op_id: u64) -> Result<(), RevoraError> {
+        let admin = Self::require_admin(&env)?;
+        let op = Self::get_admin_op(env.clone(), op_id).ok_or(RevoraError::AdminOpNotFound)?;
+        if env.ledger().timestamp() < op.eta {
+            return Err(RevoraError::TimelockNotElapsed);
+        }
+        Self::remove_admin_op(&env, op_id);
+        Self::apply_admin_action(&env, &admin, op.action.clone())?;
+        env.events()
+            .publish((EVENT_ADMIN_OP_EXECUTED, admin), (op_id, op.action.kind()));
+        Ok(())
+    }
+
+    /// Cancel a queued admin operation. Only admin may call.
+    pub fn cancel_admin_op(env: Env, op_id: u64) -> Result<(), RevoraError> {
+        let admin = Self::require_admin(&env)?;
+        let op = Self::get_admin_op(env.clone(), op_id).ok_or(RevoraError::AdminOpNotFound)?;
+        Self::remove_admin_op(&env, op_id);
+        env.events()
+            .publish((EVENT_ADMIN_OP_CANCELLED, admin), (op_id, op.action.kind()));
+        Ok(())
+    }
+
+    /// Get a queued admin operation by id.
+    pub fn get_admin_op(env: Env, op_id: u64) -> Option<AdminOp> {
+        env.storage().persistent().get(&AdminKey::AdminOp(op_id))
+    }
+
+    /// List all queued admin operations, oldest first.
+    pub fn get_pending_admin_ops(env: Env) -> Vec<AdminOp> {
+        let mut ops = Vec::new(&env);
+        for id in Self::pending_admin_op_ids(&env).iter() {
+            if let Some(op) = Self::get_admin_op(env.clone(), id) {
+                ops.push_back(op);
+            }
+        }
+        ops
+    }
+
+    /// Minimum timelock delay in seconds for an admin operation kind (0 if unset).
+    pub fn get_admin_op_delay(env: Env, kind: u32) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::AdminOpDelay(kind))
+            .unwrap_or(0)
+    }
+
+    /// Run an admin operation directly, which is only allowed while its kind has no
+    /// timelock delay; otherwise it must be queued with `propose_admin_op`.
+    fn run_admin_action(env: &Env, action: AdminAction) -> Result<(), RevoraError> {
+        let admin = Self::require_admin(env)?;
+        if Self::get_admin_op_delay(env.clone(), action.kind()) > 0 {
+            return Err(RevoraError::TimelockNotElapsed);
+        }
+        Self::apply_admin_action(env, &admin, action)
+    }
+
+    fn apply_admin_action(
+        env: &Env,
+        admin: &Address,
+        action: AdminAction,
+    ) -> Result<(), RevoraError> {
+        let store = env.storage().persistent();
+        let admin = admin.clone();
+        match action {
+            AdminAction::SetTestnetMode(enabled) => {
+                store.set(&DataKey::TestnetMode, &enabled);
+                env.events().publish((EVENT_TESTNET_MODE, admin), enabled);
+            }
+            AdminAction::Freeze => {
+                store.set(&DataKey::Frozen, &true);
+                env.events().publish((EVENT_FREEZE, admin), true);
+            }
+            AdminAction::SetOpDelay(kind, delay_secs) => {
+                store.set(&AdminKey::AdminOpDelay(kind), &delay_secs);
+            }
+            AdminAction::Upgrade(wasm_hash) => {
+                Self::install_wasm(env, &admin, wasm_hash);
+            }
+            AdminAction::SetRegistrationFee(fee_token, amount) => {
+                Self::store_registration_fee(env, &admin, fee_token, amount)?;
+            }
+            AdminAction::SetTreasury(treasury) => {
+                store.set(&AdminKey::Treasury, &treasury);
+                env.events().publish((EVENT_TREASURY_SET, admin), treasury);
+            }
+            AdminAction::SetInsuranceBps(bps) => {
+                if bps > 10_000 {
+                    return Err(RevoraError::InvalidShareBps);
+                }
+                store.set(&AdminKey::InsuranceBps, &bps);
+                env.events().publish((EVENT_INSURANCE_BPS_SET, admin), bps);
+            }
+            AdminAction::SetMaxOfferingsPerIssuer(max) => {
+                store.set(&AdminKey::MaxOfferingsPerIssuer, &max);
+                env.events().publish(
+                    (EVENT_OFFERING_LIMIT_SET, admin),
+                    (None::<Address>, Some(max)),
+                );
+            }
+            AdminAction::SetIssuerOfferingLimit(issuer, max) => {
+                let key = AdminKey::IssuerOfferingLimit(issuer.clone());
+                match max {
+                    Some(m) => store.set(&key, &m),
+                    None => store.remove(&key),
+                }
+                env.events()
+                    .publish((EVENT_OFFERING_LIMIT_SET, admin), (Some(issuer), max));
+            }
+            AdminAction::SetIssuerAllowlistEnabled(enabled) => {
+                store.set(&AdminKey::IssuerAllowlistEnabled, &enabled);
+                env.events().publish((EVENT_ISSUER_MODE, admin), enabled);
+            }
+            AdminAction::SetIssuerAllowed(issuer, allowed) => {
+                let key = AdminKey::AllowedIssuer(issuer.clone());
+                if allowed {
+                    store.set(&key, &true);
+                } else {
+                    store.remove(&key);
+                }
+                env.events()
+                    .publish((EVENT_ISSUER_ALLOW, admin, issuer), allowed);
+            }
+            AdminAction::SetFeeWaiver(issuer, waived) => {
+                let key = AdminKey::FeeWaiver(issuer.clone());
+                if waived {
+                    store.set(&key, &true);
+                } else {
+                    store.remove(&key);
+                }
+                env.events()
+                    .publish((EVENT_FEE_WAIVER, admin, issuer), waived);
+            }
+            AdminAction::SetIssuerBond(bond_token, amount) => {
+                if amount < 0 {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                store.set(
+                    &AdminKey::IssuerBondConfig,
+                    &FeeConfig {
+                        token: bond_token.clone(),
+                        amount,
+                    },
+                );
+                env.events()
+                    .publish((EVENT_BOND_CONFIG_SET, admin), (bond_token, amount));
+            }
+            AdminAction::SetRole(role, account, granted) => {
+                Self::set_role_member(env, role, &account, granted);
+                env.events()
+                    .publish((EVENT_ROLE_SET, admin, account), (role, granted));
+            }
+            AdminAction::SetAppealWindow(window_secs) => {
+                store.set(&AdminKey::AppealWindowSecs, &window_secs);
+                env.events()
+                    .publish((EVENT_APPEAL_WINDOW_SET, admin), window_secs);
+            }
+            AdminAction::Compensate(token, period_id, investors, amounts) => {
+                Self::pay_compensation(env, token, period_id, investors, amounts)?;
+            }
+            AdminAction::RemoveListener(token, listener) => {
+                let key = ReportKey::ReportListeners(token.clone());
+                let mut listeners = Self::get_report_listeners(env.clone(), token.clone());
+                if let Some(idx) = listeners.first_index_of(&listener) {
+                    listeners.remove(idx);
+                    store.set(&key, &listeners);
+                    let offering_id = Self::offering_id_of(env, &token);
+                    env.events()
+                        .publish((EVENT_LISTENER_REMOVED, token, offering_id), listener);
+                }
+            }
+            AdminAction::SetOfferingArbiter(token, arbiter) => {
+                let key = BondKey::OfferingArbiter(token.clone());
+                match &arbiter {
+                    Some(a) => store.set(&key, a),
+                    None => store.remove(&key),
+                }
+                let offering_id = Self::offering_id_of(env, &token);
+                env.events()
+                    .publish((EVENT_ARBITER_SET, admin, token, offering_id), arbiter);
+            }
+            AdminAction::SetPenaltyTerms(token, window_secs, bps_per_day) => {
+                if bps_per_day > 10_000 {
+                    return Err(RevoraError::InvalidShareBps);
+                }
+                let terms = PenaltyTerms {
+                    window_secs,
+                    bps_per_day,
+                };
+                store.set(&ReportKey::PenaltyTerms(token.clone()), &terms);
+                let offering_id = Self::offering_id_of(env, &token);
+                env.events()
+                    .publish((EVENT_PENALTY_TERMS_SET, admin, token, offering_id), terms);
+            }
+            AdminAction::SetShortfallInterest(token, bps_per_day) => {
+                if bps_per_day > 10_000 {
+                    return Err(RevoraError::InvalidShareBps);
+                }
+                store.set(&ReportKey::ShortfallRate(token.clone()), &bps_per_day);
+                let offering_id = Self::offering_id_of(env, &token);
+                env.events().publish(
+                    (EVENT_SHORTFALL_RATE_SET, admin, token, offering_id),
+                    bps_per_day,
+                );
+            }
+            AdminAction::SetEngineStaleSecs(secs) => {
+                store.set(&AdminKey::EngineStaleSecs, &secs);
+                env.events().publish((EVENT_STALE_SECS_SET, admin), secs);
+            }
+            AdminAction::SetChallengeBond(bond_token, amount) => {
+                if amount < 0 {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                store.set(
+                    &AdminKey::ChallengeBond,
+                    &FeeConfig {
+                        token: bond_token.clone(),
+                        amount,
+                    },
+                );
+                env.events()
+                    .publish((EVENT_BOND_CONFIG_SET, admin), (bond_token, amount));
+            }
+            AdminAction::SetChildWasmHash(wasm_hash) => {
+                store.set(&AdminKey::ChildWasmHash, &wasm_hash);
+                env.events()
+                    .publish((EVENT_CHILD_WASM_SET, admin), Some(wasm_hash));
+            }
+            AdminAction::ClearChildWasmHash => {
+                store.remove(&AdminKey::ChildWasmHash);
+                env.events()
+                    .publish((EVENT_CHILD_WASM_SET, admin), None::<BytesN<32>>);
+            }
+            AdminAction::SetDefaultEventVerbosity(verbosity) => {
+                store.set(&AdminKey::DefaultVerbosity, &verbosity);
+                env.events()
+                    .publish((EVENT_VERBOSITY_DEFAULT_SET, admin), verbosity);
+            }
+            AdminAction::AddEngine(engine) => {
+                Self::set_role_member(env, Role::DistributionEngine, &engine, true);
+                env.events().publish(
+                    (EVENT_ROLE_SET, admin, engine.clone()),
+                    (Role::DistributionEngine, true),
+                );
+                Self::touch_engine(env, &engine);
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the admin and require its authorization.
+    fn require_admin(env: &Env) -> Result<Address, RevoraError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RevoraError::LimitReached)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    fn pending_admin_op_ids(env: &Env) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::PendingAdminOps)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Delete a queued operation and drop it from the pending list.
+    fn remove_admin_op(env: &Env, op_id: u64) {
+        env.storage().persistent().remove(&AdminKey::AdminOp(op_id));
+        let pending = Self::pending_admin_op_ids(env);
+        let mut remaining = Vec::new(env);
+        for id in pending.iter() {
+            if id != op_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&AdminKey::PendingAdminOps, &remaining);
+    }
+
+    // ── Upgrade and migration hook ──────────────────────────────
+
+    /// Install new contract WASM. Only admin may call. Timelocked as kind 3.
+    /// The new code takes effect after this invocation and records the storage layout
+    /// version it replaced; a release that changes the layout migrates it in
+    /// `post_upgrade`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::Upgrade(new_wasm_hash))
+    }
+
+    /// Migrate storage from `from_version` to this code's `CONTRACT_VERSION` after an
+    /// upgrade, then record the new version. Only admin may call. `from_version` must match
+    /// the recorded version and be older than this code. State changes fail with
+    /// `MigrationPending` until this has run.
+    pub fn post_upgrade(env: Env, from_version: u32) -> Result<(), RevoraError> {
+        let admin = Self::require_admin(&env)?;
+        if from_version != Self::get_contract_version(env.clone())
+            || from_version >= CONTRACT_VERSION
+        {
+            return Err(RevoraError::InvalidMigration);
+        }
+        Self::migrate(&env, from_version);
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ContractVersion, &CONTRACT_VERSION);
+        env.events()
+            .publish((EVENT_MIGRATED, admin), (from_version, CONTRACT_VERSION));
+        Ok(())
+    }
+
+    /// Storage layout version currently recorded (0 if the deployment predates versioning).
+    pub fn get_contract_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::ContractVersion)
+            .unwrap_or(0)
+    }
+
+    /// True while an administered deployment's recorded layout is older than the installed
+    /// code, i.e. until `post_upgrade` runs.
+    pub fn is_migration_pending(env: Env) -> bool {
+        let store = env.storage().persistent();
+        match store.get::<AdminKey, u32>(&AdminKey::ContractVersion) {
+            Some(v) => v < CONTRACT_VERSION,
+            None => store.has(&DataKey::Admin),
+        }
+    }
+
+    /// Run each layout step from `from_version` up to `CONTRACT_VERSION`.
+    fn migrate(env: &Env, from_version: u32) {
+        if from_version < 1 {
+            // Layout 0 kept each blacklist as one `DataKey::Blacklist` map. Keep reading
+            // those maps; each is folded into per-address entries on its next change.
+            env.storage()
+                .persistent()
+                .set(&AdminKey::LegacyBlacklists, &true);
+        }
+    }
+
+    /// Fresh deployments start at the current layout.
+    fn record_fresh_version(env: &Env) {
+        let store = env.storage().persistent();
+        if !store.has(&AdminKey::ContractVersion) {
+            store.set(&AdminKey::ContractVersion, &CONTRACT_VERSION);
+        }
+    }
+
+    /// Record the version being replaced and swap in the new WASM.
+    fn install_wasm(env: &Env, admin: &Address, new_wasm_hash: BytesN<32>) {
+        let from_version = Self::get_contract_version(env.clone());
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ContractVersion, &from_version);
+        env.events().publish(
+            (EVENT_UPGRADED, admin.clone()),
+            (new_wasm_hash.clone(), from_version),
+        );
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    // ── Offerings-per-issuer limit ──────────────────────────────
+
+    /// Set the default maximum number of offerings per issuer (0 = unlimited). Only admin may call.
+    /// Timelocked as kind 7.
+    pub fn set_max_offerings_per_issuer(env: Env, max: u32) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetMaxOfferingsPerIssuer(max))
+    }
+
+    /// Set or clear (`None`) a per-issuer override of the offering limit. Only admin may call.
+    /// Timelocked as kind 8.
+    pub fn set_issuer_offering_limit(
+        env: Env,
+        issuer: Address,
+        max: Option<u32>,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetIssuerOfferingLimit(issuer, max))
+    }
+
+    /// Effective offering limit for an issuer: its override, else the default (0 = unlimited).
+    pub fn get_offering_limit(env: Env, issuer: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::IssuerOfferingLimit(issuer))
+            .or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get(&AdminKey::MaxOfferingsPerIssuer)
+            })
+            .unwrap_or(0)
+    }
+
+    // ── Issuer allowlist ────────────────────────────────────────
+
+    /// Switch between permissionless (false) and permissioned (true) registration.
+    /// Only admin may call. Timelocked as kind 9.
+    pub fn set_issuer_allowlist_enabled(env: Env, enabled: bool) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetIssuerAllowlistEnabled(enabled))
+    }
+
+    /// Return true if registration is permissioned.
+    pub fn is_issuer_allowlist_enabled(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::IssuerAllowlistEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Add an issuer to the allowlist. Only admin may call. Idempotent. Timelocked as kind 10.
+    pub fn allow_issuer(env: Env, issuer: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetIssuerAllowed(issuer, true))
+    }
+
+    /// Remove an issuer from the allowlist. Existing offerings are unaffected. Idempotent.
+    /// Timelocked as kind 10.
+    pub fn disallow_issuer(env: Env, issuer: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetIssuerAllowed(issuer, false))
+    }
+
+    /// Return true if `issuer` may register offerings under the current mode.
+    pub fn is_issuer_allowed(env: Env, issuer: Address) -> bool {
+        !Self::is_issuer_allowlist_enabled(env.clone())
+            || env
+                .storage()
+                .persistent()
+                .get(&AdminKey::AllowedIssuer(issuer))
+                .unwrap_or(false)
+    }
+
+    // ── Registration fee and waivers ────────────────────────────
+
+    /// Set the protocol treasury that receives fees. Only admin may call. Timelocked as kind 5.
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetTreasury(treasury))
+    }
+
+    /// Get the protocol treasury, if set.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&AdminKey::Treasury)
+    }
+
+    /// Set the registration fee (0 disables). Only admin may call; requires a treasury
+    /// when `amount > 0`. Timelocked as kind 4.
+    pub fn set_registration_fee(
+        env: Env,
+        fee_token: Address,
+        amount: i128,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetRegistrationFee(fee_token, amount))
+    }
+
+    /// Get the registration fee, if configured.
+    pub fn get_registration_fee(env: Env) -> Option<FeeConfig> {
+        env.storage().persistent().get(&AdminKey::RegistrationFee)
+    }
+
+    /// Exempt an issuer from the registration fee. Only admin may call. Idempotent.
+    /// Timelocked as kind 11.
+    pub fn add_fee_waiver(env: Env, issuer: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetFeeWaiver(issuer, true))
+    }
+
+    /// Remove an issuer's fee waiver. Only admin may call. Idempotent. Timelocked as kind 11.
+    pub fn remove_fee_waiver(env: Env, issuer: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetFeeWaiver(issuer, false))
+    }
+
+    /// Return true if the issuer is exempt from the registration fee.
+    pub fn has_fee_waiver(env: Env, issuer: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::FeeWaiver(issuer))
+            .unwrap_or(false)
+    }
+
+    fn store_registration_fee(
+        env: &Env,
+        admin: &Address,
+        fee_token: Address,
+        amount: i128,
+    ) -> Result<(), RevoraError> {
+        if amount < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if amount > 0 && Self::get_treasury(env.clone()).is_none() {
+            return Err(RevoraError::RecipientNotSet);
+        }
+        let fee = FeeConfig {
+            token: fee_token.clone(),
+            amount,
+        };
+        env.storage()
+            .persistent()
+            .set(&AdminKey::RegistrationFee, &fee);
+        env.events()
+            .publish((EVENT_FEE_SET, admin.clone()), (fee_token, amount));
+        Ok(())
+    }
+
+    // ── Issuer bonds ────────────────────────────────────────────
+
+    /// Require issuers to stake `amount` of `bond_token` when registering an offering
+    /// (0 disables). Only admin may call. Timelocked as kind 12.
+    pub fn set_issuer_bond(env: Env, bond_token: Address, amount: i128) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetIssuerBond(bond_token, amount))
+    }
+
+    /// Bond required at registration, if configured.
+    pub fn get_issuer_bond_config(env: Env) -> Option<FeeConfig> {
+        env.storage().persistent().get(&AdminKey::IssuerBondConfig)
+    }
+
+    /// Bond held for an offering, if one was posted.
+    pub fn get_offering_bond(env: Env, token: Address) -> Option<OfferingBond> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::OfferingBond(token))
+    }
+
+    // ── Roles ───────────────────────────────────────────────────
+
+    /// Grant a protocol role. Only admin may call. Idempotent. Timelocked as kind 13.
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetRole(role, account, true))
+    }
+
+    /// Revoke a protocol role. Only admin may call. Idempotent. Timelocked as kind 13.
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetRole(role, account, false))
+    }
+
+    /// Return true if `account` holds `role`.
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&AdminKey::RoleMember(role, account))
+    }
+
+    /// Page through the holders of `role` in grant order. Returns (members, next cursor).
+    pub fn list_role_members(
+        env: Env,
+        role: Role,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let members: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&AdminKey::RoleMembers(role))
+            .unwrap_or_else(|| Vec::new(&env));
+        let effective_limit = if limit == 0 || limit > MAX_PAGE_LIMIT {
+            MAX_PAGE_LIMIT
+        } else {
+            limit
+        };
+        if cursor >= members.len() {
+            return (Vec::new(&env), None);
+        }
+        let end = core::cmp::min(cursor + effective_limit, members.len());
+        let next_cursor = if end < members.len() { Some(end) } else { None };
+        (members.slice(cursor..end), next_cursor)
+    }
+
+    /// Roles held by `account`.
+    pub fn list_roles_of(env: Env, account: Address) -> Vec<Role> {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::RolesOf(account))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Grant or revoke a role, keeping the membership indexes in step.
+    fn set_role_member(env: &Env, role: Role, account: &Address, member: bool) {
+        let store = env.storage().persistent();
+        let member_key = AdminKey::RoleMember(role, account.clone());
+        if store.has(&member_key) == member {
+            return;
+        }
+        let members_key = AdminKey::RoleMembers(role);
+        let mut members: Vec<Address> = store.get(&members_key).unwrap_or_else(|| Vec::new(env));
+        let roles_key = AdminKey::RolesOf(account.clone());
+        let mut roles: Vec<Role> = store.get(&roles_key).unwrap_or_else(|| Vec::new(env));
+        if member {
+            store.set(&member_key, &true);
+            members.push_back(account.clone());
+            roles.push_back(role);
+        } else {
+            store.remove(&member_key);
+            if let Some(idx) = members.first_index_of(account) {
+                members.remove(idx);
+            }
+            if let Some(idx) = roles.first_index_of(role) {
+                roles.remove(idx);
+            }
+        }
+        store.set(&members_key, &members);
+        store.set(&roles_key, &roles);
+    }
+
+    fn require_role(env: &Env, role: Role, account: &Address) -> Result<(), RevoraError> {
+        account.require_auth();
+        if !Self::has_role(env.clone(), role, account.clone()) {
+            return Err(RevoraError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    // ── Disputes, slashing and appeals ──────────────────────────
+
+    /// Dispute a period's report. One dispute per period; the report must exist.
+    pub fn open_dispute(
+        env: Env,
+        claimant: Address,
+        token: Address,
+        period_id: u64,
+        evidence: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        claimant.require_auth();
+        let issuer = Self::offering_issuer(&env, &token)?;
+        if Self::get_period_report(env.clone(), issuer, token.clone(), period_id).is_none() {
+            return Err(RevoraError::ReportNotFound);
+        }
+        let key = BondKey::Dispute(token.clone(), period_id);
+        if env.storage().persistent().has(&key) {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        let dispute = Dispute {
+            claimant: claimant.clone(),
+            evidence,
+            opened_at: env.ledger().timestamp(),
+            status: DisputeStatus::Open,
+        };
+        env.storage().persistent().set(&key, &dispute);
+        Self::adjust_open_disputes(&env, &token, 1);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_DISPUTE_OPENED, claimant, token, offering_id),
+            period_id,
+        );
+        Ok(())
+    }
+
+    /// Rule on an open dispute: `upheld` means the issuer lost and the bond may be
+    /// slashed. Arbiter role or the offering's arbiter only.
+    pub fn resolve_dispute(
+        env: Env,
+        arbiter: Address,
+        token: Address,
+        period_id: u64,
+        upheld: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_arbiter(&env, &arbiter, &token)?;
+        let status = if upheld {
+            DisputeStatus::Upheld
+        } else {
+            DisputeStatus::Rejected
+        };
+        let dispute = Self::close_dispute(&env, &token, period_id, status)?;
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_DISPUTE_RESOLVED, arbiter, token, offering_id),
+            (period_id, dispute.status),
+        );
+        Ok(())
+    }
+
+    /// Dispute record for a period, if any.
+    pub fn get_dispute(env: Env, token: Address, period_id: u64) -> Option<Dispute> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::Dispute(token, period_id))
+    }
+
+    /// Slash the offering's remaining bond after an upheld dispute. Permissionless.
+    /// Opens the appeal window; funds move to the treasury via `execute_slash`.
+    pub fn slash_bond(env: Env, token: Address, period_id: u64) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let dispute = Self::get_dispute(env.clone(), token.clone(), period_id)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        let slash_key = BondKey::Slash(token.clone(), period_id);
+        if dispute.status != DisputeStatus::Upheld || env.storage().persistent().has(&slash_key) {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        let bond_key = BondKey::OfferingBond(token.clone());
+        let mut bond = Self::get_offering_bond(env.clone(), token.clone())
+            .ok_or(RevoraError::InvalidAmount)?;
+        if bond.amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let amount = bond.amount;
+        bond.amount = 0;
+        bond.slashed = bond.slashed.saturating_add(amount);
+        env.storage().persistent().set(&bond_key, &bond);
+
+        let now = env.ledger().timestamp();
+        let slash = Slash {
+            amount,
+            slashed_at: now,
+            appeal_deadline: now.saturating_add(Self::get_appeal_window(env.clone())),
+            status: SlashStatus::Pending,
+        };
+        env.storage().persistent().set(&slash_key, &slash);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_BOND_SLASHED, token, offering_id),
+            (period_id, amount, slash.appeal_deadline),
+        );
+        Ok(amount)
+    }
+
+    /// Reverse a pending slash on appeal, restoring the bond. Arbiter role only, and
+    /// only before the appeal deadline.
+    pub fn reverse_slash(
+        env: Env,
+        arbiter: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_arbiter(&env, &arbiter, &token)?;
+        let slash_key = BondKey::Slash(token.clone(), period_id);
+        let mut slash = Self::get_slash(env.clone(), token.clone(), period_id)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if slash.status != SlashStatus::Pending || env.ledger().timestamp() > slash.appeal_deadline
+        {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        let bond_key = BondKey::OfferingBond(token.clone());
+        let mut bond: OfferingBond = env.storage().persistent().get(&bond_key).unwrap();
+        bond.amount = bond.amount.saturating_add(slash.amount);
+        bond.slashed = bond.slashed.saturating_sub(slash.amount);
+        env.storage().persistent().set(&bond_key, &bond);
+        slash.status = SlashStatus::Reversed;
+        env.storage().persistent().set(&slash_key, &slash);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SLASH_REVERSED, arbiter, token, offering_id),
+            (period_id, slash.amount),
+        );
+        Ok(())
+    }
+
+    /// Transfer a pending slash to the treasury (less the insurance slice) once the
+    /// appeal window has passed.
+    /// Permissionless.
+    pub fn execute_slash(env: Env, token: Address, period_id: u64) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let slash_key = BondKey::Slash(token.clone(), period_id);
+        let mut slash = Self::get_slash(env.clone(), token.clone(), period_id)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if slash.status != SlashStatus::Pending {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        if env.ledger().timestamp() <= slash.appeal_deadline {
+            return Err(RevoraError::TimelockNotElapsed);
+        }
+        let bond = Self::get_offering_bond(env.clone(), token.clone()).unwrap();
+        Self::track_held(&env, &bond.bond_token, -slash.amount);
+        let treasury = Self::route_protocol_fee(
+            &env,
+            &bond.bond_token,
+            &env.current_contract_address(),
+            slash.amount,
+        )?;
+        slash.status = SlashStatus::Executed;
+        env.storage().persistent().set(&slash_key, &slash);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SLASH_EXECUTED, token, offering_id),
+            (period_id, slash.amount, treasury),
+        );
+        Ok(slash.amount)
+    }
+
+    /// Slash record for a period, if any.
+    pub fn get_slash(env: Env, token: Address, period_id: u64) -> Option<Slash> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::Slash(token, period_id))
+    }
+
+    /// Set the appeal window after `slash_bond`. Only admin may call. Timelocked as kind 14.
+    pub fn set_appeal_window(env: Env, window_secs: u64) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetAppealWindow(window_secs))
+    }
+
+    /// Appeal window in seconds (default 7 days).
+    pub fn get_appeal_window(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::AppealWindowSecs)
+            .unwrap_or(DEFAULT_APPEAL_WINDOW_SECS)
+    }
+
+    fn adjust_open_disputes(env: &Env, token: &Address, delta: i32) {
+        let key = DataKey::OpenDisputeCount(token.clone());
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let count = count.saturating_add_signed(delta);
+        env.storage().persistent().set(&key, &count);
+    }
+
+    // ── Insurance pool ──────────────────────────────────────────
+
+    /// Route `bps` of every protocol fee (registration fees, executed slashes) into the
+    /// insurance pool. Only admin may call. Timelocked as kind 6.
+    pub fn set_insurance_bps(env: Env, bps: u32) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetInsuranceBps(bps))
+    }
+
+    /// Share of protocol fees routed to the insurance pool, in bps.
+    pub fn get_insurance_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::InsuranceBps)
+            .unwrap_or(0)
+    }
+
+    /// Insurance pool balance for an asset.
+    pub fn get_insurance_pool(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&BondKey::InsurancePool(asset))
+            .unwrap_or(0)
+    }
+
+    /// Compensate investors from the insurance pool after an upheld dispute, in the
+    /// offering's payment token. Only admin may call; up to 50 investors per call.
+    /// Timelocked as kind 15. Returns the total paid.
+    pub fn compensate(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        investors: Vec<Address>,
+        amounts: Vec<i128>,
+    ) -> Result<i128, RevoraError> {
+        let total = amounts.iter().fold(0_i128, |t, a| t.saturating_add(a));
+        Self::run_admin_action(
+            &env,
+            AdminAction::Compensate(token, period_id, investors, amounts),
+        )?;
+        Ok(total)
+    }
+
+    fn pay_compensation(
+        env: &Env,
+        token: Address,
+        period_id: u64,
+        investors: Vec<Address>,
+        amounts: Vec<i128>,
+    ) -> Result<(), RevoraError> {
+        let env = env.clone();
+        if investors.len() != amounts.len() || investors.len() > MAX_COMPENSATION_BATCH {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let dispute = Self::get_dispute(env.clone(), token.clone(), period_id)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if dispute.status != DisputeStatus::Upheld {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        let asset: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .ok_or(RevoraError::InvalidAmount)?;
+
+        let mut total: i128 = 0;
+        for amount in amounts.iter() {
+            if amount <= 0 {
+                return Err(RevoraError::InvalidAmount);
+            }
+            total = total.saturating_add(amount);
+        }
+        let pool_key = BondKey::InsurancePool(asset.clone());
+        let pool = Self::get_insurance_pool(env.clone(), asset.clone());
+        if total > pool {
+            return Err(RevoraError::InsufficientFunds);
+        }
+        env.storage().persistent().set(&pool_key, &(pool - total));
+
+        let client = token::Client::new(&env, &asset);
+        let contract = env.current_contract_address();
+        for i in 0..investors.len() {
+            client.transfer(
+                &contract,
+                &investors.get(i).unwrap(),
+                &amounts.get(i).unwrap(),
+            );
+        }
+        let paid_key = BondKey::Compensated(token.clone(), period_id);
+        let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&paid_key, &paid.saturating_add(total));
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_INSURANCE_PAID, token, offering_id),
+            (period_id, investors.len(), total),
+        );
+        Ok(())
+    }
+
+    /// Total compensation paid for an offering period.
+    pub fn get_compensation_paid(env: Env, token: Address, period_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&BondKey::Compensated(token, period_id))
+            .unwrap_or(0)
+    }
+
+    /// Pay a protocol fee from `payer`: the insurance slice stays in this contract's pool,
+    /// the rest goes to the treasury. Returns the treasury.
+    fn route_protocol_fee(
+        env: &Env,
+        asset: &Address,
+        payer: &Address,
+        amount: i128,
+    ) -> Result<Address, RevoraError> {
+        let treasury = Self::get_treasury(env.clone()).ok_or(RevoraError::RecipientNotSet)?;
+        let to_pool = amount * Self::get_insurance_bps(env.clone()) as i128 / 10_000;
+        let to_treasury = amount - to_pool;
+        let client = token::Client::new(env, asset);
+        let contract = env.current_contract_address();
+        if to_pool > 0 {
+            if *payer != contract {
+                client.transfer(payer, &contract, &to_pool);
+            }
+            let pool_key = BondKey::InsurancePool(asset.clone());
+            let pool = Self::get_insurance_pool(env.clone(), asset.clone());
+            env.storage()
+                .persistent()
+                .set(&pool_key, &pool.saturating_add(to_pool));
+            env.events()
+                .publish((EVENT_INSURANCE_FUNDED, asset.clone()), to_pool);
+        }
+        if to_treasury > 0 {
+            client.transfer(payer, &treasury, &to_treasury);
+        }
+        Ok(treasury)
+    }
+
+    // ── NFT share class ─────────────────────────────────────────
+
+    /// Define the offering's investors as holders of an NFT collection, one equal share
+    /// per id in `first_id..first_id + supply`. Only the issuer may call, and only before
+    /// the first deposit. Revenue is then paid only through `claim_nft`: `set_holder_share`,
+    /// `claim` and `claim_for` fail with `InvalidOfferingStatus`.
+    pub fn set_nft_share_class(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        collection: Address,
+        first_id: u32,
+        supply: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if supply == 0 || first_id.checked_add(supply).is_none() {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if Self::get_period_count(env.clone(), token.clone()) > 0 {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        let class = NftShareClass {
+            collection,
+            first_id,
+            supply,
+        };
+        env.storage()
+            .persistent()
+            .set(&ShareKey::NftClass(token.clone()), &class);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_NFT_CLASS_SET, issuer, token, offering_id),
+            (class.collection, first_id, supply),
+        );
+        Ok(())
+    }
+
+    /// NFT share class of an offering, if any.
+    pub fn get_nft_share_class(env: Env, token: Address) -> Option<NftShareClass> {
+        env.storage().persistent().get(&ShareKey::NftClass(token))
+    }
+
+    fn is_nft_class(env: &Env, token: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ShareKey::NftClass(token.clone()))
+    }
+
+    /// Record the current owners of up to 50 NFT ids (from `start_id`) as the distribution
+    /// basis for `period_id`. Ids already snapshotted for the period are left unchanged, so
+    /// a snapshot can be taken page by page. Only the issuer may call. Returns ids recorded.
+    pub fn snapshot_nft_owners(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        start_id: u32,
+        count: u32,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let class = Self::get_nft_share_class(env.clone(), token.clone())
+            .ok_or(RevoraError::NotEligible)?;
+        let end_id = class.first_id + class.supply;
+        if count == 0 || count > MAX_NFT_BATCH || start_id < class.first_id {
+            return Err(RevoraError::InvalidChunk);
+        }
+        let stop = core::cmp::min(start_id.saturating_add(count), end_id);
+
+        let collection = NftCollectionClient::new(&env, &class.collection);
+        let mut recorded = 0u32;
+        for nft_id in start_id..stop {
+            let key = ShareKey::OwnerAt(token.clone(), period_id, nft_id);
+            if env.storage().persistent().has(&key) {
+                continue;
+            }
+            env.storage()
+                .persistent()
+                .set(&key, &collection.owner_of(&nft_id));
+            recorded += 1;
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_NFT_SNAPSHOT, token, offering_id),
+            (period_id, start_id, recorded),
+        );
+        Ok(recorded)
+    }
+
+    /// Snapshotted owner of an NFT id for a period.
+    pub fn get_nft_owner_at(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        nft_id: u32,
+    ) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::OwnerAt(token, period_id, nft_id))
+    }
+
+    /// Per-NFT payout for a deposited period: `revenue / supply`, rounded down.
+    pub fn get_nft_payout_per_token(env: Env, token: Address, period_id: u64) -> i128 {
+        let Some(class) = Self::get_nft_share_class(env.clone(), token.clone()) else {
+            return 0;
+        };
+        let revenue: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token, period_id))
+            .unwrap_or(0);
+        revenue / class.supply as i128
+    }
+
+    /// Claim a deposited period's revenue for NFT ids the holder owned at the period's
+    /// snapshot. Each id pays `revenue / supply` once. Returns the amount paid.
+    pub fn claim_nft(
+        env: Env,
+        holder: Address,
+        token: Address,
+        period_id: u64,
+        nft_ids: Vec<u32>,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        holder.require_auth_for_args((token.clone(), period_id, nft_ids.clone()).into_val(&env));
+        match Self::is_eligible(env.clone(), token.clone(), holder.clone()) {
+            EligibilityResult::Eligible => {}
+            EligibilityResult::Blacklisted => return Err(RevoraError::HolderBlacklisted),
+            _ => return Err(RevoraError::NotEligible),
+        }
+        if nft_ids.is_empty() || nft_ids.len() > MAX_NFT_BATCH {
+            return Err(RevoraError::InvalidChunk);
+        }
+        if Self::is_period_frozen(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodFrozen);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+        {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        let revenue: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap();
+        if Self::awaiting_installments(&env, &token, period_id, revenue) {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        let per_token = Self::get_nft_payout_per_token(env.clone(), token.clone(), period_id);
+
+        for nft_id in nft_ids.iter() {
+            let owner = Self::get_nft_owner_at(env.clone(), token.clone(), period_id, nft_id);
+            if owner != Some(holder.clone()) {
+                return Err(RevoraError::NotEligible);
+            }
+            let claimed_key = ShareKey::NftClaimed(token.clone(), period_id, nft_id);
+            if env.storage().persistent().has(&claimed_key) {
+                return Err(RevoraError::NoPendingClaims);
+            }
+            env.storage().persistent().set(&claimed_key, &true);
+        }
+
+        let payout = per_token * nft_ids.len() as i128;
+        if payout > 0 {
+            let payment_token: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone()))
+                .unwrap();
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &holder,
+                &payout,
+            );
+            Self::adjust_escrow(&env, &token, -payout);
+            let paid_key = ShareKey::NftPaid(token.clone(), period_id);
+            let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&paid_key, &paid.saturating_add(payout));
+            let claimed_key = DataKey::TotalClaimed(token.clone(), holder.clone());
+            let claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&claimed_key, &claimed.saturating_add(payout));
+            Self::record_distributed(&env, &holder, payout);
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_NFT_CLAIM, holder, token, offering_id),
+            (period_id, nft_ids, payout),
+        );
+        Ok(payout)
+    }
+
+    /// True if an NFT id's share of a period has been claimed.
+    pub fn is_nft_claimed(env: Env, token: Address, period_id: u64, nft_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ShareKey::NftClaimed(token, period_id, nft_id))
+    }
+
+    // ── Share classes ───────────────────────────────────────────
+
+    /// Set the portion of a holder's share (bps) held in `class_id`. The holder's total
+    /// share (`get_holder_share`, the accrual weight) moves by the same delta and must
+    /// stay <= 10000. Only the issuer may call.
+    pub fn set_class_share(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        class_id: u32,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let previous = Self::get_class_share(env.clone(), token.clone(), holder.clone(), class_id);
+        Self::move_class_share(&env, &token, &holder, class_id, previous, share_bps)?;
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CLASS_SHARE_SET, issuer, token, offering_id),
+            (holder, class_id, share_bps),
+        );
+        Ok(())
+    }
+
+    /// Holder share (bps) held in a class (0 if unset).
+    pub fn get_class_share(env: Env, token: Address, holder: Address, class_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::ClassShare(token, class_id, holder))
+            .unwrap_or(0)
+    }
+
+    /// Define how `from_class` converts into `to_class` and when. Only the issuer may call.
+    pub fn set_class_conversion(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        from_class: u32,
+        to_class: u32,
+        rule: ClassConversion,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if from_class == to_class || rule.ratio_bps == 0 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        if rule.closes_at <= rule.opens_at {
+            return Err(RevoraError::InvalidSchedule);
+        }
+        env.storage().persistent().set(
+            &ShareKey::Conversion(token.clone(), from_class, to_class),
+            &rule,
+        );
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CONVERSION_SET, issuer, token, offering_id),
+            (from_class, to_class, rule),
+        );
+        Ok(())
+    }
+
+    /// Conversion rule between two classes, if defined.
+    pub fn get_class_conversion(
+        env: Env,
+        token: Address,
+        from_class: u32,
+        to_class: u32,
+    ) -> Option<ClassConversion> {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::Conversion(token, from_class, to_class))
+    }
+
+    /// Convert `amount` bps of the investor's `from_class` share into `to_class` at the
+    /// issuer's ratio, inside the conversion window. Returns the bps received.
+    pub fn convert_class(
+        env: Env,
+        investor: Address,
+        token: Address,
+        from_class: u32,
+        to_class: u32,
+        amount: u32,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor
+            .require_auth_for_args((token.clone(), from_class, to_class, amount).into_val(&env));
+        let rule = Self::get_class_conversion(env.clone(), token.clone(), from_class, to_class)
+            .ok_or(RevoraError::InvalidShareBps)?;
+        let now = env.ledger().timestamp();
+        if now < rule.opens_at || now >= rule.closes_at {
+            return Err(RevoraError::WindowClosed);
+        }
+        let from_share =
+            Self::get_class_share(env.clone(), token.clone(), investor.clone(), from_class);
+        if amount == 0 || amount > from_share {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let received = (amount as u64 * rule.ratio_bps as u64 / 10_000) as u32;
+        Self::move_class_share(
+            &env,
+            &token,
+            &investor,
+            from_class,
+            from_share,
+            from_share - amount,
+        )?;
+        let to_share =
+            Self::get_class_share(env.clone(), token.clone(), investor.clone(), to_class);
+        Self::move_class_share(
+            &env,
+            &token,
+            &investor,
+            to_class,
+            to_share,
+            to_share + received,
+        )?;
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CLASS_CONVERTED, investor, token, offering_id),
+            (from_class, to_class, amount, received),
+        );
+        Ok(received)
+    }
+
+    /// Replace a class share and shift the holder's total share by the same delta.
+    fn move_class_share(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        class_id: u32,
+        from: u32,
+        to: u32,
+    ) -> Result<(), RevoraError> {
+        let total = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        let new_total = (total as i64) - (from as i64) + (to as i64);
+        if !(0..=10_000).contains(&new_total) {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        Self::write_holder_share(env, token, holder, new_total as u32)?;
+        env.storage().persistent().set(
+            &ShareKey::ClassShare(token.clone(), class_id, holder.clone()),
+            &to,
+        );
+        Ok(())
+    }
+
+    // ── Transfer agent ──────────────────────────────────────────
+
+    /// Move `share_bps` of `from`'s holder share to `to` (court order, lost keys).
+    /// Requires the TransferAgent role. `reason` is a hash of the supporting document.
+    pub fn force_transfer(
+        env: Env,
+        agent: Address,
+        token: Address,
+        from: Address,
+        to: Address,
+        share_bps: u32,
+        reason: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_role(&env, Role::TransferAgent, &agent)?;
+        Self::offering_issuer(&env, &token)?;
+        Self::move_holder_share(&env, &token, &from, &to, share_bps)?;
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_FORCED_TRANSFER, agent, token, offering_id),
+            (from, to, share_bps, reason),
+        );
+        Ok(())
+    }
+
+    /// Claw `share_bps` of `from`'s holder share back to the offering issuer.
+    /// Requires the TransferAgent role.
+    pub fn clawback_shares(
+        env: Env,
+        agent: Address,
+        token: Address,
+        from: Address,
+        share_bps: u32,
+        reason: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_role(&env, Role::TransferAgent, &agent)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        Self::move_holder_share(&env, &token, &from, &issuer, share_bps)?;
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_CLAWBACK, agent, token, offering_id),
+            (from, issuer, share_bps, reason),
+        );
+        Ok(())
+    }
+
+    /// Move holder share bps between accounts. A recipient without a prior share starts
+    /// accruing from the next deposited period.
+    fn move_holder_share(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        to: &Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        let from_share = Self::get_holder_share(env.clone(), token.clone(), from.clone());
+        if share_bps == 0 || share_bps > from_share || from == to {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        let to_share = Self::get_holder_share(env.clone(), token.clone(), to.clone());
+        if to_share == 0 {
+            let period_count = Self::get_period_count(env.clone(), token.clone());
+            env.storage().persistent().set(
+                &DataKey::LastClaimedIdx(token.clone(), to.clone()),
+                &period_count,
+            );
+        }
+        Self::write_holder_share(env, token, from, from_share - share_bps)?;
+        Self::write_holder_share(env, token, to, to_share + share_bps)?;
+        Ok(())
+    }
+
+    // ── Historical balances ─────────────────────────────────────
+
+    /// Holder share (bps) in effect when `period_id` was deposited, i.e. the basis of that
+    /// period's distribution. None if the period was never deposited.
+    pub fn balance_at(env: Env, token: Address, investor: Address, period_id: u64) -> Option<u32> {
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::PeriodIndex(token.clone(), period_id))?;
+        Some(Self::share_at_index(&env, &token, &investor, index))
+    }
+
+    /// Holder share in effect for the period at `index`.
+    fn share_at_index(env: &Env, token: &Address, investor: &Address, index: u32) -> u32 {
+        let history: Vec<(u32, u32)> = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::ShareHistory(token.clone(), investor.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+        // Binary search for the last checkpoint starting at or before `index`
+        let (mut lo, mut hi) = (0u32, history.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if history.get(mid).unwrap().0 <= index {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        if lo == 0 {
+            0
+        } else {
+            history.get(lo - 1).unwrap().1
+        }
+    }
+
+    /// Store a holder share and checkpoint it from the next deposited period onwards.
+    fn write_holder_share(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        if share_bps > 0 {
+            if Self::is_nft_class(env, token) {
+                return Err(RevoraError::InvalidOfferingStatus);
+            }
+            Self::admit_investor(env, token, holder)?;
+        } else if !Self::is_whitelisted(env.clone(), token.clone(), holder.clone()) {
+            Self::release_investor(env, token, holder);
+        }
+        env.storage().persistent().set(
+            &DataKey::HolderShare(token.clone(), holder.clone()),
+            &share_bps,
+        );
+        if Self::get_accrual_mode(env.clone(), token.clone()) == AccrualMode::Shares {
+            Self::acc_set_weight(env, token, holder, share_bps as i128);
+        }
+
+        let from_index = Self::get_period_count(env.clone(), token.clone());
+        let history_key = ShareKey::ShareHistory(token.clone(), holder.clone());
+        let mut history: Vec<(u32, u32)> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some((last_index, _)) = history.last() {
+            if last_index == from_index {
+                history.pop_back();
+            }
+        }
+        history.push_back((from_index, share_bps));
+        env.storage().persistent().set(&history_key, &history);
+        Ok(())
+    }
+
+    // ── Accumulator accrual ─────────────────────────────────────
+
+    /// Choose how deposited revenue accrues. Only the issuer may call, and only before the
+    /// first deposit; in `Shares` mode, set holder shares after switching.
+    pub fn set_accrual_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        mode: AccrualMode,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if Self::get_period_count(env.clone(), token.clone()) > 0 {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        env.storage()
+            .persistent()
+            .set(&AccrualKey::Mode(token.clone()), &mode);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_ACCRUAL_MODE_SET, issuer, token, offering_id), mode);
+        Ok(())
+    }
+
+    /// Accrual mode of an offering (Periods by default).
+    pub fn get_accrual_mode(env: Env, token: Address) -> AccrualMode {
+        env.storage()
+            .persistent()
+            .get(&AccrualKey::Mode(token))
+            .unwrap_or(AccrualMode::Periods)
+    }
+
+    /// Accumulator state of an offering.
+    pub fn get_accumulator(env: Env, token: Address) -> AccumulatorState {
+        env.storage()
+            .persistent()
+            .get(&AccrualKey::State(token))
+            .unwrap_or(AccumulatorState {
+                acc_per_share: 0,
+                total_weight: 0,
+                undistributed: 0,
+            })
+    }
+
+    /// An investor's accumulator position.
+    pub fn get_accrual_position(env: Env, token: Address, investor: Address) -> AccrualPosition {
+        env.storage()
+            .persistent()
+            .get(&AccrualKey::Position(token, investor))
+            .unwrap_or_default()
+    }
+
+    /// Revenue an investor could claim now through the accumulator (before withholding),
+    /// computed from the accumulator and their stake or share without mutating state.
+    pub fn pending_rewards(env: Env, token: Address, investor: Address) -> i128 {
+        Self::acc_settle(&env, &token, &investor).accrued
+    }
+
+    /// Claim all revenue accrued through the accumulator, net of tax withholding.
+    pub fn claim_accrued(env: Env, investor: Address, token: Address) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(),).into_val(&env));
+        Self::claim_accrued_for(&env, &investor, &token)
+    }
+
+    /// `claim_accrued` body shared with `claim_for`; authorization is done by the caller.
+    fn claim_accrued_for(
+        env: &Env,
+        investor: &Address,
+        token: &Address,
+    ) -> Result<i128, RevoraError> {
+        let env = env.clone();
+        let investor = investor.clone();
+        let token = token.clone();
+        match Self::is_eligible(env.clone(), token.clone(), investor.clone()) {
+            EligibilityResult::Eligible => {}
+            EligibilityResult::Blacklisted => return Err(RevoraError::HolderBlacklisted),
+            _ => return Err(RevoraError::NotEligible),
+        }
+        let mut position = Self::acc_settle(&env, &token, &investor);
+        let gross = position.accrued;
+        if gross <= 0 {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        position.accrued = 0;
+        env.storage().persistent().set(
+            &AccrualKey::Position(token.clone(), investor.clone()),
+            &position,
+        );
+
+        let withheld = Self::withhold(&env, &token, &investor, gross);
+        let net = gross - withheld;
+        if net > 0 {
+            let payment_token: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone()))
+                .unwrap();
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &Self::get_payout_address(env.clone(), token.clone(), investor.clone()),
+                &net,
+            );
+        }
+        Self::adjust_escrow(&env, &token, -gross);
+        let claimed_key = DataKey::TotalClaimed(token.clone(), investor.clone());
+        let claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&claimed_key, &claimed.saturating_add(gross));
+        Self::record_distributed(&env, &investor, gross);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_ACCRUED_CLAIM, investor, token, offering_id),
+            (net, withheld),
+        );
+        Ok(net)
+    }
+
+    /// Spread a deposit over the current total weight: one update regardless of holders.
+    fn acc_credit(env: &Env, token: &Address, amount: i128) {
+        let mut state = Self::get_accumulator(env.clone(), token.clone());
+        let pending = amount.saturating_add(state.undistributed);
+        if state.total_weight > 0 {
+            let increment = pending * ACC_SCALE / state.total_weight;
+            state.acc_per_share += increment;
+            // Keep the rounding remainder for the next deposit
+            state.undistributed = pending - increment * state.total_weight / ACC_SCALE;
+        } else {
+            state.undistributed = pending;
+        }
+        env.storage()
+            .persistent()
+            .set(&AccrualKey::State(token.clone()), &state);
+    }
+
+    /// Settle an investor's position up to the current accumulator value (not stored).
+    fn acc_settle(env: &Env, token: &Address, investor: &Address) -> AccrualPosition {
+        let state = Self::get_accumulator(env.clone(), token.clone());
+        let mut position = Self::get_accrual_position(env.clone(), token.clone(), investor.clone());
+        let earned = position.weight * state.acc_per_share / ACC_SCALE;
+        position.accrued += earned - position.reward_debt;
+        position.reward_debt = earned;
+        position
+    }
+
+    /// Settle an investor and change their weight (and the offering's total weight).
+    fn acc_set_weight(env: &Env, token: &Address, investor: &Address, weight: i128) {
+        let mut position = Self::acc_settle(env, token, investor);
+        let mut state = Self::get_accumulator(env.clone(), token.clone());
+        state.total_weight = state.total_weight - position.weight + weight;
+        position.weight = weight;
+        position.reward_debt = weight * state.acc_per_share / ACC_SCALE;
+        env.storage()
+            .persistent()
+            .set(&AccrualKey::State(token.clone()), &state);
+        env.storage().persistent().set(
+            &AccrualKey::Position(token.clone(), investor.clone()),
+            &position,
+        );
+    }
+
+    // ── Staking ─────────────────────────────────────────────────
+
+    /// Lock offering tokens in this contract; revenue accrues to staked balances only.
+    /// The offering must be in `Staked` accrual mode. Returns the new staked balance.
+    pub fn stake(
+        env: Env,
+        investor: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(), amount).into_val(&env));
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Staked {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        if amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        token::Client::new(&env, &token).transfer(
+            &investor,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Self::track_held(&env, &token, amount);
+        let staked = Self::get_staked(env.clone(), token.clone(), investor.clone()) + amount;
+        Self::acc_set_weight(&env, &token, &investor, staked);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_STAKE, investor, token, offering_id),
+            (amount, staked),
+        );
+        Ok(staked)
+    }
+
+    /// Withdraw staked offering tokens immediately; only for offerings without an unstake
+    /// cooldown (use `request_unstake` otherwise). Accrued revenue stays claimable.
+    /// Returns the remaining staked balance.
+    pub fn unstake(
+        env: Env,
+        investor: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(), amount).into_val(&env));
+        if Self::get_unstake_cooldown(env.clone(), token.clone()) > 0 {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        let staked = Self::get_staked(env.clone(), token.clone(), investor.clone());
+        if amount <= 0 || amount > staked {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let remaining = staked - amount;
+        Self::acc_set_weight(&env, &token, &investor, remaining);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &investor,
+            &amount,
+        );
+        Self::track_held(&env, &token, -amount);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_UNSTAKE, investor, token, offering_id),
+            (amount, remaining),
+        );
+        Ok(remaining)
+    }
+
+    /// Offering tokens staked by an investor.
+    pub fn get_staked(env: Env, token: Address, investor: Address) -> i128 {
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Staked {
+            return 0;
+        }
+        Self::get_accrual_position(env, token, investor).weight
+    }
+
+    /// Set the cooldown between `request_unstake` and `withdraw_unstaked`. Only the issuer
+    /// may call.
+    pub fn set_unstake_cooldown(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        cooldown_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&AccrualKey::UnstakeCooldown(token.clone()), &cooldown_secs);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_COOLDOWN_SET, issuer, token, offering_id),
+            cooldown_secs,
+        );
+        Ok(())
+    }
+
+    /// Unstake cooldown in seconds (0 if not configured).
+    pub fn get_unstake_cooldown(env: Env, token: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AccrualKey::UnstakeCooldown(token))
+            .unwrap_or(0)
+    }
+
+    /// Stop `amount` of stake from accruing and start the cooldown. Further requests add
+    /// to the pending amount and restart the cooldown. Returns the remaining staked balance.
+    pub fn request_unstake(
+        env: Env,
+        investor: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(), amount).into_val(&env));
+        let staked = Self::get_staked(env.clone(), token.clone(), investor.clone());
+        if amount <= 0 || amount > staked {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let remaining = staked - amount;
+        Self::acc_set_weight(&env, &token, &investor, remaining);
+
+        let cooldown = Self::get_unstake_cooldown(env.clone(), token.clone());
+        let mut pending = Self::get_pending_unstake(env.clone(), token.clone(), investor.clone())
+            .unwrap_or(PendingUnstake {
+                amount: 0,
+                available_at: 0,
+            });
+        pending.amount += amount;
+        pending.available_at = env.ledger().timestamp().saturating_add(cooldown);
+        env.storage().persistent().set(
+            &AccrualKey::PendingUnstake(token.clone(), investor.clone()),
+            &pending,
+        );
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_UNSTAKE_REQ, investor, token, offering_id),
+            (amount, pending.available_at),
+        );
+        Ok(remaining)
+    }
+
+    /// Pending unstake of an investor, if any.
+    pub fn get_pending_unstake(
+        env: Env,
+        token: Address,
+        investor: Address,
+    ) -> Option<PendingUnstake> {
+        env.storage()
+            .persistent()
+            .get(&AccrualKey::PendingUnstake(token, investor))
+    }
+
+    /// Withdraw unstaked tokens once the cooldown has elapsed. Returns the amount withdrawn.
+    pub fn withdraw_unstaked(
+        env: Env,
+        investor: Address,
+        token: Address,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(),).into_val(&env));
+        let pending = Self::get_pending_unstake(env.clone(), token.clone(), investor.clone())
+            .ok_or(RevoraError::InvalidAmount)?;
+        if env.ledger().timestamp() < pending.available_at {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        env.storage()
+            .persistent()
+            .remove(&AccrualKey::PendingUnstake(token.clone(), investor.clone()));
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &investor,
+            &pending.amount,
+        );
+        Self::track_held(&env, &token, -pending.amount);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_UNSTAKE_WD, investor, token, offering_id),
+            pending.amount,
+        );
+        Ok(pending.amount)
+    }
+
+    // ── Claim managers ──────────────────────────────────────────
+
+    /// Approve a manager (custodian) who may claim and set the payout address for the
+    /// investor without further investor signatures. Replaces any previous manager.
+    pub fn approve_manager(
+        env: Env,
+        investor: Address,
+        token: Address,
+        manager: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth();
+        env.storage().persistent().set(
+            &ShareKey::ClaimManager(token.clone(), investor.clone()),
+            &manager,
+        );
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_MANAGER_SET, investor, token, offering_id),
+            Some(manager),
+        );
+        Ok(())
+    }
+
+    /// Revoke the investor's claim manager.
+    pub fn revoke_manager(env: Env, investor: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&ShareKey::ClaimManager(token.clone(), investor.clone()));
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_MANAGER_SET, investor, token, offering_id),
+            None::<Address>,
+        );
+        Ok(())
+    }
+
+    /// Claim manager of an investor, if any.
+    pub fn get_manager(env: Env, token: Address, investor: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::ClaimManager(token, investor))
+    }
+
+    /// Claim on the investor's behalf (period claims or accrued revenue, per the offering's
+    /// accrual mode). Only the approved manager may call; funds go to the payout address.
+    pub fn claim_for(
+        env: Env,
+        manager: Address,
+        investor: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_manager(&env, &manager, &token, &investor)?;
+        if Self::get_accrual_mode(env.clone(), token.clone()) == AccrualMode::Periods {
+            Self::claim_periods(&env, &investor, &token, max_periods)
+        } else {
+            Self::claim_accrued_for(&env, &investor, &token)
+        }
+    }
+
+    /// Route the investor's payouts to `payout`. The investor or their manager may call.
+    pub fn set_payout_address(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+        payout: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        if caller == investor {
+            investor.require_auth();
+        } else {
+            Self::require_manager(&env, &caller, &token, &investor)?;
+        }
+        env.storage().persistent().set(
+            &ShareKey::PayoutAddress(token.clone(), investor.clone()),
+            &payout,
+        );
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_PAYOUT_ADDR_SET, caller, token, offering_id),
+            (investor, payout),
+        );
+        Ok(())
+    }
+
+    /// Address receiving the investor's payouts (the investor unless redirected).
+    pub fn get_payout_address(env: Env, token: Address, investor: Address) -> Address {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::PayoutAddress(token, investor.clone()))
+            .unwrap_or(investor)
+    }
+
+    fn require_manager(
+        env: &Env,
+        manager: &Address,
+        token: &Address,
+        investor: &Address,
+    ) -> Result<(), RevoraError> {
+        manager.require_auth();
+        if Self::get_manager(env.clone(), token.clone(), investor.clone()) != Some(manager.clone())
+        {
+            return Err(RevoraError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    // ── Term amendments ─────────────────────────────────────────
+
+    /// Propose new material terms (revenue share, payout currency). Terms only change
+    /// once holders approve via `vote_amendment` and `execute_amendment` is called.
+    /// Only the issuer may call, once a period has been deposited (the voting basis).
+    /// Returns the amendment id.
+    pub fn propose_amendment(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        new_bps: u32,
+        new_payment_token: Option<Address>,
+    ) -> Result<u64, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if new_bps > 10_000 {
+            return Err(RevoraError::InvalidRevenueShareBps);
+        }
+        let period_count = Self::get_period_count(env.clone(), token.clone());
+        if period_count == 0 {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        let id_key = GovKey::LastAmendmentId(token.clone());
+        let id: u64 = env.storage().persistent().get(&id_key).unwrap_or(0) + 1;
+        let now = env.ledger().timestamp();
+        let amendment = Amendment {
+            new_bps,
+            new_payment_token,
+            proposed_at: now,
+            deadline: now.saturating_add(AMENDMENT_VOTING_SECS),
+            snapshot_index: period_count - 1,
+            votes_for: 0,
+            votes_against: 0,
+            status: AmendmentStatus::Pending,
+        };
+        env.storage().persistent().set(&id_key, &id);
+        env.storage()
+            .persistent()
+            .set(&GovKey::Amendment(token.clone(), id), &amendment);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_AMEND_PROPOSED, issuer, token, offering_id),
+            (
+                id,
+                amendment.new_bps,
+                amendment.new_payment_token,
+                amendment.deadline,
+            ),
+        );
+        Ok(id)
+    }
+
+    /// Amendment by id.
+    pub fn get_amendment(env: Env, token: Address, amendment_id: u64) -> Option<Amendment> {
+        env.storage()
+            .persistent()
+            .get(&GovKey::Amendment(token, amendment_id))
+    }
+
+    /// Vote on a pending amendment, weighted by the investor's share bps at the
+    /// amendment's snapshot period, so shares moved after the proposal cannot vote twice.
+    /// One vote per investor, before the deadline.
+    pub fn vote_amendment(
+        env: Env,
+        investor: Address,
+        token: Address,
+        amendment_id: u64,
+        approve: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth();
+        let mut amendment = Self::get_amendment(env.clone(), token.clone(), amendment_id)
+            .ok_or(RevoraError::ProposalNotFound)?;
+        if amendment.status != AmendmentStatus::Pending {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        if env.ledger().timestamp() >= amendment.deadline {
+            return Err(RevoraError::WindowClosed);
+        }
+        let weight = Self::share_at_index(&env, &token, &investor, amendment.snapshot_index);
+        if weight == 0 {
+            return Err(RevoraError::NotEligible);
+        }
+        let vote_key = GovKey::AmendmentVote(token.clone(), amendment_id, investor.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(RevoraError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&vote_key, &approve);
+        if approve {
+            amendment.votes_for += weight;
+        } else {
+            amendment.votes_against += weight;
+        }
+        env.storage()
+            .persistent()
+            .set(&GovKey::Amendment(token.clone(), amendment_id), &amendment);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_AMEND_VOTE, investor, token, offering_id),
+            (amendment_id, approve, weight),
+        );
+        Ok(())
+    }
+
+    /// Apply an approved amendment (more than half of all shares voted for), or mark it
+    /// rejected once the deadline passes without approval. Anyone may call. The superseded
+    /// terms are kept for past periods (`get_terms_at`). A payout currency swap requires
+    /// all escrowed revenue to have been claimed. Returns the resulting status.
+    pub fn execute_amendment(
+        env: Env,
+        token: Address,
+        amendment_id: u64,
+    ) -> Result<AmendmentStatus, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let mut amendment = Self::get_amendment(env.clone(), token.clone(), amendment_id)
+            .ok_or(RevoraError::ProposalNotFound)?;
+        if amendment.status != AmendmentStatus::Pending {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        let issuer = Self::offering_issuer(&env, &token)?;
+        let offering_id = Self::offering_id_of(&env, &token);
+
+        if amendment.votes_for <= 5_000 {
+            if env.ledger().timestamp() < amendment.deadline && amendment.votes_against < 5_000 {
+                return Err(RevoraError::WindowClosed);
+            }
+            amendment.status = AmendmentStatus::Rejected;
+            env.storage()
+                .persistent()
+                .set(&GovKey::Amendment(token.clone(), amendment_id), &amendment);
+            env.events()
+                .publish((EVENT_AMEND_REJECTED, token, offering_id), amendment_id);
+            return Ok(amendment.status);
+        }
+
+        // Snapshot the superseded terms for periods deposited so far
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        let pt_key = DataKey::PaymentToken(token.clone());
+        let previous = OfferingTerms {
+            revenue_share_bps: offering.revenue_share_bps,
+            payment_token: env.storage().persistent().get(&pt_key),
+        };
+        if let Some(new_payment_token) = amendment.new_payment_token.clone() {
+            if Self::get_escrow_balance(env.clone(), token.clone()) != 0 {
+                return Err(RevoraError::InvalidOfferingStatus);
+            }
+            env.storage().persistent().set(&pt_key, &new_payment_token);
+        }
+        let history_key = GovKey::TermsHistory(token.clone());
+        let mut history: Vec<(u32, OfferingTerms)> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        history.push_back((Self::get_period_count(env.clone(), token.clone()), previous));
+        env.storage().persistent().set(&history_key, &history);
+
+        Self::set_offering_bps(&env, &issuer, &token, amendment.new_bps);
+        amendment.status = AmendmentStatus::Applied;
+        env.storage()
+            .persistent()
+            .set(&GovKey::Amendment(token.clone(), amendment_id), &amendment);
+        env.events().publish(
+            (EVENT_AMEND_APPLIED, token, offering_id),
+            (amendment_id, amendment.new_bps, amendment.new_payment_token),
+        );
+        Ok(AmendmentStatus::Applied)
+    }
+
+    /// Terms that applied to a deposited period (current terms for periods after the last
+    /// amendment). None if the period was never deposited.
+    pub fn get_terms_at(env: Env, token: Address, period_id: u64) -> Option<OfferingTerms> {
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::PeriodIndex(token.clone(), period_id))?;
+        let history: Vec<(u32, OfferingTerms)> = env
+            .storage()
+            .persistent()
+            .get(&GovKey::TermsHistory(token.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        for (until_index, terms) in history.iter() {
+            if index < until_index {
+                return Some(terms);
+            }
+        }
+        let issuer = Self::offering_issuer(&env, &token).ok()?;
+        let offering = Self::get_offering(env.clone(), issuer, token.clone())?;
+        Some(OfferingTerms {
+            revenue_share_bps: offering.revenue_share_bps,
+            payment_token: env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token)),
+        })
+    }
+
+    /// Overwrite an offering's revenue share in the issuer's offering list.
+    fn set_offering_bps(env: &Env, issuer: &Address, token: &Address, bps: u32) {
+        Self::update_offering(env, issuer, token, |offering| {
+            offering.revenue_share_bps = bps
+        });
+        Self::refresh_state_hash(env, token);
+    }
+
+    /// Apply `f` to an offering record in the issuer's offering list and bump its
+    /// `updated_at`.
+    fn update_offering(
+        env: &Env,
+        issuer: &Address,
+        token: &Address,
+        f: impl FnOnce(&mut Offering),
+    ) {
+        let count = Self::get_offering_count(env.clone(), issuer.clone());
+        for i in 0..count {
+            let mut offering = Self::load_offering(env, issuer, i);
+            if offering.token == *token {
+                f(&mut offering);
+                offering.updated_at = env.ledger().timestamp();
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::OfferItem(issuer.clone(), i), &offering);
+                return;
+            }
+        }
+    }
+
+    /// Read an issuer's offering at `index`, rewriting it in the current layout if it
+    /// was stored by an older contract version.
+    fn load_offering(env: &Env, issuer: &Address, index: u32) -> Offering {
+        let item_key = DataKey::OfferItem(issuer.clone(), index);
+        let val: Val = env.storage().persistent().get(&item_key).unwrap();
+        let (offering, upgraded) = Offering::from_stored(env, &val);
+        if upgraded {
+            env.storage().persistent().set(&item_key, &offering);
+        }
+        offering
+    }
+
+    // ── Arbiter rulings ─────────────────────────────────────────
+
+    /// Set or clear a per-offering arbiter, who may rule on that offering's disputes
+    /// alongside global Arbiter role holders. Only admin may call. Timelocked as kind 17.
+    pub fn set_offering_arbiter(
+        env: Env,
+        token: Address,
+        arbiter: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetOfferingArbiter(token, arbiter))
+    }
+
+    /// Per-offering arbiter, if set.
+    pub fn get_offering_arbiter(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::OfferingArbiter(token))
+    }
+
+    /// Rule on a disputed report: uphold it, amend its amount, or void it. Amended and
+    /// voided periods lose their finalization (funded periods return to Deposited) and
+    /// must be re-finalized. The ruling is
+    /// stored on-chain (`get_ruling`).
+    pub fn rule_on_report(
+        env: Env,
+        arbiter: Address,
+        token: Address,
+        period_id: u64,
+        ruling: Ruling,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_arbiter(&env, &arbiter, &token)?;
+        if let Ruling::Amend(amount) = ruling {
+            if amount < 0 {
+                return Err(RevoraError::InvalidAmount);
+            }
+        }
+        let issuer = Self::offering_issuer(&env, &token)?;
+        let reports_key = DataKey::RevenueReports(issuer.clone(), token.clone());
+        let mut reports: Map<u64, (i128, u64)> = env
+            .storage()
+            .persistent()
+            .get(&reports_key)
+            .unwrap_or_else(|| Map::new(&env));
+        let (previous_amount, reported_at) =
+            reports.get(period_id).ok_or(RevoraError::ReportNotFound)?;
+
+        let status = if ruling == Ruling::Uphold {
+            DisputeStatus::Rejected
+        } else {
+            DisputeStatus::Upheld
+        };
+        Self::close_dispute(&env, &token, period_id, status)?;
+
+        if ruling != Ruling::Uphold {
+            let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
+            let mut summary: AuditSummary = env.storage().persistent().get(&summary_key).unwrap();
+            match ruling {
+                Ruling::Amend(amount) => {
+                    reports.set(period_id, (amount, reported_at));
+                    summary.total_revenue = summary.total_revenue - previous_amount + amount;
+                }
+                _ => {
+                    reports.remove(period_id);
+                    summary.total_revenue -= previous_amount;
+                    summary.report_count = summary.report_count.saturating_sub(1);
+                }
+            }
+            let amended = reports.get(period_id).map_or(0, |(a, _)| a);
+            Self::update_metrics(&env, |m| {
+                m.total_reported = m
+                    .total_reported
+                    .saturating_add(amended.saturating_sub(previous_amount))
+            });
+            env.storage().persistent().set(&reports_key, &reports);
+            env.storage().persistent().set(&summary_key, &summary);
+            env.storage()
+                .persistent()
+                .remove(&ReportKey::FinalizedPeriod(token.clone(), period_id));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::PeriodSummary(token.clone(), period_id));
+            let funded = env
+                .storage()
+                .persistent()
+                .has(&DataKey::PeriodRevenue(token.clone(), period_id));
+            if funded {
+                Self::set_period_state(&env, &token, period_id, PeriodState::Deposited);
+            } else if ruling == Ruling::Void {
+                env.storage()
+                    .persistent()
+                    .remove(&ReportKey::PeriodState(token.clone(), period_id));
+            }
+        }
+
+        let record = RulingRecord {
+            arbiter: arbiter.clone(),
+            ruling: ruling.clone(),
+            previous_amount,
+            ruled_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&BondKey::Ruling(token.clone(), period_id), &record);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_RULING, arbiter, token, offering_id),
+            (period_id, ruling, previous_amount),
+        );
+        Ok(())
+    }
+
+    /// Arbiter ruling on a period's report, if any.
+    pub fn get_ruling(env: Env, token: Address, period_id: u64) -> Option<RulingRecord> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::Ruling(token, period_id))
+    }
+
+    /// Require auth from a global Arbiter role holder or the offering's arbiter.
+    fn require_arbiter(env: &Env, arbiter: &Address, token: &Address) -> Result<(), RevoraError> {
+        arbiter.require_auth();
+        if Self::has_role(env.clone(), Role::Arbiter, arbiter.clone())
+            || Self::get_offering_arbiter(env.clone(), token.clone()) == Some(arbiter.clone())
+        {
+            return Ok(());
+        }
+        Err(RevoraError::Unauthorized)
+    }
+
+    /// Move an open dispute to a final status.
+    fn close_dispute(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        status: DisputeStatus,
+    ) -> Result<Dispute, RevoraError> {
+        let mut dispute = Self::get_dispute(env.clone(), token.clone(), period_id)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        dispute.status = status;
+        env.storage()
+            .persistent()
+            .set(&BondKey::Dispute(token.clone(), period_id), &dispute);
+        Self::adjust_open_disputes(env, token, -1);
+        Ok(dispute)
+    }
+
+    // ── Late-funding penalties ──────────────────────────────────
+
+    /// Set late-funding penalty terms for an offering. Only admin may call. Periods
+    /// declared before the terms are set accrue no penalty. Timelocked as kind 18.
+    pub fn set_penalty_terms(
+        env: Env,
+        token: Address,
+        window_secs: u64,
+        bps_per_day: u32,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(
+            &env,
+            AdminAction::SetPenaltyTerms(token, window_secs, bps_per_day),
+        )
+    }
+
+    /// Late-funding penalty terms, if configured.
+    pub fn get_penalty_terms(env: Env, token: Address) -> Option<PenaltyTerms> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::PenaltyTerms(token))
+    }
+
+    /// Penalty owed for a period: accruing while a declared period is unfunded past the
+    /// window, then fixed at the amount paid with the deposit.
+    pub fn get_penalty_owed(env: Env, token: Address, period_id: u64) -> i128 {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+        {
+            return env
+                .storage()
+                .persistent()
+                .get(&ReportKey::PenaltyPaid(token, period_id))
+                .unwrap_or(0);
+        }
+        let Some(terms) = Self::get_penalty_terms(env.clone(), token.clone()) else {
+            return 0;
+        };
+        let Some(declared_at) = env
+            .storage()
+            .persistent()
+            .get::<ReportKey, u64>(&ReportKey::DeclaredAt(token.clone(), period_id))
+        else {
+            return 0;
+        };
+        let Ok(issuer) = Self::offering_issuer(&env, &token) else {
+            return 0;
+        };
+        let principal = Self::get_period_report(env.clone(), issuer, token, period_id).unwrap_or(0);
+        let due = declared_at.saturating_add(terms.window_secs);
+        let now = env.ledger().timestamp();
+        if now <= due {
+            return 0;
+        }
+        let days_late = ((now - due) / 86_400) as i128;
+        principal * terms.bps_per_day as i128 * days_late / 10_000
+    }
+
+    // ── Shortfall interest ──────────────────────────────────────
+
+    /// Set the daily interest rate (bps) accruing on a period's shortfall after
+    /// `finalize_report_short`, owed to investors. 0 disables. Only admin may call.
+    /// Timelocked as kind 19.
+    pub fn set_shortfall_interest(
+        env: Env,
+        token: Address,
+        bps_per_day: u32,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetShortfallInterest(token, bps_per_day))
+    }
+
+    /// Daily shortfall interest rate in bps (0 if unset).
+    pub fn get_shortfall_interest(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ShortfallRate(token))
+            .unwrap_or(0)
+    }
+
+    /// Interest accrued and not yet collected on a period's outstanding shortfall:
+    /// simple interest per full day since finalization or the last `fund_shortfall`.
+    pub fn get_shortfall_interest_owed(env: Env, token: Address, period_id: u64) -> i128 {
+        let (interest, _) = Self::shortfall_interest(&env, &token, period_id);
+        interest
+    }
+
+    /// Total shortfall interest collected for a period.
+    pub fn get_shortfall_interest_paid(env: Env, token: Address, period_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::InterestPaid(token, period_id))
+            .unwrap_or(0)
+    }
+
+    /// Accrued interest and the number of whole days it covers.
+    fn shortfall_interest(env: &Env, token: &Address, period_id: u64) -> (i128, u64) {
+        let shortfall: i128 = env
+            .storage()
+            .persistent()
+            .get(&ReportKey::Shortfall(token.clone(), period_id))
+            .unwrap_or(0);
+        let rate = Self::get_shortfall_interest(env.clone(), token.clone());
+        if shortfall <= 0 || rate == 0 {
+            return (0, 0);
+        }
+        let from = Self::interest_from(env, token, period_id);
+        let days = env.ledger().timestamp().saturating_sub(from) / 86_400;
+        (shortfall * rate as i128 * days as i128 / 10_000, days)
+    }
+
+    /// Start of the current shortfall accrual: the last funding checkpoint, else the
+    /// finalization time.
+    fn interest_from(env: &Env, token: &Address, period_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::InterestFrom(token.clone(), period_id))
+            .or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get::<_, PeriodSummary>(&DataKey::PeriodSummary(token.clone(), period_id))
+                    .map(|summary| summary.finalized_at)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Pay `amount` (at most the outstanding shortfall) of a short-finalized period, plus
+    /// the interest accrued to date, into its escrow (issuer only). Both join the period's
+    /// distributable pool; holders who already claimed the period collect their part with
+    /// `claim_topup`. Fails with `InvalidPeriodState` if the period has no shortfall.
+    /// Returns the interest collected.
+    pub fn fund_shortfall(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        amount: i128,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let shortfall_key = ReportKey::Shortfall(token.clone(), period_id);
+        let shortfall: i128 = env
+            .storage()
+            .persistent()
+            .get(&shortfall_key)
+            .ok_or(RevoraError::InvalidPeriodState)?;
+        if amount <= 0 || amount > shortfall {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let (interest, days) = Self::shortfall_interest(&env, &token, period_id);
+        let total = amount.saturating_add(interest);
+        let payment_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .ok_or(RevoraError::InvalidPeriodState)?;
+        token::Client::new(&env, &payment_token).transfer(
+            &issuer,
+            &env.current_contract_address(),
+            &total,
+        );
+
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&rev_key, &revenue.saturating_add(total));
+        Self::adjust_escrow(&env, &token, total);
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods {
+            Self::acc_credit(&env, &token, total);
+        }
+        if amount == shortfall {
+            env.storage().persistent().remove(&shortfall_key);
+            Self::adjust_period_count(&env, ReportKey::OpenShortfallCount(token.clone()), false);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&shortfall_key, &(shortfall - amount));
+        }
+        if interest > 0 {
+            let paid_key = ReportKey::InterestPaid(token.clone(), period_id);
+            let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&paid_key, &paid.saturating_add(interest));
+        }
+        // Only whole days are consumed, so a partial day keeps accruing
+        if days > 0 {
+            let from = Self::interest_from(&env, &token, period_id);
+            env.storage().persistent().set(
+                &ReportKey::InterestFrom(token.clone(), period_id),
+                &from.saturating_add(days * 86_400),
+            );
+        }
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_SHORTFALL_FUNDED, issuer, token, offering_id),
+            (period_id, amount, interest),
+        );
+        Ok(interest)
+    }
+
+    /// Payouts a holder took from periods while they were short, by period.
+    fn get_short_paid(env: &Env, token: &Address, holder: &Address) -> Map<u64, i128> {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::ShortPaid(token.clone(), holder.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Collect a holder's part of shortfall funding added to a period after they claimed
+    /// it, at the share the claim was paid at. Fails with `NoPendingClaims` if nothing was
+    /// added since. The holder authorizes `(token, period_id)`.
+    pub fn claim_topup(
+        env: Env,
+        holder: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        holder.require_auth_for_args((token.clone(), period_id).into_val(&env));
+        match Self::is_eligible(env.clone(), token.clone(), holder.clone()) {
+            EligibilityResult::Eligible => {}
+            EligibilityResult::Blacklisted => return Err(RevoraError::HolderBlacklisted),
+            _ => return Err(RevoraError::NotEligible),
+        }
+        if Self::is_period_frozen(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodFrozen);
+        }
+        let mut short_paid = Self::get_short_paid(&env, &token, &holder);
+        let paid = short_paid
+            .get(period_id)
+            .ok_or(RevoraError::NoPendingClaims)?;
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::PeriodIndex(token.clone(), period_id))
+            .ok_or(RevoraError::NoPendingClaims)?;
+        let share_bps = Self::claimed_share_at(&env, &token, &holder, index);
+        let revenue: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        let payout = Self::period_payout(&env, &token, &holder, share_bps, period_id, revenue);
+        let due = payout - paid;
+        if due <= 0 {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        short_paid.set(period_id, payout);
+        env.storage().persistent().set(
+            &ShareKey::ShortPaid(token.clone(), holder.clone()),
+            &short_paid,
+        );
+        Ok(Self::pay_claim(
+            &env,
+            &holder,
+            &token,
+            due,
+            Vec::from_array(&env, [period_id]),
+        ))
+    }
+
+    // ── Period lifecycle ────────────────────────────────────────
+
+    /// Lifecycle state of a period, or `None` if it was never declared or deposited.
+    /// Declared follows from the offering's revenue report; later states are stored.
+    pub fn get_period_state(env: Env, token: Address, period_id: u64) -> Option<PeriodState> {
+        let stored = env
+            .storage()
+            .persistent()
+            .get(&ReportKey::PeriodState(token.clone(), period_id));
+        if stored.is_some() {
+            return stored;
+        }
+        let issuer = Self::offering_issuer(&env, &token).ok()?;
+        Self::get_period_report(env, issuer, token, period_id).map(|_| PeriodState::Declared)
+    }
+
+    /// Mark a finalized period distributed, committing the Merkle root of the payouts
+    /// actually made and their total (at most the period's deposit). The root is stored
+    /// (`get_payout_root`) so individual payouts can be verified or challenged.
+    /// Distribution engines only.
+    pub fn mark_period_distributed(
+        env: Env,
+        engine: Address,
+        token: Address,
+        period_id: u64,
+        payout_root: BytesN<32>,
+        total_paid: i128,
+        seq: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_engine(&env, &engine)?;
+        Self::consume_engine_seq(&env, &engine, seq)?;
+        Self::offering_issuer(&env, &token)?;
+        let deposited: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        if total_paid < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if total_paid > deposited {
+            return Err(RevoraError::InsufficientFunds);
+        }
+        Self::advance_period(&env, &engine, &token, period_id, PeriodState::Distributed)?;
+        let record = PayoutRoot {
+            engine: engine.clone(),
+            root: payout_root.clone(),
+            total_paid,
+            submitted_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&ReportKey::PayoutRoot(token.clone(), period_id), &record);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_PAYOUT_ROOT, engine, token.clone(), offering_id),
+            (period_id, payout_root, total_paid),
+        );
+        Self::advance_distribution(&env, &token, period_id, DistributionStatus::Distributed);
+        Ok(())
+    }
+
+    /// Check that `investor` was paid `amount` for a period: the leaf
+    /// `sha256(investor.to_xdr() || amount as 16-byte big-endian)` must fold up to the
+    /// committed payout root with `proof` (sorted-pair hashing, as the whitelist).
+    /// False if no root was committed.
+    pub fn verify_payout(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        investor: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let Some(committed) = Self::get_payout_root(env.clone(), token, period_id) else {
+            return false;
+        };
+        let leaf = Self::payout_leaf(&env, &investor, amount);
+        Self::merkle_root_from_proof(&env, leaf, &proof) == committed.root
+    }
+
+    /// Leaf of a payout tree: sha256 of the investor XDR followed by the big-endian amount.
+    fn payout_leaf(env: &Env, investor: &Address, amount: i128) -> BytesN<32> {
+        let mut data = investor.clone().to_xdr(env);
+        data.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Payout root committed by `mark_period_distributed`, if any.
+    pub fn get_payout_root(env: Env, token: Address, period_id: u64) -> Option<PayoutRoot> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::PayoutRoot(token, period_id))
+    }
+
+    /// Close a distributed period once reconciled. Only the issuer may call.
+    pub fn settle_period(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        Self::advance_period(&env, &issuer, &token, period_id, PeriodState::Settled)?;
+        Self::advance_distribution(&env, &token, period_id, DistributionStatus::Verified);
+        Ok(())
+    }
+
+    /// Move a period into `to` from the state immediately before it. Callers authorize.
+    fn advance_period(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+        period_id: u64,
+        to: PeriodState,
+    ) -> Result<(), RevoraError> {
+        let from = match to {
+            PeriodState::Distributed => PeriodState::Finalized,
+            _ => PeriodState::Distributed,
+        };
+        if Self::get_period_state(env.clone(), token.clone(), period_id) != Some(from) {
+            return Err(RevoraError::InvalidPeriodState);
+        }
+        Self::set_period_state(env, token, period_id, to);
+        let offering_id = Self::offering_id_of(env, token);
+        env.events().publish(
+            (
+                EVENT_PERIOD_STATE,
+                caller.clone(),
+                token.clone(),
+                offering_id,
+            ),
+            (period_id, to),
+        );
+        Ok(())
+    }
+
+    /// Record a period's state. Transitions implied by report, deposit and finalize are
+    /// already announced by their own events.
+    fn set_period_state(env: &Env, token: &Address, period_id: u64, state: PeriodState) {
+        env.storage()
+            .persistent()
+            .set(&ReportKey::PeriodState(token.clone(), period_id), &state);
+    }
+
+    // ── Escrow timeout refunds ──────────────────────────────────
+
+    /// Return a period's unclaimed escrow to the issuer when it was deposited (or
+    /// finalized) but never distributed within `ESCROW_REFUND_TIMEOUT_SECS` of the deposit.
+    /// Remaining claims on the period pay nothing; the period moves to Settled.
+    /// Per-period accrual only. Returns the refunded amount.
+    pub fn refund_escrow(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        reason: Symbol,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        match Self::get_period_state(env.clone(), token.clone(), period_id) {
+            Some(PeriodState::Deposited) | Some(PeriodState::Finalized) => {}
+            _ => return Err(RevoraError::InvalidPeriodState),
+        }
+        if Self::is_period_frozen(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodFrozen);
+        }
+        let deposited_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+        if now < deposited_at.saturating_add(ESCROW_REFUND_TIMEOUT_SECS) {
+            return Err(RevoraError::WindowClosed);
+        }
+
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap_or(0);
+        let claimed = Self::period_claimed(&env, &token, period_id);
+        let amount = (revenue - claimed).max(0);
+        env.storage().persistent().set(&rev_key, &0i128);
+        if amount > 0 {
+            let payment_token: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone()))
+                .unwrap();
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &issuer,
+                &amount,
+            );
+            Self::adjust_escrow(&env, &token, -amount);
+        }
+        Self::set_period_state(&env, &token, period_id, PeriodState::Settled);
+        env.storage().persistent().set(
+            &ReportKey::EscrowRefund(token.clone(), period_id),
+            &EscrowRefund {
+                amount,
+                reason: reason.clone(),
+                refunded_at: now,
+            },
+        );
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_ESCROW_REFUND, issuer, token, offering_id),
+            (period_id, amount, reason),
+        );
+        Ok(amount)
+    }
+
+    /// Escrow refund recorded for a period, if any.
+    pub fn get_escrow_refund(env: Env, token: Address, period_id: u64) -> Option<EscrowRefund> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::EscrowRefund(token, period_id))
+    }
+
+    /// Record that `holder` claimed period indices `start..end` at `share_bps`, and raise
+    /// the offering's claim frontier past them (and past the period at `end` when a
+    /// tranche of it was released). One read and write of each key per claim.
+    fn note_claimed(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        start: u32,
+        end: u32,
+        share_bps: u32,
+        released: bool,
+    ) {
+        let store = env.storage().persistent();
+        let reach = if released { end + 1 } else { end };
+        let frontier_key = ReportKey::ClaimFrontier(token.clone());
+        if reach > store.get(&frontier_key).unwrap_or(0) {
+            store.set(&frontier_key, &reach);
+        }
+        if end <= start {
+            return;
+        }
+        let ranges_key = ShareKey::ClaimedShares(token.clone(), holder.clone());
+        let mut ranges: Vec<(u32, u32)> = store.get(&ranges_key).unwrap_or_else(|| Vec::new(env));
+        match ranges.last() {
+            // Indices skipped before this claim (e.g. a new recipient) were never paid
+            Some((last_end, _)) if last_end < start => ranges.push_back((start, 0)),
+            None if start > 0 => ranges.push_back((start, 0)),
+            Some((last_end, last_bps)) if last_end == start && last_bps == share_bps => {
+                ranges.pop_back();
+            }
+            _ => {}
+        }
+        ranges.push_back((end, share_bps));
+        store.set(&ranges_key, &ranges);
+    }
+
+    /// True once any holder has claimed from the period (including zero payouts).
+    fn period_claimed_from(env: &Env, token: &Address, period_id: u64) -> bool {
+        let store = env.storage().persistent();
+        if store.has(&ShareKey::NftPaid(token.clone(), period_id)) {
+            return true;
+        }
+        let Some(index) = store.get::<_, u32>(&ShareKey::PeriodIndex(token.clone(), period_id))
+        else {
+            return false;
+        };
+        index
+            < store
+                .get(&ReportKey::ClaimFrontier(token.clone()))
+                .unwrap_or(0)
+    }
+
+    /// Amount paid out to holders from a period's deposit.
+    fn period_claimed(env: &Env, token: &Address, period_id: u64) -> i128 {
+        Self::period_payouts(env, token, period_id).0
+    }
+
+    /// Amount paid out to holders from a period's deposit and the tax withheld from it,
+    /// worked out from each rostered holder's claim index and the share their claims were
+    /// paid at. Reads the roster only if the period has been claimed from.
+    fn period_payouts(env: &Env, token: &Address, period_id: u64) -> (i128, i128) {
+        if !Self::period_claimed_from(env, token, period_id) {
+            return (0, 0);
+        }
+        let store = env.storage().persistent();
+        let index: u32 = store
+            .get(&ShareKey::PeriodIndex(token.clone(), period_id))
+            .unwrap_or(0);
+        let revenue: i128 = store
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        let mut claimed: i128 = store
+            .get(&ShareKey::NftPaid(token.clone(), period_id))
+            .unwrap_or(0);
+        let mut withheld: i128 = 0;
+        for i in 0..Self::get_roster_len(env.clone(), token.clone()) {
+            let holder: Address = store
+                .get(&ComplianceKey::RosterEntry(token.clone(), i))
+                .unwrap();
+            let paid = Self::holder_claimed(env, token, &holder, period_id, index, revenue);
+            if paid <= 0 {
+                continue;
+            }
+            claimed = claimed.saturating_add(paid);
+            let jurisdiction =
+                Self::get_investor_jurisdiction(env.clone(), token.clone(), holder.clone());
+            let bps = Self::effective_withholding_bps(env, token, jurisdiction);
+            withheld = withheld.saturating_add(paid * bps as i128 / 10_000);
+        }
+        (claimed, withheld)
+    }
+
+    /// Amount `holder` took from the period at deposit `index`.
+    fn holder_claimed(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        period_id: u64,
+        index: u32,
+        revenue: i128,
+    ) -> i128 {
+        let store = env.storage().persistent();
+        let next: u32 = store
+            .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+            .unwrap_or(0);
+        if index > next {
+            return 0;
+        }
+        if index == next {
+            return store
+                .get(&ShareKey::ReleasedPaid(token.clone(), holder.clone()))
+                .unwrap_or(0);
+        }
+        if let Some(paid) = Self::get_short_paid(env, token, holder).get(period_id) {
+            return paid;
+        }
+        let share_bps = Self::claimed_share_at(env, token, holder, index);
+        if share_bps == 0 {
+            return 0;
+        }
+        Self::period_payout(env, token, holder, share_bps, period_id, revenue)
+    }
+
+    /// Share `holder`'s claim of the period at deposit `index` was paid at (0 if unclaimed).
+    fn claimed_share_at(env: &Env, token: &Address, holder: &Address, index: u32) -> u32 {
+        let ranges: Vec<(u32, u32)> = env
+            .storage()
+            .persistent()
+            .get(&ShareKey::ClaimedShares(token.clone(), holder.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+        ranges
+            .iter()
+            .find(|(end, _)| index < *end)
+            .map_or(0, |(_, bps)| bps)
+    }
+
+    // ── Installment funding ─────────────────────────────────────
+
+    /// Declared payout, deposits so far and recorded shortfall of a period. `declared`
+    /// is 0 for undeclared periods and for fiat periods before finalization.
+    pub fn get_period_funding(env: Env, token: Address, period_id: u64) -> PeriodFunding {
+        PeriodFunding {
+            declared: Self::funding_target(&env, &token, period_id).unwrap_or(0),
+            deposited: env
+                .storage()
+                .persistent()
+                .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+                .unwrap_or(0),
+            shortfall: env
+                .storage()
+                .persistent()
+                .get(&ReportKey::Shortfall(token, period_id))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Payout amount a period must be funded to: the finalized amount, else the reported
+    /// amount. `None` if undeclared or fiat-reported and not yet converted.
+    fn funding_target(env: &Env, token: &Address, period_id: u64) -> Option<i128> {
+        if let Some(amount) = env
+            .storage()
+            .persistent()
+            .get(&ReportKey::FinalizedPeriod(token.clone(), period_id))
+        {
+            return Some(amount);
+        }
+        if Self::get_fiat_reporting(env.clone(), token.clone()).is_some() {
+            return None;
+        }
+        let issuer = Self::offering_issuer(env, token).ok()?;
+        Self::get_period_report(env.clone(), issuer, token.clone(), period_id)
+    }
+
+    /// True while a declared, unfinalized period is funded below its declared amount.
+    fn awaiting_installments(env: &Env, token: &Address, period_id: u64, deposited: i128) -> bool {
+        let Ok(issuer) = Self::offering_issuer(env, token) else {
+            return false;
+        };
+        if Self::get_period_report(env.clone(), issuer, token.clone(), period_id).is_none() {
+            return false;
+        }
+        if Self::get_period_state(env.clone(), token.clone(), period_id)
+            != Some(PeriodState::Deposited)
+        {
+            return false;
+        }
+        Self::funding_target(env, token, period_id).is_none_or(|target| deposited < target)
+    }
+
+    // ── Period release schedules ────────────────────────────────
+
+    /// Release a period's payouts in tranches (issuer only): each tranche unlocks `bps`
+    /// of every holder's payout `delay_secs` after the period's deposit, on top of the
+    /// offering claim delay. Delays must be strictly increasing and bps must sum to 10000;
+    /// an empty list clears the schedule. Fails with `InvalidPeriodState` once the period
+    /// has been claimed from. Applies to share claims (`claim`, `claim_for`).
+    pub fn set_release_schedule(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        tranches: Vec<ReleaseTranche>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if tranches.len() > MAX_RELEASE_TRANCHES {
+            return Err(RevoraError::InvalidSchedule);
+        }
+        if !tranches.is_empty() {
+            let mut total_bps: u32 = 0;
+            let mut last_delay: Option<u64> = None;
+            for tranche in tranches.iter() {
+                if tranche.bps == 0 || last_delay.is_some_and(|d| tranche.delay_secs <= d) {
+                    return Err(RevoraError::InvalidSchedule);
+                }
+                total_bps = total_bps.saturating_add(tranche.bps);
+                last_delay = Some(tranche.delay_secs);
+            }
+            if total_bps != 10_000 {
+                return Err(RevoraError::InvalidSchedule);
+            }
+        }
+        if Self::period_claimed_from(&env, &token, period_id) {
+            return Err(RevoraError::InvalidPeriodState);
+        }
+        let key = ReportKey::ReleaseSchedule(token.clone(), period_id);
+        let count_key = ReportKey::ScheduledPeriodCount(token.clone());
+        let existed = env.storage().persistent().has(&key);
+        if tranches.is_empty() {
+            env.storage().persistent().remove(&key);
+            if existed {
+                Self::adjust_period_count(&env, count_key, false);
+            }
+        } else {
+            env.storage().persistent().set(&key, &tranches);
+            if !existed {
+                Self::adjust_period_count(&env, count_key, true);
+            }
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_RELEASE_SET, issuer, token, offering_id),
+            (period_id, tranches),
+        );
+        Ok(())
+    }
+
+    /// Release schedule of a period (empty if paid out in one go).
+    pub fn get_release_schedule(env: Env, token: Address, period_id: u64) -> Vec<ReleaseTranche> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ReleaseSchedule(token, period_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Part of a holder's period `payout` due at `now` under the period's release
+    /// schedule, less the `paid` the holder already took, and whether every tranche is
+    /// unlocked.
+    fn released_payout(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        payout: i128,
+        paid: i128,
+        now: u64,
+    ) -> (i128, bool) {
+        if !Self::counted_any(env, ReportKey::ScheduledPeriodCount(token.clone())) {
+            return (payout, true);
+        }
+        let Some(tranches) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<ReleaseTranche>>(&ReportKey::ReleaseSchedule(token.clone(), period_id))
+        else {
+            return (payout, true);
+        };
+        let deposit_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        let mut unlocked_bps: u32 = 0;
+        for tranche in tranches.iter() {
+            if now >= deposit_time.saturating_add(tranche.delay_secs) {
+                unlocked_bps += tranche.bps;
+            }
+        }
+        let complete = unlocked_bps >= 10_000;
+        let unlocked = if complete {
+            payout
+        } else {
+            payout * (unlocked_bps as i128) / 10_000
+        };
+        ((unlocked - paid).max(0), complete)
+    }
+
+    // ── Distribution engines ────────────────────────────────────
+
+    /// Register an off-chain distribution engine (`Role::DistributionEngine`). Only admin
+    /// may call. Timelocked as kind 24.
+    pub fn add_engine(env: Env, engine: Address) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::AddEngine(engine))
+    }
+
+    /// Deregister a distribution engine. Only admin may call. Idempotent. Timelocked as
+    /// kind 13.
+    pub fn remove_engine(env: Env, engine: Address) -> Result<(), RevoraError> {
+        Self::revoke_role(env, Role::DistributionEngine, engine)
+    }
+
+    /// True if `engine` is a registered distribution engine.
+    pub fn is_engine(env: Env, engine: Address) -> bool {
+        Self::has_role(env, Role::DistributionEngine, engine)
+    }
+
+    /// Record a payout the engine made to `investor` from a finalized period. Recorded
+    /// totals may not exceed the period's deposit. Distribution engines only.
+    pub fn record_payout(
+        env: Env,
+        engine: Address,
+        token: Address,
+        period_id: u64,
+        investor: Address,
+        amount: i128,
+        seq: u64,
+    ) -> Result<(), RevoraError> {
+        let mut investors = Vec::new(&env);
+        investors.push_back(investor);
+        let mut amounts = Vec::new(&env);
+        amounts.push_back(amount);
+        Self::record_payouts(env, engine, token, period_id, investors, amounts, seq)
+    }
+
+    /// Record a chunk of up to `MAX_PAYOUT_BATCH` (50) payouts for a finalized period.
+    /// Distribution engines only.
+    pub fn record_payouts(
+        env: Env,
+        engine: Address,
+        token: Address,
+        period_id: u64,
+        investors: Vec<Address>,
+        amounts: Vec<i128>,
+        seq: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_engine(&env, &engine)?;
+        Self::consume_engine_seq(&env, &engine, seq)?;
+        Self::offering_issuer(&env, &token)?;
+        if investors.is_empty()
+            || investors.len() != amounts.len()
+            || investors.len() > MAX_PAYOUT_BATCH
+        {
+            return Err(RevoraError::InvalidChunk);
+        }
+        if Self::get_period_state(env.clone(), token.clone(), period_id)
+            != Some(PeriodState::Finalized)
+        {
+            return Err(RevoraError::InvalidPeriodState);
+        }
+        let deposited: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        let total_key = ReportKey::PayoutTotal(token.clone(), period_id);
+        let mut total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        let offering_id = Self::offering_id_of(&env, &token);
+        for (investor, amount) in investors.iter().zip(amounts.iter()) {
+            if amount <= 0 {
+                return Err(RevoraError::InvalidAmount);
+            }
+            total = total.saturating_add(amount);
+            if total > deposited {
+                return Err(RevoraError::InsufficientFunds);
+            }
+            let key = ReportKey::PayoutRecord(token.clone(), period_id, investor.clone());
+            let recorded: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&key, &recorded.saturating_add(amount));
+            env.events().publish(
+                (
+                    EVENT_PAYOUT_RECORDED,
+                    engine.clone(),
+                    token.clone(),
+                    offering_id,
+                ),
+                (period_id, investor, amount),
+            );
+        }
+        env.storage().persistent().set(&total_key, &total);
+        Ok(())
+    }
+
+    /// Payouts recorded to an investor for a period.
+    pub fn get_recorded_payout(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        investor: Address,
+    ) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::PayoutRecord(token, period_id, investor))
+            .unwrap_or(0)
+    }
+
+    /// Total payouts recorded for a period.
+    pub fn get_recorded_payout_total(env: Env, token: Address, period_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::PayoutTotal(token, period_id))
+            .unwrap_or(0)
+    }
+
+    // ── Engine liveness ─────────────────────────────────────────
+
+    /// Acknowledge that the engine picked up a period's report. The latest ack is stored
+    /// (`get_report_ack`). Distribution engines only.
+    pub fn ack_report(
+        env: Env,
+        engine: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_engine(&env, &engine)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        if Self::get_period_report(env.clone(), issuer, token.clone(), period_id).is_none() {
+            return Err(RevoraError::ReportNotFound);
+        }
+        let ack = EngineAck {
+            engine: engine.clone(),
+            acked_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&ReportKey::EngineAck(token.clone(), period_id), &ack);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_ENGINE_ACK, engine, token, offering_id), period_id);
+        Ok(())
+    }
+
+    /// Latest engine acknowledgement of a period's report, if any.
+    pub fn get_report_ack(env: Env, token: Address, period_id: u64) -> Option<EngineAck> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::EngineAck(token, period_id))
+    }
+
+    /// Timestamp of an engine's latest action (registration, ack, payout or distribution).
+    pub fn get_engine_last_seen(env: Env, engine: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::EngineLastSeen(engine))
+    }
+
+    /// Set the inactivity after which engines may be flagged stale (0 disables). Only
+    /// admin may call. Timelocked as kind 20.
+    pub fn set_engine_stale_secs(env: Env, secs: u64) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetEngineStaleSecs(secs))
+    }
+
+    /// Inactivity threshold for stale engines (0 = disabled).
+    pub fn get_engine_stale_secs(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::EngineStaleSecs)
+            .unwrap_or(0)
+    }
+
+    /// True if `engine` is registered and inactive for longer than the stale threshold.
+    pub fn is_engine_stale(env: Env, engine: Address) -> bool {
+        let stale_secs = Self::get_engine_stale_secs(env.clone());
+        if stale_secs == 0 || !Self::is_engine(env.clone(), engine.clone()) {
+            return false;
+        }
+        let last_seen = Self::get_engine_last_seen(env.clone(), engine).unwrap_or(0);
+        env.ledger().timestamp() > last_seen.saturating_add(stale_secs)
+    }
+
+    /// Deregister a stale engine so the admin can rotate in a replacement. Anyone may
+    /// call; fails with `WindowClosed` unless the engine is stale.
+    pub fn flag_stale_engine(env: Env, engine: Address) -> Result<(), RevoraError> {
+        if !Self::is_engine_stale(env.clone(), engine.clone()) {
+            return Err(RevoraError::WindowClosed);
+        }
+        Self::set_role_member(&env, Role::DistributionEngine, &engine, false);
+        let last_seen = Self::get_engine_last_seen(env.clone(), engine.clone()).unwrap_or(0);
+        env.events()
+            .publish((EVENT_ENGINE_STALE, engine), last_seen);
+        Ok(())
+    }
+
+    /// Require a registered engine's auth and record its activity.
+    fn require_engine(env: &Env, engine: &Address) -> Result<(), RevoraError> {
+        Self::require_role(env, Role::DistributionEngine, engine)?;
+        Self::touch_engine(env, engine);
+        Ok(())
+    }
+
+    /// Accept `seq` only if it is above the engine's last used sequence number, then
+    /// record it, so a retried or replayed engine call cannot apply twice.
+    fn consume_engine_seq(env: &Env, engine: &Address, seq: u64) -> Result<(), RevoraError> {
+        if seq <= Self::get_engine_seq(env.clone(), engine.clone()) {
+            return Err(RevoraError::StaleSequence);
+        }
+        env.storage()
+            .persistent()
+            .set(&AdminKey::EngineSeq(engine.clone()), &seq);
+        Ok(())
+    }
+
+    /// Last sequence number used by an engine (0 before its first sequenced call).
+    /// `record_payout(s)` and `mark_period_distributed` require a higher one.
+    pub fn get_engine_seq(env: Env, engine: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::EngineSeq(engine))
+            .unwrap_or(0)
+    }
+
+    fn touch_engine(env: &Env, engine: &Address) {
+        env.storage().persistent().set(
+            &AdminKey::EngineLastSeen(engine.clone()),
+            &env.ledger().timestamp(),
+        );
+    }
+
+    // ── Distribution status ─────────────────────────────────────
+
+    /// Whether a period has paid out, or `None` if it was never funded. Pending and
+    /// InProgress follow from the period's deposit, claims and recorded payouts;
+    /// Distributed and Verified are stored by `mark_period_distributed` and `settle_period`.
+    pub fn get_distribution_status(
+        env: Env,
+        token: Address,
+        period_id: u64,
+    ) -> Option<DistributionStatus> {
+        let store = env.storage().persistent();
+        if let Some(status) = store.get(&ReportKey::DistStatus(token.clone(), period_id)) {
+            return Some(status);
+        }
+        if Self::period_claimed_from(&env, &token, period_id)
+            || store.has(&ReportKey::PayoutTotal(token.clone(), period_id))
+        {
+            return Some(DistributionStatus::InProgress);
+        }
+        if store.has(&DataKey::PeriodRevenue(token, period_id)) {
+            return Some(DistributionStatus::Pending);
+        }
+        None
+    }
+
+    /// Record a period's Distributed or Verified status; never moves it backwards.
+    fn advance_distribution(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        status: DistributionStatus,
+    ) {
+        let key = ReportKey::DistStatus(token.clone(), period_id);
+        let current: Option<DistributionStatus> = env.storage().persistent().get(&key);
+        if current.is_none_or(|c| c < status) {
+            env.storage().persistent().set(&key, &status);
+        }
+    }
+
+    // ── Payout challenges ───────────────────────────────────────
+
+    /// Require investors to post `amount` of `bond_token` to challenge a payout root
+    /// (0 disables). Only admin may call. Timelocked as kind 21.
+    pub fn set_challenge_bond(
+        env: Env,
+        bond_token: Address,
+        amount: i128,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetChallengeBond(bond_token, amount))
+    }
+
+    /// Bond required to challenge a payout root, if configured.
+    pub fn get_challenge_bond_config(env: Env) -> Option<FeeConfig> {
+        env.storage().persistent().get(&AdminKey::ChallengeBond)
+    }
+
+    /// Challenge a period's payout root: `proof` shows the root paid the investor
+    /// `received`, which must be below their on-chain entitlement (share at the period
+    /// times its deposit). Posts the configured bond. One challenge per investor and
+    /// period; an arbiter resolves it with `resolve_payout_challenge`.
+    pub fn challenge_payout(
+        env: Env,
+        investor: Address,
+        token: Address,
+        period_id: u64,
+        received: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor.require_auth_for_args((token.clone(), period_id, received).into_val(&env));
+        if Self::get_payout_root(env.clone(), token.clone(), period_id).is_none() {
+            return Err(RevoraError::InvalidPeriodState);
+        }
+        if !Self::verify_payout(
+            env.clone(),
+            token.clone(),
+            period_id,
+            investor.clone(),
+            received,
+            proof,
+        ) {
+            return Err(RevoraError::InvalidProof);
+        }
+        let key = BondKey::PayoutChallenge(token.clone(), period_id, investor.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        let share_bps =
+            Self::balance_at(env.clone(), token.clone(), investor.clone(), period_id).unwrap_or(0);
+        let revenue: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .unwrap_or(0);
+        let entitled = Self::period_payout(&env, &token, &investor, share_bps, period_id, revenue);
+        if received >= entitled {
+            return Err(RevoraError::InvalidAmount);
+        }
+
+        let bond = Self::get_challenge_bond_config(env.clone()).filter(|b| b.amount > 0);
+        if let Some(bond) = &bond {
+            token::Client::new(&env, &bond.token).transfer(
+                &investor,
+                &env.current_contract_address(),
+                &bond.amount,
+            );
+            Self::track_held(&env, &bond.token, bond.amount);
+        }
+        let challenge = PayoutChallenge {
+            received,
+            entitled,
+            bond_amount: bond.as_ref().map_or(0, |b| b.amount),
+            bond_token: bond.map(|b| b.token),
+            opened_at: env.ledger().timestamp(),
+            status: DisputeStatus::Open,
+        };
+        env.storage().persistent().set(&key, &challenge);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_PAYOUT_CHALLENGED, investor, token, offering_id),
+            (period_id, received, entitled),
+        );
+        Ok(())
+    }
+
+    /// Resolve a payout challenge. Sustained: the bond is returned and the period's
+    /// dispute is upheld (opened if needed), so `slash_bond` can slash the issuer bond.
+    /// Rejected: the bond is forfeited to the treasury (less the insurance slice).
+    /// Arbiter role or the offering's arbiter only.
+    pub fn resolve_payout_challenge(
+        env: Env,
+        arbiter: Address,
+        token: Address,
+        period_id: u64,
+        investor: Address,
+        sustained: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_arbiter(&env, &arbiter, &token)?;
+        let key = BondKey::PayoutChallenge(token.clone(), period_id, investor.clone());
+        let mut challenge: PayoutChallenge = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if challenge.status != DisputeStatus::Open {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+
+        let contract = env.current_contract_address();
+        if let Some(bond_token) = &challenge.bond_token {
+            Self::track_held(&env, bond_token, -challenge.bond_amount);
+        }
+        if sustained {
+            challenge.status = DisputeStatus::Upheld;
+            if let Some(bond_token) = &challenge.bond_token {
+                token::Client::new(&env, bond_token).transfer(
+                    &contract,
+                    &investor,
+                    &challenge.bond_amount,
+                );
+            }
+            match Self::get_dispute(env.clone(), token.clone(), period_id) {
+                None => {
+                    let root = Self::get_payout_root(env.clone(), token.clone(), period_id)
+                        .unwrap()
+                        .root;
+                    let dispute = Dispute {
+                        claimant: investor.clone(),
+                        evidence: root,
+                        opened_at: challenge.opened_at,
+                        status: DisputeStatus::Upheld,
+                    };
+                    env.storage()
+                        .persistent()
+                        .set(&BondKey::Dispute(token.clone(), period_id), &dispute);
+                }
+                Some(d) if d.status == DisputeStatus::Open => {
+                    Self::close_dispute(&env, &token, period_id, DisputeStatus::Upheld)?;
+                }
+                Some(_) => {}
+            }
+        } else {
+            challenge.status = DisputeStatus::Rejected;
+            if let Some(bond_token) = &challenge.bond_token {
+                Self::route_protocol_fee(&env, bond_token, &contract, challenge.bond_amount)?;
+            }
+        }
+        env.storage().persistent().set(&key, &challenge);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_PAYOUT_CHALLENGE_RESOLVED, arbiter, token, offering_id),
+            (period_id, investor, challenge.status),
+        );
+        Ok(())
+    }
+
+    /// An investor's payout challenge for a period, if any.
+    pub fn get_payout_challenge(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        investor: Address,
+    ) -> Option<PayoutChallenge> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::PayoutChallenge(token, period_id, investor))
+    }
+
+    // ── Complaints ──────────────────────────────────────────────
+
+    /// File a complaint about a period. At most `MAX_COMPLAINTS_PER_PERIOD` (3) per
+    /// investor and period, and `MAX_OPEN_COMPLAINTS` (100) open per offering.
+    /// Returns the complaint id.
+    pub fn file_complaint(
+        env: Env,
+        investor: Address,
+        token: Address,
+        period_id: u64,
+        reason_hash: BytesN<32>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        investor
+            .require_auth_for_args((token.clone(), period_id, reason_hash.clone()).into_val(&env));
+        Self::offering_issuer(&env, &token)?;
+        let filed_key = BondKey::ComplaintsFiled(token.clone(), period_id, investor.clone());
+        let filed: u32 = env.storage().persistent().get(&filed_key).unwrap_or(0);
+        let open_key = BondKey::OpenComplaints(token.clone());
+        let mut open: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&open_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if filed >= MAX_COMPLAINTS_PER_PERIOD || open.len() >= MAX_OPEN_COMPLAINTS {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let id_key = BondKey::NextComplaintId(token.clone());
+        let id: u32 = env.storage().persistent().get(&id_key).unwrap_or(0);
+        env.storage().persistent().set(&id_key, &(id + 1));
+        let complaint = Complaint {
+            investor: investor.clone(),
+            period_id,
+            reason_hash: reason_hash.clone(),
+            filed_at: env.ledger().timestamp(),
+            open: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&BondKey::Complaint(token.clone(), id), &complaint);
+        open.push_back(id);
+        env.storage().persistent().set(&open_key, &open);
+        env.storage().persistent().set(&filed_key, &(filed + 1));
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_COMPLAINT_FILED, investor, token, offering_id),
+            (id, period_id, reason_hash),
+        );
+        Ok(id)
+    }
+
+    /// Close an open complaint once reviewed. Arbiter role or the offering's arbiter only.
+    pub fn close_complaint(
+        env: Env,
+        arbiter: Address,
+        token: Address,
+        complaint_id: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_arbiter(&env, &arbiter, &token)?;
+        let key = BondKey::Complaint(token.clone(), complaint_id);
+        let mut complaint: Complaint = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::DisputeNotFound)?;
+        if !complaint.open {
+            return Err(RevoraError::InvalidDisputeState);
+        }
+        complaint.open = false;
+        env.storage().persistent().set(&key, &complaint);
+        let open_key = BondKey::OpenComplaints(token.clone());
+        let mut open: Vec<u32> = env.storage().persistent().get(&open_key).unwrap();
+        if let Some(i) = open.first_index_of(complaint_id) {
+            open.remove(i);
+        }
+        env.storage().persistent().set(&open_key, &open);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_COMPLAINT_CLOSED, arbiter, token, offering_id),
+            complaint_id,
+        );
+        Ok(())
+    }
+
+    /// Complaint by id, if any.
+    pub fn get_complaint(env: Env, token: Address, complaint_id: u32) -> Option<Complaint> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::Complaint(token, complaint_id))
+    }
+
+    /// Ids of an offering's open complaints, oldest first.
+    pub fn get_open_complaints(env: Env, token: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&BondKey::OpenComplaints(token))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // ── Token rescue ────────────────────────────────────────────
+
+    /// Recover `amount` of `token_contract` sent to this contract by mistake. Only the
+    /// untracked surplus may leave: the contract balance minus the tracked held balance
+    /// (`get_held_balance`) and the insurance pool. Only admin may call.
+    pub fn rescue_tokens(
+        env: Env,
+        admin: Address,
+        token_contract: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), RevoraError> {
+        if Self::require_admin(&env)? != admin {
+            return Err(RevoraError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let client = token::Client::new(&env, &token_contract);
+        let contract = env.current_contract_address();
+        let locked = Self::get_held_balance(env.clone(), token_contract.clone()).saturating_add(
+            Self::get_insurance_pool(env.clone(), token_contract.clone()),
+        );
+        if amount > client.balance(&contract).saturating_sub(locked) {
+            return Err(RevoraError::InsufficientFunds);
+        }
+        client.transfer(&contract, &to, &amount);
+        env.events()
+            .publish((EVENT_TOKENS_RESCUED, admin, token_contract), (to, amount));
+        Ok(())
+    }
+
+    /// Balance of an asset the contract owes to investors, issuers or stakers: escrow,
+    /// undisbursed withholding, issuer and challenge bonds, and staked tokens.
+    pub fn get_held_balance(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::HeldBalance(asset))
+            .unwrap_or(0)
+    }
+
+    fn track_held(env: &Env, asset: &Address, delta: i128) {
+        let key = AdminKey::HeldBalance(asset.clone());
+        let held: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &held.saturating_add(delta));
+    }
+
+    // ── Event catalog ───────────────────────────────────────────
+
+    /// Machine-readable layouts of every event this contract emits, for indexers.
+    pub fn get_event_catalog(env: Env) -> Vec<EventSpec> {
+        let names = |list: &str| {
+            let mut out = Vec::new(&env);
+            for name in list.split(',').filter(|n| !n.is_empty()) {
+                out.push_back(Symbol::new(&env, name));
+            }
+            out
+        };
+        let mut catalog = Vec::new(&env);
+        for &(name, topics, data) in EVENT_CATALOG {
+            catalog.push_back(EventSpec {
+                name: Symbol::new(&env, name),
+                topics: names(topics),
+                data: names(data),
+            });
+        }
+        catalog
+    }
+
+    // ── Reporter committees ─────────────────────────────────────
+
+    /// Require `threshold` of `reporters` to submit matching amounts before a period's
+    /// report is accepted (`submit_report`). While a committee is set, direct reports by
+    /// the issuer or operator fail with `Unauthorized`. An empty list removes the
+    /// committee. Only the issuer may call.
+    pub fn set_reporters(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        reporters: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let key = ReportKey::ReporterSet(token.clone());
+        if let Some(previous) = Self::get_reporters(env.clone(), token.clone()) {
+            for reporter in previous.reporters.iter() {
+                env.storage()
+                    .persistent()
+                    .remove(&ReportKey::ReporterRotation(token.clone(), reporter));
+            }
+        }
+        if reporters.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            if reporters.len() > MAX_REPORTERS || threshold == 0 || threshold > reporters.len() {
+                return Err(RevoraError::InvalidReporterSet);
+            }
+            for (i, reporter) in reporters.iter().enumerate() {
+                if reporters.first_index_of(&reporter) != Some(i as u32) {
+                    return Err(RevoraError::InvalidReporterSet);
+                }
+            }
+            env.storage().persistent().set(
+                &key,
+                &ReporterSet {
+                    reporters: reporters.clone(),
+                    threshold,
+                },
+            );
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REPORTERS_SET, issuer, token, offering_id),
+            (reporters, threshold),
+        );
+        Ok(())
+    }
+
+    /// Reporter committee of an offering, if one is set.
+    pub fn get_reporters(env: Env, token: Address) -> Option<ReporterSet> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ReporterSet(token))
+    }
+
+    /// Submit (or revise) a committee reporter's amount for a period. Once `threshold`
+    /// reporters agree on an amount it is recorded as the issuer's report, with the same
+    /// guards as `report_revenue`, and the votes are cleared. Returns true if the report
+    /// was accepted by this submission.
+    pub fn submit_report(
+        env: Env,
+        reporter: Address,
+        token: Address,
+        amount: i128,
+        period_id: u64,
+        override_existing: bool,
+    ) -> Result<bool, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        reporter.require_auth();
+        let set =
+            Self::get_reporters(env.clone(), token.clone()).ok_or(RevoraError::Unauthorized)?;
+        let seat = Self::reporter_seat(&env, &set, &token, &reporter, period_id)
+            .ok_or(RevoraError::Unauthorized)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        env.storage().persistent().set(
+            &ReportKey::ReporterVote(token.clone(), period_id, seat),
+            &amount,
+        );
+        let mut matching = 0u32;
+        for member in set.reporters.iter() {
+            let vote: Option<i128> = env.storage().persistent().get(&ReportKey::ReporterVote(
+                token.clone(),
+                period_id,
+                member,
+            ));
+            if vote == Some(amount) {
+                matching += 1;
+            }
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REPORT_VOTE, reporter, token.clone(), offering_id),
+            (period_id, amount, matching),
+        );
+        if matching < set.threshold {
+            return Ok(false);
+        }
+        for member in set.reporters.iter() {
+            env.storage().persistent().remove(&ReportKey::ReporterVote(
+                token.clone(),
+                period_id,
+                member,
+            ));
+        }
+        Self::record_revenue_report(
+            env,
+            issuer,
+            token,
+            amount,
+            period_id,
+            ReportMode::Full { override_existing },
+        )?;
+        Ok(true)
+    }
+
+    /// Amount a committee reporter has pending for a period.
+    pub fn get_reporter_vote(
+        env: Env,
+        token: Address,
+        period_id: u64,
+        reporter: Address,
+    ) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ReporterVote(token, period_id, reporter))
+    }
+
+    /// Schedule a committee reporter's key rotation: `reporter` keeps reporting periods
+    /// before `effective_period` and `new_reporter` takes over the seat from it, so there
+    /// is no gap in reporting authority. `effective_period` must be after the latest
+    /// reported period (`WindowClosed`). Rescheduling replaces the pending rotation.
+    /// Only the issuer may call.
+    pub fn schedule_reporter_rotation(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        reporter: Address,
+        new_reporter: Address,
+        effective_period: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let set = Self::get_reporters(env.clone(), token.clone())
+            .ok_or(RevoraError::InvalidReporterSet)?;
+        if !set.reporters.contains(&reporter) || set.reporters.contains(&new_reporter) {
+            return Err(RevoraError::InvalidReporterSet);
+        }
+        for seat in set.reporters.iter() {
+            if seat != reporter
+                && Self::get_reporter_rotation(env.clone(), token.clone(), seat)
+                    .is_some_and(|r| r.new_reporter == new_reporter)
+            {
+                return Err(RevoraError::InvalidReporterSet);
+            }
+        }
+        let last_reported: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastReportedPeriod(token.clone()));
+        if last_reported.is_some_and(|last| effective_period <= last) {
+            return Err(RevoraError::WindowClosed);
+        }
+        env.storage().persistent().set(
+            &ReportKey::ReporterRotation(token.clone(), reporter.clone()),
+            &ReporterRotation {
+                new_reporter: new_reporter.clone(),
+                effective_period,
+            },
+        );
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_REPORTER_ROTATION, issuer, token, offering_id),
+            (reporter, new_reporter, effective_period),
+        );
+        Ok(())
+    }
+
+    /// Pending or applied key rotation of a committee seat.
+    pub fn get_reporter_rotation(
+        env: Env,
+        token: Address,
+        reporter: Address,
+    ) -> Option<ReporterRotation> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ReporterRotation(token, reporter))
+    }
+
+    /// Committee seat `reporter` may vote for on `period_id`: its own seat until a
+    /// rotation takes effect, or the seat it rotates into from the effective period.
+    fn reporter_seat(
+        env: &Env,
+        set: &ReporterSet,
+        token: &Address,
+        reporter: &Address,
+        period_id: u64,
+    ) -> Option<Address> {
+        for seat in set.reporters.iter() {
+            let rotation = Self::get_reporter_rotation(env.clone(), token.clone(), seat.clone());
+            let rotated = rotation
+                .as_ref()
+                .is_some_and(|r| period_id >= r.effective_period);
+            if seat == *reporter {
+                return if rotated { None } else { Some(seat) };
+            }
+            if rotated && rotation.is_some_and(|r| r.new_reporter == *reporter) {
+                return Some(seat);
+            }
+        }
+        None
+    }
+
+    /// Fail with `Unauthorized` if the offering reports through a reporter committee.
+    fn require_direct_reporting(env: &Env, token: &Address) -> Result<(), RevoraError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&ReportKey::ReporterSet(token.clone()))
+        {
+            return Err(RevoraError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    // ── Protocol metrics ────────────────────────────────────────
+
+    /// Protocol-wide counters maintained on writes, for dashboards without an indexer.
+    pub fn get_protocol_metrics(env: Env) -> ProtocolMetrics {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::ProtocolMetrics)
+            .unwrap_or_default()
+    }
+
+    fn update_metrics(env: &Env, f: impl FnOnce(&mut ProtocolMetrics)) {
+        let mut metrics = Self::get_protocol_metrics(env.clone());
+        f(&mut metrics);
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ProtocolMetrics, &metrics);
+    }
+
+    /// Count `investor` in `unique_investors` the first time it is seen.
+    fn note_investor(env: &Env, investor: &Address) {
+        let key = AdminKey::InvestorSeen(investor.clone());
+        if !env.storage().persistent().has(&key) {
+            env.storage().persistent().set(&key, &true);
+            Self::update_metrics(env, |m| m.unique_investors += 1);
+        }
+    }
+
+    fn record_distributed(env: &Env, investor: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        Self::note_investor(env, investor);
+        Self::update_metrics(env, |m| {
+            m.total_distributed = m.total_distributed.saturating_add(amount)
+        });
+    }
+
+    // ── State commitment ────────────────────────────────────────
+
+    /// Commitment to an offering's critical state, refreshed on every write to it:
+    /// `sha256(revenue_share_bps ‖ payment_token ‖ blacklist_hash ‖ last_finalized)`,
+    /// with the bps as 4 big-endian bytes, each optional value as a 0 byte when unset or
+    /// a 1 byte followed by its XDR (token) or 8 big-endian bytes (period), and the
+    /// 32-byte blacklist hash. None for unregistered tokens.
+    pub fn get_state_hash(env: Env, token: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&ReportKey::StateHash(token))
+    }
+
+    /// Highest finalized period of an offering. Reports for lower periods are rejected
+    /// unless an arbiter ruling reopened them.
+    pub fn get_highest_finalized_period(env: Env, token: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::MaxFinalized(token))
+    }
+
+    /// Reject reports that would reuse a period below the highest finalized one. Periods
+    /// amended or voided by `rule_on_report` stay open to the amendment flow.
+    fn require_monotonic_period(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        let highest = match Self::get_highest_finalized_period(env.clone(), token.clone()) {
+            Some(h) => h,
+            None => return Ok(()),
+        };
+        if period_id > highest {
+            return Ok(());
+        }
+        let reopened = env
+            .storage()
+            .persistent()
+            .get::<BondKey, RulingRecord>(&BondKey::Ruling(token.clone(), period_id))
+            .is_some_and(|r| r.ruling != Ruling::Uphold);
+        if reopened {
+            Ok(())
+        } else {
+            Err(RevoraError::StalePeriod)
+        }
+    }
+
+    /// Most recently finalized period of an offering.
+    pub fn get_last_finalized_period(env: Env, token: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::LastFinalized(token))
+    }
+
+    /// Number of `checkpt` events emitted so far, protocol-wide.
+    pub fn get_checkpoint_seq(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::CheckpointSeq)
+            .unwrap_or(0)
+    }
+
+    /// Emit an indexer checkpoint: protocol-wide and per-offering sequence numbers (both
+    /// gap-free from 1) with the offering's state hash.
+    fn emit_checkpoint(env: &Env, token: &Address, period_id: u64) {
+        let seq = Self::get_checkpoint_seq(env.clone()) + 1;
+        env.storage()
+            .persistent()
+            .set(&AdminKey::CheckpointSeq, &seq);
+        let offering_key = ReportKey::OfferingCheckpoints(token.clone());
+        let offering_seq: u64 = env.storage().persistent().get(&offering_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&offering_key, &offering_seq);
+        let state_hash = Self::get_state_hash(env.clone(), token.clone());
+        let offering_id = Self::offering_id_of(env, token);
+        env.events().publish(
+            (EVENT_CHECKPOINT, token.clone(), offering_id),
+            (seq, offering_seq, period_id, state_hash),
+        );
+    }
+
+    fn refresh_state_hash(env: &Env, token: &Address) {
+        let offering = match Self::offering_issuer(env, token)
+            .ok()
+            .and_then(|issuer| Self::get_offering(env.clone(), issuer, token.clone()))
+        {
+            Some(o) => o,
+            None => return,
+        };
+        let payment_token: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()));
+        let last_finalized = Self::get_last_finalized_period(env.clone(), token.clone());
+        Self::write_state_hash(
+            env,
+            token,
+            offering.revenue_share_bps,
+            payment_token,
+            last_finalized,
+        );
+    }
+
+    fn write_state_hash(
+        env: &Env,
+        token: &Address,
+        revenue_share_bps: u32,
+        payment_token: Option<Address>,
+        last_finalized: Option<u64>,
+    ) {
+        let mut preimage = Bytes::from_array(env, &revenue_share_bps.to_be_bytes());
+        match payment_token {
+            Some(pt) => {
+                preimage.push_back(1);
+                preimage.append(&pt.to_xdr(env));
+            }
+            None => preimage.push_back(0),
+        }
+        preimage.append(&Bytes::from_array(
+            env,
+            &Self::get_blacklist_hash(env.clone(), token.clone()).to_array(),
+        ));
+        match last_finalized {
+            Some(period_id) => {
+                preimage.push_back(1);
+                preimage.append(&Bytes::from_array(env, &period_id.to_be_bytes()));
+            }
+            None => preimage.push_back(0),
+        }
+        let hash = env.crypto().sha256(&preimage).to_bytes();
+        env.storage()
+            .persistent()
+            .set(&ReportKey::StateHash(token.clone()), &hash);
+    }
+
+    // ── Batch execution ─────────────────────────────────────────
+
+    /// Run up to 20 operations atomically as `caller`: if any fails, the whole batch
+    /// reverts with that error. Fails with `InvalidChunk` if `ops` is empty or too long.
+    /// Returns the number of operations applied.
+    pub fn batch(env: Env, caller: Address, ops: Vec<BatchOp>) -> Result<u32, RevoraError> {
+        if ops.is_empty() || ops.len() > MAX_BATCH_OPS {
+            return Err(RevoraError::InvalidChunk);
+        }
+        // Authorized once here: the host rejects a second require_auth in the same frame.
+        caller.require_auth();
+        for op in ops.iter() {
+            let c = caller.clone();
+            match op {
+                BatchOp::BlacklistAdd(token, investor) => {
+                    Self::apply_blacklist_add(env.clone(), c, token, investor)?
+                }
+                BatchOp::BlacklistRemove(token, investor) => {
+                    Self::apply_blacklist_remove(env.clone(), c, token, investor)?
+                }
+                BatchOp::SetKycStatus(token, investor, verified) => {
+                    Self::apply_set_kyc_status(env.clone(), c, token, investor, verified)?
+                }
+                BatchOp::SetInvestorFrozen(token, investor, frozen) => {
+                    Self::apply_set_investor_frozen(env.clone(), c, token, investor, frozen)?
+                }
+                BatchOp::SetHolderShare(token, holder, share_bps) => {
+                    Self::apply_set_holder_share(env.clone(), c, token, holder, share_bps)?
+                }
+                BatchOp::SetRoundingMode(token, mode) => {
+                    Self::apply_set_rounding_mode(env.clone(), c, token, mode)?
+                }
+            }
+        }
+        Ok(ops.len())
+    }
+
+    // ── Factory mode ────────────────────────────────────────────
+
+    /// Enable factory mode with the child contract WASM hash, or disable it with `None`
+    /// (admin only). Only offerings registered while enabled get a child. Timelocked as
+    /// kind 22.
+    pub fn set_child_wasm_hash(env: Env, wasm_hash: Option<BytesN<32>>) -> Result<(), RevoraError> {
+        let action = match wasm_hash {
+            Some(hash) => AdminAction::SetChildWasmHash(hash),
+            None => AdminAction::ClearChildWasmHash,
+        };
+        Self::run_admin_action(&env, action)
+    }
+
+    /// Child contract WASM hash, if factory mode is enabled.
+    pub fn get_child_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&AdminKey::ChildWasmHash)
+    }
+
+    /// Child contract deployed for `token`, if it was registered in factory mode.
+    pub fn get_offering_child(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&IndexKey::OfferingChild(token))
+    }
+
+    /// Deploy and initialize the offering's child contract when factory mode is enabled.
+    /// The salt is the offering id, so the child address is deterministic.
+    fn deploy_offering_child(env: &Env, issuer: &Address, token: &Address, offering_id: u64) {
+        let wasm_hash: BytesN<32> = match env.storage().persistent().get(&AdminKey::ChildWasmHash) {
+            Some(hash) => hash,
+            None => return,
+        };
+        let mut salt = [0u8; 32];
+        salt[24..].copy_from_slice(&offering_id.to_be_bytes());
+        let child = env
+            .deployer()
+            .with_current_contract(BytesN::from_array(env, &salt))
+            .deploy(wasm_hash);
+        OfferingChildClient::new(env, &child).init(
+            &env.current_contract_address(),
+            issuer,
+            token,
+            &offering_id,
+        );
+        env.storage()
+            .persistent()
+            .set(&IndexKey::OfferingChild(token.clone()), &child);
+        env.events().publish(
+            (
+                EVENT_CHILD_DEPLOYED,
+                issuer.clone(),
+                token.clone(),
+                offering_id,
+            ),
+            child,
+        );
+    }
+
+    // ── Cross-chain reports ─────────────────────────────────────
+
+    /// Register (or remove with `None`) the bridge contract allowed to report revenue
+    /// earned on other chains for the offering (issuer only).
+    pub fn set_bridge(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        bridge: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let key = ReportKey::Bridge(token.clone());
+        match &bridge {
+            Some(addr) => env.storage().persistent().set(&key, addr),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_BRIDGE_SET, issuer, token, offering_id), bridge);
+        Ok(())
+    }
+
+    /// Bridge contract registered for the offering, if any.
+    pub fn get_bridge(env: Env, token: Address) -> Option<Address> {
+        env.storage().persistent().get(&ReportKey::Bridge(token))
+    }
+
+    /// Record revenue attested by the offering's bridge as the issuer's report, with the
+    /// same guards as `report_revenue`. Each `message_id` is accepted once. Rejected with
+    /// `Unauthorized` while a reporter committee is set: the issuer could name itself the
+    /// bridge and bypass the committee.
+    pub fn submit_bridge_report(
+        env: Env,
+        bridge: Address,
+        token: Address,
+        report: BridgeReport,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        bridge.require_auth();
+        if Self::get_bridge(env.clone(), token.clone()) != Some(bridge) {
+            return Err(RevoraError::Unauthorized);
+        }
+        Self::require_direct_reporting(&env, &token)?;
+        let issuer = Self::offering_issuer(&env, &token)?;
+        let seen_key = ReportKey::BridgeMessage(token.clone(), report.message_id.clone());
+        if env.storage().persistent().has(&seen_key) {
+            return Err(RevoraError::MessageReplayed);
+        }
+        env.storage().persistent().set(&seen_key, &report.period_id);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_BRIDGE_REPORT, token.clone(), offering_id),
+            (
+                report.source_chain,
+                report.message_id,
+                report.period_id,
+                report.amount,
+            ),
+        );
+        Self::record_revenue_report(
+            env,
+            issuer,
+            token,
+            report.amount,
+            report.period_id,
+            ReportMode::Full {
+                override_existing: report.override_existing,
+            },
+        )
+    }
+
+    /// Period recorded for a consumed bridge message, if it was accepted.
+    pub fn get_bridge_message(env: Env, token: Address, message_id: BytesN<32>) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::BridgeMessage(token, message_id))
+    }
+
+    // ── Event verbosity ─────────────────────────────────────────
+
+    /// Set the protocol default verbosity of revenue report events (admin only).
+    /// Timelocked as kind 23.
+    pub fn set_default_event_verbosity(
+        env: Env,
+        verbosity: EventVerbosity,
+    ) -> Result<(), RevoraError> {
+        Self::run_admin_action(&env, AdminAction::SetDefaultEventVerbosity(verbosity))
+    }
+
+    /// Override the event verbosity for one offering, or follow the protocol default
+    /// with `None` (issuer only).
+    pub fn set_event_verbosity(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        verbosity: Option<EventVerbosity>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let key = ReportKey::EventVerbosity(token.clone());
+        match verbosity {
+            Some(v) => env.storage().persistent().set(&key, &v),
+            None => env.storage().persistent().remove(&key),
+        }
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_VERBOSITY_SET, issuer, token, offering_id), verbosity);
+        Ok(())
+    }
+
+    /// Effective event verbosity for the offering: its override, else the protocol
+    /// default, else `Full`.
+    pub fn get_event_verbosity(env: Env, token: Address) -> EventVerbosity {
+        let store = env.storage().persistent();
+        store
+            .get(&ReportKey::EventVerbosity(token))
+            .or_else(|| store.get(&AdminKey::DefaultVerbosity))
+            .unwrap_or(EventVerbosity::Full)
+    }
+
+    /// Blacklist as embedded in report events: the full list or its hash.
+    fn blacklist_payload(env: &Env, token: &Address) -> Val {
+        match Self::get_event_verbosity(env.clone(), token.clone()) {
+            EventVerbosity::Full => Self::get_blacklist(env.clone(), token.clone()).into_val(env),
+            EventVerbosity::Compact => {
+                Self::get_blacklist_hash(env.clone(), token.clone()).into_val(env)
+            }
+        }
+    }
+
+    // ── Zero-amount reports ─────────────────────────────────────
+
+    /// Set how the offering treats zero-revenue periods (issuer only).
+    pub fn set_zero_report_policy(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        policy: ZeroReportPolicy,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        env.storage()
+            .persistent()
+            .set(&ReportKey::ZeroReportPolicy(token.clone()), &policy);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_ZERO_POLICY_SET, issuer, token, offering_id), policy);
+        Ok(())
+    }
+
+    /// Zero-revenue report policy of an offering. Defaults to `Unrestricted`.
+    pub fn get_zero_report_policy(env: Env, token: Address) -> ZeroReportPolicy {
+        env.storage()
+            .persistent()
+            .get(&ReportKey::ZeroReportPolicy(token))
+            .unwrap_or(ZeroReportPolicy::Unrestricted)
+    }
+
+    /// Apply the offering's zero-report policy to a report of `amount` for `period_id`.
+    /// Under `Required`, a period may not be skipped: it must follow the last reported one.
+    fn check_zero_report_policy(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        match Self::get_zero_report_policy(env.clone(), token.clone()) {
+            ZeroReportPolicy::Unrestricted => Ok(()),
+            ZeroReportPolicy::Forbidden => {
+                if amount <= 0 {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                Ok(())
+            }
+            ZeroReportPolicy::Required => {
+                if amount < 0 {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                let last: Option<u64> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::LastReportedPeriod(token.clone()));
+                if last.is_some_and(|p| period_id > p.saturating_add(1)) {
+                    return Err(RevoraError::MissedReportsOutstanding);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // ── Token migration ─────────────────────────────────────────
+
+    /// Propose replacing the offering's share token with `new_token` (issuer only).
+    /// `migrate_token` may run once the 7-day timelock has elapsed; proposing again
+    /// replaces the pending migration and restarts the timelock. Returns the eta.
+    pub fn propose_token_migration(
+        env: Env,
+        issuer: Address,
+        old_token: Address,
+        new_token: Address,
+    ) -> Result<u64, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &old_token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        Self::require_migration_target(&env, &old_token, &new_token)?;
+        let now = env.ledger().timestamp();
+        let migration = TokenMigration {
+            new_token: new_token.clone(),
+            proposed_at: now,
+            eta: now.saturating_add(TOKEN_MIGRATION_DELAY_SECS),
+        };
+        env.storage()
+            .persistent()
+            .set(&GovKey::PendingMigration(old_token.clone()), &migration);
+        let offering_id = Self::offering_id_of(&env, &old_token);
+        env.events().publish(
+            (EVENT_MIGRATION_PROPOSED, issuer, old_token, offering_id),
+            (new_token, migration.eta),
+        );
+        Ok(migration.eta)
+    }
+
+    /// Cancel the offering's pending token migration (issuer only).
+    pub fn cancel_token_migration(
+        env: Env,
+        issuer: Address,
+        old_token: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &old_token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let migration = Self::get_pending_token_migration(env.clone(), old_token.clone())
+            .ok_or(RevoraError::ProposalNotFound)?;
+        env.storage()
+            .persistent()
+            .remove(&GovKey::PendingMigration(old_token.clone()));
+        let offering_id = Self::offering_id_of(&env, &old_token);
+        env.events().publish(
+            (EVENT_MIGRATION_CANCELLED, issuer, old_token, offering_id),
+            migration.new_token,
+        );
+        Ok(())
+    }
+
+    /// Pending token migration of an offering, if any.
+    pub fn get_pending_token_migration(env: Env, old_token: Address) -> Option<TokenMigration> {
+        env.storage()
+            .persistent()
+            .get(&GovKey::PendingMigration(old_token))
+    }
+
+    /// Replacement token of a migrated offering, if it was migrated.
+    pub fn get_migrated_token(env: Env, old_token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&GovKey::MigratedTo(old_token))
+    }
+
+    /// Execute the pending migration of `old_token` to `new_token` once its timelock has
+    /// elapsed (issuer only). Moves the offering record, ids, configuration, blacklist,
+    /// escrow and per-period history to the new token key; per-holder state (shares,
+    /// claim cursors, claim history) follows via `migrate_token_holders`. Records keyed by
+    /// id or by (period, investor) stay readable under the old token.
+    pub fn migrate_token(
+        env: Env,
+        issuer: Address,
+        old_token: Address,
+        new_token: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &old_token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        let migration = Self::get_pending_token_migration(env.clone(), old_token.clone())
+            .ok_or(RevoraError::ProposalNotFound)?;
+        if migration.new_token != new_token {
+            return Err(RevoraError::InvalidMigration);
+        }
+        Self::require_migration_target(&env, &old_token, &new_token)?;
+        if env.ledger().timestamp() < migration.eta {
+            return Err(RevoraError::TimelockNotElapsed);
+        }
+        let store = env.storage().persistent();
+        store.remove(&GovKey::PendingMigration(old_token.clone()));
+
+        let offering_id = Self::offering_id_of(&env, &old_token);
+        let status = Self::get_offering_status(env.clone(), old_token.clone());
+        Self::update_offering(&env, &issuer, &old_token, |o| o.token = new_token.clone());
+        Self::status_index_remove(&env, &issuer, status, &old_token);
+        Self::status_index_insert(&env, &issuer, status, &new_token);
+        store.set(
+            &IndexKey::OfferingById(offering_id),
+            &(issuer.clone(), new_token.clone()),
+        );
+        Self::move_offering_state(&env, &issuer, &old_token, &new_token);
+        store.set(&GovKey::MigratedTo(old_token.clone()), &new_token);
+        Self::refresh_state_hash(&env, &new_token);
+
+        env.events().publish(
+            (EVENT_TOKEN_MIGRATED, issuer, old_token, offering_id),
+            new_token,
+        );
+        Ok(())
+    }
+
+    /// Move per-holder state (share, claim cursor and history, compliance flags, payout
+    /// settings, accrual position) of up to 100 `holders` from a migrated offering's old
+    /// token to its new one (issuer only). Holders already moved are skipped.
+    /// Returns the number of holders moved.
+    pub fn migrate_token_holders(
+        env: Env,
+        issuer: Address,
+        old_token: Address,
+        holders: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        let new_token = Self::get_migrated_token(env.clone(), old_token.clone())
+            .ok_or(RevoraError::ProposalNotFound)?;
+        if Self::offering_issuer(&env, &new_token)? != issuer {
+            return Err(RevoraError::Unauthorized);
+        }
+        if holders.len() > MAX_IMPORT_CHUNK {
+            return Err(RevoraError::InvalidChunk);
+        }
+        let mut moved = 0u32;
+        for holder in holders.iter() {
+            if Self::move_holder_state(&env, &old_token, &new_token, &holder) {
+                moved += 1;
+            }
+        }
+        let offering_id = Self::offering_id_of(&env, &new_token);
+        env.events().publish(
+            (EVENT_HOLDERS_MIGRATED, issuer, new_token, offering_id),
+            moved,
+        );
+        Ok(moved)
+    }
+
+    /// `new_token` must differ from `old_token` and not be registered or migrated.
+    fn require_migration_target(
+        env: &Env,
+        old_token: &Address,
+        new_token: &Address,
+    ) -> Result<(), RevoraError> {
+        let store = env.storage().persistent();
+        if new_token == old_token
+            || store.has(&DataKey::OfferingIssuer(new_token.clone()))
+            || store.has(&GovKey::MigratedTo(new_token.clone()))
+        {
+            return Err(RevoraError::InvalidMigration);
+        }
+        Ok(())
+    }
+
+    /// Move a storage entry, whatever its value type, if present. Returns true if moved.
+    fn move_entry<K: IntoVal<Env, Val>>(env: &Env, from: K, to: K) -> bool {
+        if Self::copy_entry(env, &from, &to) {
+            env.storage().persistent().remove(&from);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Copy a storage entry, whatever its value type, if present. Returns true if copied.
+    fn copy_entry<K: IntoVal<Env, Val>>(env: &Env, from: &K, to: &K) -> bool {
+        let store = env.storage().persistent();
+        match store.get::<K, Val>(from) {
+            Some(value) => {
+                store.set(to, &value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn move_offering_state(env: &Env, issuer: &Address, old: &Address, new: &Address) {
+        // Keys of the form Key(token)
+        macro_rules! mv {
+            ($($key:path),* $(,)?) => {
+                $(Self::move_entry(env, $key(old.clone()), $key(new.clone()));)*
+            };
+        }
+        // Keys of the form Key(issuer, token)
+        macro_rules! mv_issuer {
+            ($($key:path),* $(,)?) => {
+                $(Self::move_entry(
+                    env,
+                    $key(issuer.clone(), old.clone()),
+                    $key(issuer.clone(), new.clone()),
+                );)*
+            };
+        }
+        // Keys of the form Key(token, n) for a period id, jurisdiction code, amendment id or
+        // series
+        macro_rules! mv_period {
+            ($p:expr; $($key:path),* $(,)?) => {
+                $(Self::move_entry(env, $key(old.clone(), $p), $key(new.clone(), $p));)*
+            };
+        }
+
+        let reports: Map<u64, (i128, u64)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RevenueReports(issuer.clone(), old.clone()))
+            .unwrap_or_else(|| Map::new(env));
+        let mut periods = reports.keys();
+        let period_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodCount(old.clone()))
+            .unwrap_or(0);
+        for i in 0..period_count {
+            let entry = DataKey::PeriodEntry(old.clone(), i);
+            if let Some(p) = env.storage().persistent().get::<DataKey, u64>(&entry) {
+                if !reports.contains_key(p) {
+                    periods.push_back(p);
+                }
+            }
+            Self::move_entry(env, entry, DataKey::PeriodEntry(new.clone(), i));
+        }
+        for p in periods.iter() {
+            mv_period!(p;
+                DataKey::PeriodRevenue, DataKey::PeriodDepositTime, DataKey::LateReport,
+                DataKey::PeriodSummary, ReportKey::FinalizedPeriod, ReportKey::AuditorAttestation,
+                ReportKey::SignedAttestation, ReportKey::FiatConversion,
+                ReportKey::StructuredReport, ReportKey::ReviewFlag, ReportKey::DeclaredAt,
+                ReportKey::PenaltyPaid, ReportKey::PeriodState, ShareKey::NftPaid,
+                ReportKey::EscrowRefund, ReportKey::Shortfall, ReportKey::PayoutTotal,
+                ReportKey::EngineAck, ReportKey::DistStatus, ReportKey::PayoutRoot,
+                BondKey::Dispute, BondKey::Slash, BondKey::Compensated, BondKey::Ruling,
+                ShareKey::PeriodIndex, ReportKey::ReleaseSchedule, ReportKey::InterestFrom,
+                ReportKey::InterestPaid, ReportKey::FrozenPeriod,
+            );
+        }
+
+        // Keys of the form Key(token, jurisdiction_code)
+        let mut codes = Self::jurisdiction_codes(env, old);
+        codes.push_front(0);
+        for code in codes.iter() {
+            mv_period!(code;
+                DataKey::WithholdingBps, DataKey::WithheldBalance, DataKey::WithholdingRecipient,
+                DataKey::WithholdingRemitted, ComplianceKey::JurisdictionBlocked,
+            );
+        }
+        let last_amendment: u64 = env
+            .storage()
+            .persistent()
+            .get(&GovKey::LastAmendmentId(old.clone()))
+            .unwrap_or(0);
+        for id in 1..=last_amendment {
+            mv_period!(id; GovKey::Amendment);
+        }
+        for series in 0..Self::get_series_count(env.clone(), old.clone()) {
+            mv_period!(series; IndexKey::SeriesArchive);
         }
 
-        // Transfer only if there is a positive payout
-        if total_payout > 0 {
-            let pt_key = DataKey::PaymentToken(token.clone());
-            let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
-            let contract_addr = env.current_contract_address();
-            token::Client::new(&env, &payment_token).transfer(
-                &contract_addr,
-                &holder,
-                &total_payout,
+        mv_issuer!(
+            DataKey::ConcentrationLimit,
+            DataKey::CurrentConcentration,
+            DataKey::AuditSummary,
+            DataKey::RoundingMode,
+            DataKey::RevenueReports,
+        );
+        for slot in 0..Self::blacklist_len(env, old) {
+            let slot_key = ComplianceKey::BlacklistSlot(old.clone(), slot);
+            if let Some(investor) = env.storage().persistent().get::<_, Address>(&slot_key) {
+                Self::move_entry(
+                    env,
+                    ComplianceKey::BlacklistMember(old.clone(), investor.clone()),
+                    ComplianceKey::BlacklistMember(new.clone(), investor),
+                );
+            }
+            Self::move_entry(
+                env,
+                slot_key,
+                ComplianceKey::BlacklistSlot(new.clone(), slot),
             );
         }
-
-        // Advance claim index only for periods actually claimed (respecting delay)
-        env.storage().persistent().set(&idx_key, &last_claimed_idx);
-
-        env.events().publish(
-            (EVENT_CLAIM, holder.clone(), token),
-            (total_payout, claimed_periods),
+        mv!(
+            DataKey::Blacklist,
+            DataKey::PeriodCount,
+            DataKey::PaymentToken,
+            DataKey::ClaimDelaySecs,
+            DataKey::ReportingSchedule,
+            DataKey::OfferingStatus,
+            DataKey::LastReportedPeriod,
+            DataKey::SuspendAfterMissed,
+            DataKey::DelinquencyNotified,
+            DataKey::EscrowBalance,
+            DataKey::OpenDisputeCount,
+            DataKey::OfferingIssuer,
+            DataKey::OfferingOperator,
+            DataKey::DistributionStrategy,
+            ReportKey::ClaimFrontier,
+            ReportKey::FrozenPeriodCount,
+            ReportKey::ScheduledPeriodCount,
+            ReportKey::OpenShortfallCount,
+            IndexKey::OfferingIdOf,
+            IndexKey::OfferingChild,
+            IndexKey::SeriesCount,
+            ComplianceKey::WhitelistRoot,
+            ComplianceKey::BlacklistHash,
+            ComplianceKey::BlacklistCount,
+            ComplianceKey::KycRequired,
+            ComplianceKey::ComplianceModule,
+            ComplianceKey::InvestorCap,
+            ComplianceKey::InvestorCount,
+            ComplianceKey::JurisdictionCodes,
+            ReportKey::ReportListeners,
+            ReportKey::AuditorConfig,
+            ReportKey::AccountingKey,
+            ReportKey::FiatReporting,
+            ReportKey::DeductionCapBps,
+            ReportKey::PenaltyTerms,
+            ReportKey::ShortfallRate,
+            ReportKey::FundingSource,
+            ReportKey::ReporterSet,
+            ReportKey::LastFinalized,
+            ReportKey::OfferingCheckpoints,
+            ReportKey::Bridge,
+            ReportKey::EventVerbosity,
+            ReportKey::MaxFinalized,
+            ReportKey::ZeroReportPolicy,
+            BondKey::OfferingBond,
+            BondKey::OfferingArbiter,
+            ShareKey::NftClass,
+            GovKey::LastAmendmentId,
+            GovKey::TermsHistory,
+            AccrualKey::Mode,
+            AccrualKey::State,
+            AccrualKey::UnstakeCooldown,
         );
-
-        Ok(total_payout)
+        env.storage()
+            .persistent()
+            .remove(&ReportKey::StateHash(old.clone()));
     }
 
-    /// Return unclaimed period IDs for a holder on an offering.
-    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder);
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
-
-        let mut periods = Vec::new(&env);
-        for i in start_idx..period_count {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            periods.push_back(period_id);
+    /// Move one holder's state between token keys. Returns true if the holder had any.
+    fn move_holder_state(env: &Env, old: &Address, new: &Address, holder: &Address) -> bool {
+        // Keys of the form Key(token, holder)
+        macro_rules! mv {
+            ($($key:path),* $(,)?) => {
+                false $(| Self::move_entry(
+                    env,
+                    $key(old.clone(), holder.clone()),
+                    $key(new.clone(), holder.clone()),
+                ))*
+            };
         }
-        periods
-    }
-
-    /// Preview the total claimable amount for a holder without claiming.
-    /// Respects per-offering claim delay (#27): only sums periods past the delay.
-    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
-        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
-            return 0;
+        let history_key = DataKey::ClaimHistoryCount(old.clone(), holder.clone());
+        let records: u32 = env.storage().persistent().get(&history_key).unwrap_or(0);
+        for page in 0..records.div_ceil(CLAIM_HISTORY_PAGE_SIZE) {
+            Self::move_entry(
+                env,
+                DataKey::ClaimHistory(old.clone(), holder.clone(), page),
+                DataKey::ClaimHistory(new.clone(), holder.clone(), page),
+            );
+        }
+        let moved = mv!(
+            DataKey::HolderShare,
+            DataKey::LastClaimedIdx,
+            DataKey::ClaimHistoryCount,
+            DataKey::TotalClaimed,
+            DataKey::InvestorJurisdiction,
+            ComplianceKey::WhitelistedUnder,
+            ComplianceKey::KycVerified,
+            ComplianceKey::InvestorFrozen,
+            ComplianceKey::InvestorAdmitted,
+            ShareKey::ShareHistory,
+            ShareKey::ClaimManager,
+            ShareKey::PayoutAddress,
+            AccrualKey::Position,
+            AccrualKey::PendingUnstake,
+            ShareKey::ReleasedPaid,
+            ShareKey::ShortPaid,
+            ShareKey::ClaimedShares,
+        );
+        let last_amendment: u64 = env
+            .storage()
+            .persistent()
+            .get(&GovKey::LastAmendmentId(new.clone()))
+            .unwrap_or(0);
+        for id in 1..=last_amendment {
+            Self::move_entry(
+                env,
+                GovKey::AmendmentVote(old.clone(), id, holder.clone()),
+                GovKey::AmendmentVote(new.clone(), id, holder.clone()),
+            );
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&ComplianceKey::InvestorAdmitted(
+                new.clone(),
+                holder.clone(),
+            ))
+        {
+            Self::enroll_investor(env, new, holder);
         }
+        moved
+    }
 
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    // ── Offering merges ─────────────────────────────────────────
 
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+    /// Merge the settled `source` offering into `target`, both of `issuer`, and close
+    /// `source` (issuer only). `holders` (at most 100) must list every source holder:
+    /// each one's share and lifetime claimed total are added to its target entry, in list
+    /// order. Source blacklist members are added to the target blacklist. Fails with
+    /// `EscrowOutstanding` while source escrow remains, `InvalidShareBps` if a combined
+    /// share exceeds 10000, `HoldersIncomplete` if a source holder was left out. A holder
+    /// new to `target` accrues from its next deposited period. Returns the number of
+    /// holders with a non-zero source share.
+    pub fn merge_offerings(
+        env: Env,
+        issuer: Address,
+        source: Address,
+        target: Address,
+        holders: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
+        issuer.require_auth();
+        if Self::offering_issuer(&env, &source)? != issuer
+            || Self::offering_issuer(&env, &target)? != issuer
+        {
+            return Err(RevoraError::Unauthorized);
+        }
+        if source == target
+            || Self::get_offering_status(env.clone(), source.clone()) == OfferingStatus::Closed
+            || Self::get_offering_status(env.clone(), target.clone()) == OfferingStatus::Closed
+            || Self::get_accrual_mode(env.clone(), source.clone()) != AccrualMode::Periods
+            || Self::get_accrual_mode(env.clone(), target.clone()) != AccrualMode::Periods
+        {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        if holders.len() > MAX_IMPORT_CHUNK {
+            return Err(RevoraError::InvalidChunk);
+        }
+        if Self::get_escrow_balance(env.clone(), source.clone()) != 0 {
+            return Err(RevoraError::EscrowOutstanding);
+        }
+        let store = env.storage().persistent();
+        let source_payment: Option<Address> = store.get(&DataKey::PaymentToken(source.clone()));
+        let target_payment: Option<Address> = store.get(&DataKey::PaymentToken(target.clone()));
+        if let (Some(a), Some(b)) = (&source_payment, &target_payment) {
+            if a != b {
+                return Err(RevoraError::PaymentTokenMismatch);
+            }
+        }
 
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
-        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
-        let now = env.ledger().timestamp();
+        let target_periods = Self::get_period_count(env.clone(), target.clone());
+        let mut merged = 0u32;
+        for holder in holders.iter() {
+            let share = Self::get_holder_share(env.clone(), source.clone(), holder.clone());
+            if share == 0 {
+                continue;
+            }
+            let target_share = Self::get_holder_share(env.clone(), target.clone(), holder.clone());
+            let combined = share + target_share;
+            if combined > 10_000 {
+                return Err(RevoraError::InvalidShareBps);
+            }
+            if target_share == 0 {
+                store.set(
+                    &DataKey::LastClaimedIdx(target.clone(), holder.clone()),
+                    &target_periods,
+                );
+            }
+            Self::write_holder_share(&env, &target, &holder, combined)?;
+            Self::write_holder_share(&env, &source, &holder, 0)?;
+            let claimed: i128 = store
+                .get(&DataKey::TotalClaimed(source.clone(), holder.clone()))
+                .unwrap_or(0);
+            if claimed != 0 {
+                let key = DataKey::TotalClaimed(target.clone(), holder.clone());
+                let total: i128 = store.get(&key).unwrap_or(0);
+                store.set(&key, &total.saturating_add(claimed));
+            }
+            merged += 1;
+        }
+        let roster_len: u32 = store
+            .get(&ComplianceKey::RosterLen(source.clone()))
+            .unwrap_or(0);
+        for i in 0..roster_len {
+            let investor: Address = store
+                .get(&ComplianceKey::RosterEntry(source.clone(), i))
+                .unwrap();
+            if Self::get_holder_share(env.clone(), source.clone(), investor) != 0 {
+                return Err(RevoraError::HoldersIncomplete);
+            }
+        }
 
-        let mut total: i128 = 0;
-        for i in start_idx..period_count {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-            let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
-                break;
+        for investor in Self::get_blacklist(env.clone(), source.clone()).iter() {
+            Self::blacklist_insert(&env, &target, &investor);
+        }
+        if target_payment.is_none() {
+            if let Some(payment_token) = source_payment {
+                store.set(&DataKey::PaymentToken(target.clone()), &payment_token);
             }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            total += revenue * (share_bps as i128) / 10_000;
         }
-        total
+        Self::refresh_state_hash(&env, &target);
+
+        Self::set_offering_status(&env, &source, OfferingStatus::Closed);
+        store.set(&IndexKey::MergedInto(source.clone()), &target);
+        let offering_id = Self::offering_id_of(&env, &source);
+        env.events().publish(
+            (EVENT_OFFERINGS_MERGED, issuer, source, offering_id),
+            (target, merged),
+        );
+        Ok(merged)
     }
 
-    // ── Time-delayed claim configuration (#27) ──────────────────
+    /// Offering a merged (and closed) offering was folded into, if any.
+    pub fn get_merged_into(env: Env, token: Address) -> Option<Address> {
+        env.storage().persistent().get(&IndexKey::MergedInto(token))
+    }
 
-    /// Set per-offering claim delay in seconds. Only issuer may set. 0 = immediate claim.
-    pub fn set_claim_delay(
+    // ── Offering splits ─────────────────────────────────────────
+
+    /// Split `ratio_bps` of `token`'s offering off into a new offering under `new_token`
+    /// (issuer only), registered with the same revenue share. The new offering gets a
+    /// copy of the compliance lists, jurisdiction blocks and withholding settings, and of
+    /// each listed holder's share and compliance flags (at most 100 `holders`). `ratio_bps` of every deposited period nobody has
+    /// claimed from yet moves to the new offering under the same period id; periods
+    /// partially claimed, refunded, frozen or still awaiting installments stay with the
+    /// source.
+    /// Returns the escrow moved.
+    pub fn split_offering(
         env: Env,
         issuer: Address,
         token: Address,
-        delay_secs: u64,
-    ) -> Result<(), RevoraError> {
+        new_token: Address,
+        ratio_bps: u32,
+        holders: Vec<Address>,
+    ) -> Result<i128, RevoraError> {
         Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env);
         issuer.require_auth();
-        if Self::get_offering(env.clone(), issuer.clone(), token.clone()).is_none() {
-            return Err(RevoraError::OfferingNotFound);
+        if Self::offering_issuer(&env, &token)? != issuer {
+            return Err(RevoraError::Unauthorized);
         }
-        let key = DataKey::ClaimDelaySecs(token.clone());
-        env.storage().persistent().set(&key, &delay_secs);
-        env.events()
-            .publish((EVENT_CLAIM_DELAY_SET, issuer, token), delay_secs);
-        Ok(())
-    }
+        if ratio_bps == 0 || ratio_bps >= 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        if holders.len() > MAX_IMPORT_CHUNK {
+            return Err(RevoraError::InvalidChunk);
+        }
+        let store = env.storage().persistent();
+        if token == new_token
+            || store.has(&DataKey::OfferingIssuer(new_token.clone()))
+            || Self::get_offering_status(env.clone(), token.clone()) == OfferingStatus::Closed
+            || Self::get_accrual_mode(env.clone(), token.clone()) != AccrualMode::Periods
+        {
+            return Err(RevoraError::InvalidOfferingStatus);
+        }
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        Self::apply_register_offering(
+            env.clone(),
+            issuer.clone(),
+            new_token.clone(),
+            offering.revenue_share_bps,
+        )?;
+        store.set(&IndexKey::SplitFrom(new_token.clone()), &token);
 
-    /// Get per-offering claim delay in seconds. 0 = immediate claim.
-    pub fn get_claim_delay(env: Env, token: Address) -> u64 {
-        let key = DataKey::ClaimDelaySecs(token);
-        env.storage().persistent().get(&key).unwrap_or(0)
+        for investor in Self::get_blacklist(env.clone(), token.clone()).iter() {
+            Self::blacklist_insert(&env, &new_token, &investor);
+        }
+        Self::copy_entry(
+            &env,
+            &DataKey::PaymentToken(token.clone()),
+            &DataKey::PaymentToken(new_token.clone()),
+        );
+        for key in [
+            ComplianceKey::WhitelistRoot,
+            ComplianceKey::KycRequired,
+            ComplianceKey::ComplianceModule,
+            ComplianceKey::InvestorCap,
+            ComplianceKey::JurisdictionCodes,
+        ] {
+            Self::copy_entry(&env, &key(token.clone()), &key(new_token.clone()));
+        }
+        let mut codes = Self::jurisdiction_codes(&env, &token);
+        codes.push_front(0);
+        for code in codes.iter() {
+            Self::copy_entry(
+                &env,
+                &ComplianceKey::JurisdictionBlocked(token.clone(), code),
+                &ComplianceKey::JurisdictionBlocked(new_token.clone(), code),
+            );
+            for key in [DataKey::WithholdingBps, DataKey::WithholdingRecipient] {
+                Self::copy_entry(
+                    &env,
+                    &key(token.clone(), code),
+                    &key(new_token.clone(), code),
+                );
+            }
+        }
+        for holder in holders.iter() {
+            Self::copy_entry(
+                &env,
+                &DataKey::InvestorJurisdiction(token.clone(), holder.clone()),
+                &DataKey::InvestorJurisdiction(new_token.clone(), holder.clone()),
+            );
+            for key in [
+                ComplianceKey::KycVerified,
+                ComplianceKey::InvestorFrozen,
+                ComplianceKey::WhitelistedUnder,
+            ] {
+                Self::copy_entry(
+                    &env,
+                    &key(token.clone(), holder.clone()),
+                    &key(new_token.clone(), holder.clone()),
+                );
+            }
+            let share = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+            if share > 0 {
+                Self::write_holder_share(&env, &new_token, &holder, share)?;
+            }
+        }
+
+        let mut moved_total: i128 = 0;
+        let mut new_count: u32 = 0;
+        for i in 0..Self::get_period_count(env.clone(), token.clone()) {
+            let period_id: u64 = store.get(&DataKey::PeriodEntry(token.clone(), i)).unwrap();
+            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+            let revenue: i128 = store.get(&rev_key).unwrap_or(0);
+            let moved = revenue.saturating_mul(ratio_bps as i128) / 10_000;
+            if moved <= 0
+                || Self::period_claimed_from(&env, &token, period_id)
+                || store.has(&ReportKey::EscrowRefund(token.clone(), period_id))
+                || Self::is_period_frozen(env.clone(), token.clone(), period_id)
+                || Self::awaiting_installments(&env, &token, period_id, revenue - moved)
+            {
+                continue;
+            }
+            store.set(&rev_key, &(revenue - moved));
+            Self::adjust_escrow(&env, &token, -moved);
+
+            store.set(
+                &DataKey::PeriodRevenue(new_token.clone(), period_id),
+                &moved,
+            );
+            Self::copy_entry(
+                &env,
+                &DataKey::PeriodDepositTime(token.clone(), period_id),
+                &DataKey::PeriodDepositTime(new_token.clone(), period_id),
+            );
+            store.set(
+                &DataKey::PeriodEntry(new_token.clone(), new_count),
+                &period_id,
+            );
+            store.set(
+                &ShareKey::PeriodIndex(new_token.clone(), period_id),
+                &new_count,
+            );
+            Self::set_period_state(&env, &new_token, period_id, PeriodState::Deposited);
+            Self::adjust_escrow(&env, &new_token, moved);
+            new_count += 1;
+            moved_total += moved;
+        }
+        store.set(&DataKey::PeriodCount(new_token.clone()), &new_count);
+        Self::refresh_state_hash(&env, &new_token);
+
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_OFFERING_SPLIT, issuer, token, offering_id),
+            (new_token, ratio_bps, moved_total),
+        );
+        Ok(moved_total)
     }
 
-    /// Return the total number of deposited periods for an offering token.
-    pub fn get_period_count(env: Env, token: Address) -> u32 {
-        let count_key = DataKey::PeriodCount(token);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+    /// Offering a split-off offering was created from, if any.
+    pub fn get_split_origin(env: Env, token: Address) -> Option<Address> {
+        env.storage().persistent().get(&IndexKey::SplitFrom(token))
     }
 
-    // ── On-chain distribution simulation (#29) ────────────────────
+    // ── Period freezes ──────────────────────────────────────────
 
-    /// Read-only: simulate distribution for sample inputs without mutating state.
-    /// Returns expected payouts per holder and total. Uses offering's rounding mode.
-    /// For integrators to preview outcomes before executing deposit/claim flows.
-    pub fn simulate_distribution(
+    /// Freeze one period pending audit (the offering's auditor or an arbiter): its claims
+    /// and escrow refund are blocked until `unfreeze_period`; other periods and the
+    /// offering are unaffected. Period claims stop at the frozen period.
+    pub fn freeze_period(
         env: Env,
-        issuer: Address,
+        caller: Address,
         token: Address,
-        amount: i128,
-        holder_shares: Vec<(Address, u32)>,
-    ) -> SimulateDistributionResult {
-        let mode = Self::get_rounding_mode(env.clone(), issuer, token.clone());
-        let mut total: i128 = 0;
-        let mut payouts = Vec::new(&env);
-        for i in 0..holder_shares.len() {
-            let (holder, share_bps) = holder_shares.get(i).unwrap();
-            let payout = if share_bps > 10_000 {
-                0_i128
-            } else {
-                Self::compute_share(env.clone(), amount, share_bps, mode)
-            };
-            total = total.saturating_add(payout);
-            payouts.push_back((holder.clone(), payout));
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_period_reviewer(&env, &caller, &token)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+            && Self::get_period_state(env.clone(), token.clone(), period_id).is_none()
+        {
+            return Err(RevoraError::ReportNotFound);
         }
-        SimulateDistributionResult {
-            total_distributed: total,
-            payouts,
+        let key = ReportKey::FrozenPeriod(token.clone(), period_id);
+        if !env.storage().persistent().has(&key) {
+            Self::adjust_period_count(&env, ReportKey::FrozenPeriodCount(token.clone()), true);
         }
+        env.storage().persistent().set(&key, &caller);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events()
+            .publish((EVENT_PERIOD_FROZEN, caller, token, offering_id), period_id);
+        Ok(())
     }
 
-    // ── Upgradeability guard and freeze (#32) ───────────────────
-
-    /// Set the admin address. May only be called once; caller must authorize as the new admin.
-    pub fn set_admin(env: Env, admin: Address) -> Result<(), RevoraError> {
-        admin.require_auth();
-        let key = DataKey::Admin;
-        if env.storage().persistent().has(&key) {
-            return Err(RevoraError::LimitReached);
+    /// Lift a period freeze (the offering's auditor or an arbiter).
+    pub fn unfreeze_period(
+        env: Env,
+        caller: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_period_reviewer(&env, &caller, &token)?;
+        let key = ReportKey::FrozenPeriod(token.clone(), period_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(RevoraError::InvalidPeriodState);
         }
-        env.storage().persistent().set(&key, &admin);
+        env.storage().persistent().remove(&key);
+        Self::adjust_period_count(&env, ReportKey::FrozenPeriodCount(token.clone()), false);
+        let offering_id = Self::offering_id_of(&env, &token);
+        env.events().publish(
+            (EVENT_PERIOD_UNFROZEN, caller, token, offering_id),
+            period_id,
+        );
         Ok(())
     }
 
-    /// Get the admin address, if set.
-    pub fn get_admin(env: Env) -> Option<Address> {
-        let key = DataKey::Admin;
-        env.storage().persistent().get(&key)
+    /// True if the period is frozen pending audit.
+    pub fn is_period_frozen(env: Env, token: Address, period_id: u64) -> bool {
+        Self::counted_any(&env, ReportKey::FrozenPeriodCount(token.clone()))
+            && env
+                .storage()
+                .persistent()
+                .has(&ReportKey::FrozenPeriod(token, period_id))
     }
 
-    /// Freeze the contract: no further state-changing operations allowed. Only admin may call.
-    /// Emits event. Claim and read-only functions remain allowed.
-    pub fn freeze(env: Env) -> Result<(), RevoraError> {
-        let key = DataKey::Admin;
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(RevoraError::LimitReached)?;
-        admin.require_auth();
-        let frozen_key = DataKey::Frozen;
-        env.storage().persistent().set(&frozen_key, &true);
-        env.events().publish((EVENT_FREEZE, admin), true);
-        Ok(())
+    /// Raise or lower a per-offering count of periods carrying an optional entry (frozen,
+    /// scheduled, short). Claim loops consult the count before looking up each period.
+    fn adjust_period_count(env: &Env, key: ReportKey, up: bool) {
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let count = if up {
+            count.saturating_add(1)
+        } else {
+            count.saturating_sub(1)
+        };
+        if count == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &count);
+        }
     }
 
-    /// Return true if the contract is frozen.
-    pub fn is_frozen(env: Env) -> bool {
-        env.storage()
-            .persistent()
-            .get::<DataKey, bool>(&DataKey::Frozen)
-            .unwrap_or(false)
+    /// True if a per-offering period count (see `adjust_period_count`) is non-zero.
+    fn counted_any(env: &Env, key: ReportKey) -> bool {
+        env.storage().persistent().has(&key)
     }
 
-    // ── Testnet mode configuration (#24) ───────────────────────
-
-    /// Enable or disable testnet mode. Only admin may call.
-    /// When enabled, certain validations are relaxed for testnet deployments.
-    /// Emits event with new mode state.
-    pub fn set_testnet_mode(env: Env, enabled: bool) -> Result<(), RevoraError> {
-        let key = DataKey::Admin;
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(RevoraError::LimitReached)?;
-        admin.require_auth();
-        let mode_key = DataKey::TestnetMode;
-        env.storage().persistent().set(&mode_key, &enabled);
-        env.events().publish((EVENT_TESTNET_MODE, admin), enabled);
-        Ok(())
+    /// True if the period has an unfunded shortfall.
+    fn has_shortfall(env: &Env, token: &Address, period_id: u64) -> bool {
+        Self::counted_any(env, ReportKey::OpenShortfallCount(token.clone()))
+            && env
+                .storage()
+                .persistent()
+                .has(&ReportKey::Shortfall(token.clone(), period_id))
     }
 
-    /// Return true if testnet mode is enabled.
-    pub fn is_testnet_mode(env: Env) -> bool {
+    /// Auditor or arbiter who froze the period, if it is frozen.
+    pub fn get_period_freeze(env: Env, token: Address, period_id: u64) -> Option<Address> {
         env.storage()
             .persistent()
-            .get::<DataKey, bool>(&DataKey::TestnetMode)
-            .unwrap_or(false)
+            .get(&ReportKey::FrozenPeriod(token, period_id))
+    }
+
+    /// Offering's designated auditor, or an arbiter (global role or offering arbiter).
+    fn require_period_reviewer(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        if Self::get_auditor(env.clone(), token.clone()).map(|c| c.auditor) == Some(caller.clone())
+        {
+            caller.require_auth();
+            return Ok(());
+        }
+        Self::require_arbiter(env, caller, token)
     }
 }
 
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
 mod test;
@@ -1,10 +1,17 @@
 #![cfg(test)]
 use soroban_sdk::{
-    testutils::Address as _, testutils::Events as _, testutils::Ledger as _, token, Address, Env,
-    Vec,
+    contract, contractimpl, testutils::Address as _, testutils::Events as _,
+    testutils::Ledger as _, token, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec,
 };
 
-use crate::{RevoraError, RevoraRevenueShare, RevoraRevenueShareClient, RoundingMode};
+use crate::{
+    AccrualMode, AdminAction, AdminKey, AmendmentStatus, BatchOp, BridgeReport, ClassConversion,
+    DataKey, DisputeStatus, DistributionStatus, EligibilityResult, EligibilityRow, EngineAck,
+    EventVerbosity, FiatReportingConfig, NftShareClass, OfferingStatus, OfferingTerms, OfferingV0,
+    OfferingV1, PayoutRoot, PenaltyTerms, PeriodFunding, PeriodState, ReleaseTranche, RevoraError,
+    RevoraRevenueShare, RevoraRevenueShareClient, Role, RoundingMode, Ruling, SlashStatus,
+    ZeroReportPolicy, OFFERING_VERSION,
+};
 
 // ── helper ────────────────────────────────────────────────────
 
@@ -302,6 +309,7 @@ fn add_marks_investor_as_blacklisted() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -310,12 +318,29 @@ fn add_marks_investor_as_blacklisted() {
     assert!(client.is_blacklisted(&token, &investor));
 }
 
+#[test]
+fn blacklist_add_on_unregistered_token_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let stranger = Address::generate(&env);
+    let token = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let r = client.try_blacklist_add(&stranger, &token, &investor);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    assert!(!client.is_blacklisted(&token, &investor));
+}
+
 #[test]
 fn remove_unmarks_investor() {
     let env = Env::default();
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -330,6 +355,7 @@ fn get_blacklist_returns_all_blocked_investors() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let inv_a = Address::generate(&env);
     let inv_b = Address::generate(&env);
@@ -364,6 +390,7 @@ fn double_add_is_idempotent() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -379,6 +406,7 @@ fn remove_nonexistent_is_idempotent() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -394,6 +422,7 @@ fn blacklist_is_scoped_per_offering() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token_a = Address::generate(&env);
     let token_b = Address::generate(&env);
     let investor = Address::generate(&env);
@@ -410,6 +439,7 @@ fn removing_from_one_offering_does_not_affect_another() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token_a = Address::generate(&env);
     let token_b = Address::generate(&env);
     let investor = Address::generate(&env);
@@ -430,6 +460,7 @@ fn blacklist_add_emits_event() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -444,6 +475,7 @@ fn blacklist_remove_emits_event() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -461,6 +493,7 @@ fn blacklisted_investor_excluded_from_distribution_filter() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let allowed = Address::generate(&env);
     let blocked = Address::generate(&env);
@@ -482,6 +515,7 @@ fn blacklist_takes_precedence_over_whitelist() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
     let investor = Address::generate(&env);
 
@@ -551,12 +585,31 @@ fn register_offering_accepts_bps_exactly_10000() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn register_offering_rejects_registered_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let issuer = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+
+    let r = client.try_register_offering(&other, &token, &2_000);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingAlreadyExists)));
+    let r = client.try_register_offering(&issuer, &token, &2_000);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingAlreadyExists)));
+    assert_eq!(client.get_offering(&other, &token), None);
+    assert_eq!(client.get_offering_count(&issuer), 1);
+}
+
 // ---------------------------------------------------------------------------
 // Storage limit negative tests (#31): many offerings/reports, no panics
 // ---------------------------------------------------------------------------
 
-/// Maximum reasonable offering count used in tests to probe storage growth.
-const STORAGE_STRESS_OFFERING_COUNT: u32 = 200;
+/// Offering count used in tests to probe storage growth. The test host meters every
+/// write against all ledger entries, so this stays within the default budget.
+const STORAGE_STRESS_OFFERING_COUNT: u32 = 40;
 
 #[test]
 fn storage_stress_many_offerings_no_panic() {
@@ -600,6 +653,7 @@ fn storage_stress_large_blacklist_no_panic() {
     env.mock_all_auths();
     let client = make_client(&env);
     let admin = Address::generate(&env);
+    client.set_admin(&admin);
     let token = Address::generate(&env);
 
     for _ in 0..80 {
@@ -636,12 +690,9 @@ fn gas_characterization_report_revenue_with_large_blacklist() {
     let token = Address::generate(&env);
     client.register_offering(&issuer, &token, &500);
 
-    for _ in 0..30 {
-        client.blacklist_add(&Address::generate(&env), &token, &Address::generate(&env));
+    for _ in 0..31 {
+        client.blacklist_add(&issuer, &token, &Address::generate(&env));
     }
-    let admin = Address::generate(&env);
-    env.mock_all_auths();
-    client.blacklist_add(&admin, &token, &Address::generate(&env)); // ensure admin is auth
 
     client.report_revenue(&issuer, &token, &1_000_000, &1, &false);
     assert!(!env.events().all().is_empty());
@@ -2393,3 +2444,5096 @@ fn blacklist_remove_blocked_while_paused() {
     client.pause_admin(&admin);
     client.blacklist_remove(&admin, &token, &investor);
 }
+
+// ===========================================================================
+// Reporting schedule and grace period
+// ===========================================================================
+
+#[test]
+fn set_reporting_schedule_stores_config() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &1_000, &100, &20);
+    let schedule = client.get_reporting_schedule(&token).unwrap();
+    assert_eq!(schedule.start_time, 1_000);
+    assert_eq!(schedule.period_secs, 100);
+    assert_eq!(schedule.grace_secs, 20);
+}
+
+#[test]
+fn set_reporting_schedule_requires_offering() {
+    let (env, client, issuer, _token, _payment_token, _contract_id) = claim_setup();
+    let unknown = Address::generate(&env);
+
+    let r = client.try_set_reporting_schedule(&issuer, &unknown, &0, &100, &0);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+}
+
+#[test]
+fn set_reporting_schedule_rejects_zero_period() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    let r = client.try_set_reporting_schedule(&issuer, &token, &0, &0, &10);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidSchedule)));
+}
+
+#[test]
+fn report_within_grace_is_not_late() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    // Period 0 ends at 1100, grace until 1120
+    client.set_reporting_schedule(&issuer, &token, &1_000, &100, &20);
+    env.ledger().set_timestamp(1_120);
+    client.report_revenue(&issuer, &token, &5_000, &0, &false);
+    assert!(!client.is_report_late(&token, &0));
+}
+
+#[test]
+fn report_after_grace_is_flagged_late() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &1_000, &100, &20);
+    env.ledger().set_timestamp(1_121);
+    let before = env.events().all().len();
+    client.report_revenue(&issuer, &token, &5_000, &0, &false);
+    assert!(client.is_report_late(&token, &0));
+    // rev_init + rev_late + rev_rep
+    assert_eq!(env.events().all().len(), before + 3);
+}
+
+#[test]
+fn late_override_of_on_time_report_is_not_flagged() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &1_000, &100, &20);
+    env.ledger().set_timestamp(1_110);
+    client.report_revenue(&issuer, &token, &5_000, &0, &false);
+    env.ledger().set_timestamp(1_500);
+    client.report_revenue(&issuer, &token, &6_000, &0, &true);
+    assert!(!client.is_report_late(&token, &0));
+}
+
+#[test]
+fn report_without_schedule_is_never_late() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    env.ledger().set_timestamp(u64::MAX);
+    client.report_revenue(&issuer, &token, &5_000, &0, &false);
+    assert!(!client.is_report_late(&token, &0));
+}
+
+// ===========================================================================
+// Delinquency and auto-suspension
+// ===========================================================================
+
+#[test]
+fn offering_status_defaults_to_active() {
+    let (_env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+}
+
+#[test]
+fn missed_periods_counts_unreported_due_periods() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &10);
+    // Deadlines: p0 = 110, p1 = 210, p2 = 310
+    env.ledger().set_timestamp(110);
+    assert_eq!(client.get_missed_periods(&token), 0);
+    env.ledger().set_timestamp(111);
+    assert_eq!(client.get_missed_periods(&token), 1);
+    env.ledger().set_timestamp(311);
+    assert_eq!(client.get_missed_periods(&token), 3);
+
+    // Reporting period 1 leaves only period 2 outstanding
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    assert_eq!(client.get_missed_periods(&token), 1);
+}
+
+#[test]
+fn report_for_future_period_is_rejected() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &10);
+    env.ledger().set_timestamp(150);
+    let r = client.try_report_revenue(&issuer, &token, &1_000, &2, &false);
+    assert_eq!(r, Err(Ok(RevoraError::FuturePeriod)));
+    // The running period may be reported
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+}
+
+#[test]
+fn missed_periods_not_covered_by_later_report() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &50);
+    // Periods 0..=2 are past their deadlines; period 3 is running
+    env.ledger().set_timestamp(351);
+    client.report_revenue(&issuer, &token, &1_000, &3, &false);
+    assert_eq!(client.get_missed_periods(&token), 3);
+
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    assert_eq!(client.get_missed_periods(&token), 1);
+}
+
+#[test]
+fn check_delinquency_suspends_after_threshold() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    client.set_suspension_threshold(&issuer, &token, &3);
+
+    env.ledger().set_timestamp(201);
+    assert_eq!(client.check_delinquency(&token), 2);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+
+    env.ledger().set_timestamp(301);
+    assert_eq!(client.check_delinquency(&token), 3);
+    assert_eq!(
+        client.get_offering_status(&token),
+        OfferingStatus::Suspended
+    );
+}
+
+#[test]
+fn check_delinquency_threshold_zero_never_suspends() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    env.ledger().set_timestamp(10_000);
+    assert_eq!(client.check_delinquency(&token), 99);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+}
+
+#[test]
+fn check_delinquency_timely_reports_stay_active() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    client.set_suspension_threshold(&issuer, &token, &1);
+    for p in 0..3u64 {
+        env.ledger().set_timestamp(100 * (p + 1));
+        client.report_revenue(&issuer, &token, &1_000, &p, &false);
+    }
+    env.ledger().set_timestamp(301);
+    assert_eq!(client.check_delinquency(&token), 0);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+}
+
+#[test]
+fn set_suspension_threshold_requires_offering() {
+    let (env, client, issuer, _token, _payment_token, _contract_id) = claim_setup();
+    let unknown = Address::generate(&env);
+
+    let r = client.try_set_suspension_threshold(&issuer, &unknown, &3);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+}
+
+#[test]
+fn reinstate_offering_rejects_active_offering() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    let r = client.try_reinstate_offering(&issuer, &token);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+/// Helper: schedule 100s periods and suspend the offering after 3 missed periods.
+fn suspend_offering(
+    env: &Env,
+    client: &RevoraRevenueShareClient,
+    issuer: &Address,
+    token: &Address,
+) {
+    client.set_reporting_schedule(issuer, token, &0, &100, &0);
+    client.set_suspension_threshold(issuer, token, &3);
+    env.ledger().set_timestamp(301);
+    client.check_delinquency(token);
+    assert_eq!(client.get_offering_status(token), OfferingStatus::Suspended);
+}
+
+#[test]
+fn reinstate_offering_requires_missing_reports() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    suspend_offering(&env, &client, &issuer, &token);
+
+    let r = client.try_reinstate_offering(&issuer, &token);
+    assert_eq!(r, Err(Ok(RevoraError::MissedReportsOutstanding)));
+
+    // Filing only the oldest missing period is not enough
+    client.report_revenue(&issuer, &token, &1_000, &0, &false);
+    let r = client.try_reinstate_offering(&issuer, &token);
+    assert_eq!(r, Err(Ok(RevoraError::MissedReportsOutstanding)));
+}
+
+#[test]
+fn reinstate_offering_after_all_missing_reports() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    suspend_offering(&env, &client, &issuer, &token);
+
+    for p in 0..3u64 {
+        client.report_revenue(&issuer, &token, &1_000, &p, &false);
+    }
+    client.reinstate_offering(&issuer, &token);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+}
+
+#[test]
+fn reinstate_offering_after_catch_up_report() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    suspend_offering(&env, &client, &issuer, &token);
+
+    // One catch-up report for the latest due period covers the gap
+    client.report_revenue(&issuer, &token, &300_000, &2, &false);
+    client.reinstate_offering(&issuer, &token);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+
+    // Catch-up revenue flows through the normal deposit/claim path
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &300_000, &2);
+    assert_eq!(client.claim(&holder, &token, &0), 150_000);
+}
+
+#[test]
+fn suspended_offering_pays_funded_periods_but_takes_no_new_funds() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    env.ledger().set_timestamp(50);
+    client.report_revenue(&issuer, &token, &100_000, &0, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &0);
+
+    client.set_suspension_threshold(&issuer, &token, &3);
+    env.ledger().set_timestamp(401);
+    client.check_delinquency(&token);
+    assert_eq!(
+        client.get_offering_status(&token),
+        OfferingStatus::Suspended
+    );
+
+    let r = client.try_deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    let r = client.try_report_revenue(&issuer, &token, &1_000, &4, &false);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+
+    assert_eq!(client.claim(&holder, &token, &0), 50_000);
+}
+
+#[test]
+fn check_delinquency_emits_missed_event_once_per_period() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+
+    // Nothing overdue yet: no delinquency event
+    env.ledger().set_timestamp(100);
+    let before = env.events().all().len();
+    client.check_delinquency(&token);
+    assert_eq!(env.events().all().len(), before);
+
+    env.ledger().set_timestamp(101);
+    let before = env.events().all().len();
+    client.check_delinquency(&token);
+    assert_eq!(env.events().all().len(), before + 1);
+
+    // Same overdue period: not re-announced
+    let before = env.events().all().len();
+    client.check_delinquency(&token);
+    assert_eq!(env.events().all().len(), before);
+
+    // Next period becomes overdue: announced again
+    env.ledger().set_timestamp(201);
+    let before = env.events().all().len();
+    client.check_delinquency(&token);
+    assert_eq!(env.events().all().len(), before + 1);
+}
+
+#[test]
+fn check_delinquency_emits_suspension_events() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    client.set_suspension_threshold(&issuer, &token, &1);
+
+    env.ledger().set_timestamp(101);
+    let before = env.events().all().len();
+    client.check_delinquency(&token);
+    // delinq(missed) + off_stat + delinq(suspended)
+    assert_eq!(env.events().all().len(), before + 3);
+    assert_eq!(
+        client.get_offering_status(&token),
+        OfferingStatus::Suspended
+    );
+}
+
+// ===========================================================================
+// Offering health summary
+// ===========================================================================
+
+#[test]
+fn offering_health_none_for_unknown_offering() {
+    let (env, client, issuer, _token, _payment_token, _contract_id) = claim_setup();
+    let unknown = Address::generate(&env);
+    assert_eq!(client.get_offering_health(&issuer, &unknown), None);
+}
+
+#[test]
+fn offering_health_defaults_for_new_offering() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    let health = client.get_offering_health(&issuer, &token).unwrap();
+    assert_eq!(health.status, OfferingStatus::Active);
+    assert_eq!(health.last_report_period, None);
+    assert_eq!(health.last_report_time, None);
+    assert_eq!(health.missed_periods, 0);
+    assert_eq!(health.escrow_balance, 0);
+    assert_eq!(health.blacklist_size, 0);
+    assert_eq!(health.pending_disputes, 0);
+}
+
+#[test]
+fn offering_health_reflects_activity() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let blocked = Address::generate(&env);
+
+    client.set_reporting_schedule(&issuer, &token, &0, &100, &0);
+    env.ledger().set_timestamp(150);
+    client.report_revenue(&issuer, &token, &100_000, &0, &false);
+    client.blacklist_add(&issuer, &token, &blocked);
+    client.set_holder_share(&issuer, &token, &holder, &2_500);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &0);
+    client.claim(&holder, &token, &0);
+    env.ledger().set_timestamp(301);
+
+    let health = client.get_offering_health(&issuer, &token).unwrap();
+    assert_eq!(health.last_report_period, Some(0));
+    assert_eq!(health.last_report_time, Some(150));
+    assert_eq!(health.missed_periods, 2);
+    assert_eq!(health.escrow_balance, 75_000);
+    assert_eq!(health.blacklist_size, 1);
+}
+
+#[test]
+fn escrow_balance_tracks_deposits_and_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &50_000, &2);
+    assert_eq!(client.get_escrow_balance(&token), 150_000);
+
+    client.claim(&holder, &token, &1);
+    assert_eq!(client.get_escrow_balance(&token), 50_000);
+}
+
+// ===========================================================================
+// Per-investor claim history
+// ===========================================================================
+
+#[test]
+fn claim_history_empty_before_any_claim() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    assert_eq!(client.get_claim_history_count(&token, &holder), 0);
+    assert_eq!(client.get_claim_history(&token, &holder, &0).len(), 0);
+}
+
+#[test]
+fn claim_history_records_each_claim() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &7);
+    client.deposit_revenue(&issuer, &token, &payment_token, &200_000, &8);
+    env.ledger().set_timestamp(500);
+    client.claim(&holder, &token, &0);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &40_000, &9);
+    env.ledger().set_timestamp(900);
+    client.claim(&holder, &token, &0);
+
+    assert_eq!(client.get_claim_history_count(&token, &holder), 2);
+    let history = client.get_claim_history(&token, &holder, &0);
+    assert_eq!(history.len(), 2);
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.amount, 150_000);
+    assert_eq!(first.period_count, 2);
+    assert_eq!(first.last_period_id, 8);
+    assert_eq!(first.timestamp, 500);
+
+    let second = history.get(1).unwrap();
+    assert_eq!(second.amount, 20_000);
+    assert_eq!(second.period_count, 1);
+    assert_eq!(second.last_period_id, 9);
+    assert_eq!(second.timestamp, 900);
+}
+
+#[test]
+fn claim_history_paginates_after_page_size() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    for period in 1..=25u64 {
+        client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &period);
+        client.claim(&holder, &token, &0);
+    }
+
+    assert_eq!(client.get_claim_history_count(&token, &holder), 25);
+    assert_eq!(client.get_claim_history(&token, &holder, &0).len(), 20);
+    let page1 = client.get_claim_history(&token, &holder, &1);
+    assert_eq!(page1.len(), 5);
+    assert_eq!(page1.get(4).unwrap().last_period_id, 25);
+    assert_eq!(client.get_claim_history(&token, &holder, &2).len(), 0);
+}
+
+// ===========================================================================
+// Investor statement
+// ===========================================================================
+
+#[test]
+fn investor_statement_empty_for_unknown_investor() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+
+    let st = client.get_investor_statement(&token, &investor);
+    assert_eq!(st.total_earned, 0);
+    assert_eq!(st.total_claimed, 0);
+    assert_eq!(st.pending, 0);
+    assert_eq!(st.frozen, 0);
+    assert_eq!(st.share_bps, 0);
+    assert!(!st.blacklisted);
+    assert!(!st.can_claim);
+}
+
+#[test]
+fn investor_statement_tracks_claimed_pending_and_frozen() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+
+    env.ledger().set_timestamp(1_000);
+    client.set_holder_share(&issuer, &token, &investor, &5_000);
+    client.set_claim_delay(&issuer, &token, &100);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    env.ledger().set_timestamp(1_100);
+    client.claim(&investor, &token, &0);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &60_000, &2);
+    env.ledger().set_timestamp(1_150);
+    client.deposit_revenue(&issuer, &token, &payment_token, &20_000, &3);
+    env.ledger().set_timestamp(1_200);
+
+    // Period 2 claimable (deposited 1100), period 3 still delayed (deposited 1150)
+    let st = client.get_investor_statement(&token, &investor);
+    assert_eq!(st.total_claimed, 50_000);
+    assert_eq!(st.pending, 30_000);
+    assert_eq!(st.frozen, 10_000);
+    assert_eq!(st.total_earned, 90_000);
+    assert_eq!(st.share_bps, 5_000);
+    assert!(st.can_claim);
+}
+
+#[test]
+fn investor_statement_sums_a_bounded_window_of_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &investor, &10_000);
+    for p in 1..=25u64 {
+        client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &p);
+    }
+    client.claim(&investor, &token, &2);
+
+    // Periods 3..=22 are in the window; 23..=25 are counted after the next claim
+    let st = client.get_investor_statement(&token, &investor);
+    assert_eq!(st.total_claimed, 2_000);
+    assert_eq!(st.pending, 20_000);
+    assert_eq!(st.total_earned, 22_000);
+
+    client.claim(&investor, &token, &20);
+    let st = client.get_investor_statement(&token, &investor);
+    assert_eq!(st.total_claimed, 22_000);
+    assert_eq!(st.pending, 3_000);
+    assert_eq!(st.total_earned, 25_000);
+}
+
+#[test]
+fn investor_statement_blacklisted_amounts_are_frozen() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &investor, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &40_000, &1);
+    client.blacklist_add(&issuer, &token, &investor);
+
+    let st = client.get_investor_statement(&token, &investor);
+    assert_eq!(st.pending, 0);
+    assert_eq!(st.frozen, 40_000);
+    assert_eq!(st.total_earned, 40_000);
+    assert!(st.blacklisted);
+    assert!(!st.can_claim);
+}
+
+// ===========================================================================
+// Tax withholding
+// ===========================================================================
+
+#[test]
+fn set_withholding_rate_rejects_over_10000() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    let r = client.try_set_withholding_rate(&issuer, &token, &0, &10_001);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidWithholdingBps)));
+}
+
+#[test]
+fn claim_applies_default_withholding() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_withholding_rate(&issuer, &token, &0, &1_500);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    let payout = client.claim(&holder, &token, &0);
+    assert_eq!(payout, 85_000);
+    assert_eq!(balance(&env, &payment_token, &holder), 85_000);
+    assert_eq!(client.get_withheld_balance(&token, &0), 15_000);
+    assert_eq!(client.get_escrow_balance(&token), 0);
+
+    let record = client
+        .get_claim_history(&token, &holder, &0)
+        .get(0)
+        .unwrap();
+    assert_eq!(record.amount, 85_000);
+    assert_eq!(record.withheld, 15_000);
+}
+
+#[test]
+fn claim_uses_jurisdiction_rate_over_default() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let domestic = Address::generate(&env);
+    let foreign = Address::generate(&env);
+
+    client.set_withholding_rate(&issuer, &token, &0, &1_000);
+    client.set_withholding_rate(&issuer, &token, &44, &3_000);
+    client.set_investor_jurisdiction(&issuer, &token, &foreign, &44);
+    client.set_holder_share(&issuer, &token, &domestic, &5_000);
+    client.set_holder_share(&issuer, &token, &foreign, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    assert_eq!(client.claim(&domestic, &token, &0), 45_000);
+    assert_eq!(client.claim(&foreign, &token, &0), 35_000);
+    assert_eq!(client.get_withheld_balance(&token, &0), 5_000);
+    assert_eq!(client.get_withheld_balance(&token, &44), 15_000);
+}
+
+#[test]
+fn claim_without_withholding_pays_gross() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    assert_eq!(client.claim(&holder, &token, &0), 100_000);
+    assert_eq!(client.get_withheld_balance(&token, &0), 0);
+}
+
+#[test]
+fn remit_withholding_requires_recipient() {
+    let (_env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+
+    let r = client.try_remit_withholding(&token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::RecipientNotSet)));
+}
+
+#[test]
+fn set_withholding_recipient_requires_registered_offering() {
+    let (env, client, _issuer, _token, _payment_token, _contract_id) = claim_setup();
+    let unknown = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let r = client.try_set_withholding_recipient(&unknown, &0, &recipient);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+}
+
+#[test]
+fn remit_withholding_forwards_balance_to_recipient() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let tax_office = Address::generate(&env);
+
+    client.set_withholding_rate(&issuer, &token, &7, &2_000);
+    client.set_investor_jurisdiction(&issuer, &token, &holder, &7);
+    client.set_withholding_recipient(&token, &7, &tax_office);
+    assert_eq!(
+        client.get_withholding_recipient(&token, &7),
+        Some(tax_office.clone())
+    );
+
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.claim(&holder, &token, &0);
+
+    let before = env.events().all().len();
+    assert_eq!(client.remit_withholding(&token, &7), 20_000);
+    assert!(env.events().all().len() > before);
+    assert_eq!(balance(&env, &payment_token, &tax_office), 20_000);
+    assert_eq!(client.get_withheld_balance(&token, &7), 0);
+    assert_eq!(client.get_remitted_withholding(&token, &7), 20_000);
+
+    // Nothing left to remit
+    assert_eq!(client.remit_withholding(&token, &7), 0);
+}
+
+// ===========================================================================
+// Timelocked admin operations
+// ===========================================================================
+
+/// Helper: contract with admin set, returns (env, client, admin).
+fn admin_setup() -> (Env, RevoraRevenueShareClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    (env, client, admin)
+}
+
+#[test]
+fn propose_admin_op_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+
+    let r = client.try_propose_admin_op(&AdminAction::Freeze);
+    assert!(r.is_err());
+}
+
+#[test]
+fn admin_op_without_delay_executes_immediately() {
+    let (_env, client, _admin) = admin_setup();
+
+    let id = client.propose_admin_op(&AdminAction::SetTestnetMode(true));
+    assert_eq!(client.get_pending_admin_ops().len(), 1);
+    client.execute_admin_op(&id);
+    assert!(client.is_testnet_mode());
+    assert_eq!(client.get_pending_admin_ops().len(), 0);
+    assert_eq!(client.get_admin_op(&id), None);
+}
+
+#[test]
+fn admin_op_respects_per_kind_delay() {
+    let (env, client, _admin) = admin_setup();
+    env.ledger().set_timestamp(1_000);
+
+    // Configure a 1 day delay for Freeze (kind 1)
+    let set_delay = client.propose_admin_op(&AdminAction::SetOpDelay(1, 86_400));
+    client.execute_admin_op(&set_delay);
+    assert_eq!(client.get_admin_op_delay(&1), 86_400);
+
+    let id = client.propose_admin_op(&AdminAction::Freeze);
+    let op = client.get_admin_op(&id).unwrap();
+    assert_eq!(op.eta, 1_000 + 86_400);
+
+    let r = client.try_execute_admin_op(&id);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+    assert!(!client.is_frozen());
+
+    env.ledger().set_timestamp(1_000 + 86_400);
+    client.execute_admin_op(&id);
+    assert!(client.is_frozen());
+}
+
+#[test]
+fn direct_admin_call_rejected_once_kind_is_timelocked() {
+    let (env, client, _admin) = admin_setup();
+    let treasury = Address::generate(&env);
+
+    // Treasury changes (kind 5) run directly until a delay is configured
+    client.set_treasury(&treasury);
+    let set_delay = client.propose_admin_op(&AdminAction::SetOpDelay(5, 3_600));
+    client.execute_admin_op(&set_delay);
+
+    let other = Address::generate(&env);
+    let r = client.try_set_treasury(&other);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+    assert_eq!(client.get_treasury(), Some(treasury));
+
+    let id = client.propose_admin_op(&AdminAction::SetTreasury(other.clone()));
+    env.ledger().set_timestamp(3_600);
+    client.execute_admin_op(&id);
+    assert_eq!(client.get_treasury(), Some(other));
+}
+
+#[test]
+fn role_grants_and_dispute_settings_follow_admin_timelock() {
+    let (env, client, _admin) = admin_setup();
+    let arbiter = Address::generate(&env);
+    let set_delay = client.propose_admin_op(&AdminAction::SetOpDelay(13, 3_600));
+    client.execute_admin_op(&set_delay);
+    let set_delay = client.propose_admin_op(&AdminAction::SetOpDelay(14, 3_600));
+    client.execute_admin_op(&set_delay);
+
+    let r = client.try_grant_role(&Role::Arbiter, &arbiter);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+    let r = client.try_add_engine(&arbiter);
+    assert_eq!(r, Ok(Ok(())));
+    let r = client.try_set_appeal_window(&60);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+
+    let grant =
+        client.propose_admin_op(&AdminAction::SetRole(Role::Arbiter, arbiter.clone(), true));
+    let window = client.propose_admin_op(&AdminAction::SetAppealWindow(60));
+    env.ledger().set_timestamp(3_600);
+    client.execute_admin_op(&grant);
+    client.execute_admin_op(&window);
+    assert!(client.has_role(&Role::Arbiter, &arbiter));
+    assert_eq!(client.get_appeal_window(), 60);
+}
+
+#[test]
+fn cancel_admin_op_removes_it() {
+    let (env, client, _admin) = admin_setup();
+
+    let id = client.propose_admin_op(&AdminAction::Freeze);
+    let before = env.events().all().len();
+    client.cancel_admin_op(&id);
+    assert!(env.events().all().len() > before);
+    assert_eq!(client.get_pending_admin_ops().len(), 0);
+
+    let r = client.try_execute_admin_op(&id);
+    assert_eq!(r, Err(Ok(RevoraError::AdminOpNotFound)));
+}
+
+#[test]
+fn pending_admin_ops_lists_queued_in_order() {
+    let (_env, client, _admin) = admin_setup();
+
+    let a = client.propose_admin_op(&AdminAction::SetTestnetMode(true));
+    let b = client.propose_admin_op(&AdminAction::Freeze);
+    let c = client.propose_admin_op(&AdminAction::SetOpDelay(0, 60));
+    client.cancel_admin_op(&b);
+
+    let pending = client.get_pending_admin_ops();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending.get(0).unwrap().id, a);
+    assert_eq!(pending.get(1).unwrap().id, c);
+}
+
+// ===========================================================================
+// Upgrade and migration hook
+// ===========================================================================
+
+/// Simulate the version recorded by `upgrade` (installing real WASM is not possible in
+/// unit tests).
+fn record_upgrade_from(env: &Env, client: &RevoraRevenueShareClient, from_version: u32) {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ContractVersion, &from_version);
+    });
+}
+
+#[test]
+fn contract_version_defaults_to_current() {
+    let (_env, client, _admin) = admin_setup();
+    assert_eq!(client.get_contract_version(), 1);
+    assert!(!client.is_migration_pending());
+}
+
+#[test]
+fn post_upgrade_without_older_version_fails() {
+    let (_env, client, _admin) = admin_setup();
+
+    let r = client.try_post_upgrade(&1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidMigration)));
+}
+
+/// Simulate a deployment from before versioning: admin set, no recorded version.
+fn simulate_layout_zero(env: &Env, client: &RevoraRevenueShareClient) {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&AdminKey::ContractVersion);
+    });
+}
+
+#[test]
+fn upgrade_from_version_zero_blocks_state_changes_until_migrated() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+    simulate_layout_zero(&env, &client);
+    assert_eq!(client.get_contract_version(), 0);
+    assert!(client.is_migration_pending());
+
+    let r = client.try_register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::MigrationPending)));
+
+    // Wrong source version is rejected
+    let r = client.try_post_upgrade(&1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidMigration)));
+
+    let before = env.events().all().len();
+    client.post_upgrade(&0);
+    assert!(env.events().all().len() > before);
+    assert_eq!(client.get_contract_version(), 1);
+    assert!(!client.is_migration_pending());
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&AdminKey::LegacyBlacklists));
+    });
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+}
+
+#[test]
+fn recorded_older_version_is_pending_until_post_upgrade() {
+    let (env, client, _admin) = admin_setup();
+    record_upgrade_from(&env, &client, 0);
+    assert!(client.is_migration_pending());
+    client.post_upgrade(&0);
+    assert!(!client.is_migration_pending());
+}
+
+#[test]
+#[should_panic]
+fn upgrade_with_unknown_wasm_hash_panics() {
+    let (env, client, _admin) = admin_setup();
+    let hash = soroban_sdk::BytesN::from_array(&env, &[7u8; 32]);
+    client.upgrade(&hash);
+}
+
+#[test]
+fn upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let hash = soroban_sdk::BytesN::from_array(&env, &[7u8; 32]);
+
+    let r = client.try_upgrade(&hash);
+    assert_eq!(r, Err(Ok(RevoraError::LimitReached)));
+}
+
+// ===========================================================================
+// Offerings-per-issuer limit
+// ===========================================================================
+
+#[test]
+fn offering_limit_defaults_to_unlimited() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+    assert_eq!(client.get_offering_limit(&issuer), 0);
+    register_n(&env, &client, &issuer, 30);
+    assert_eq!(client.get_offering_count(&issuer), 30);
+}
+
+#[test]
+fn register_offering_enforces_default_limit() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+
+    client.set_max_offerings_per_issuer(&2);
+    register_n(&env, &client, &issuer, 2);
+    let r = client.try_register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::LimitReached)));
+    assert_eq!(client.get_offering_count(&issuer), 2);
+}
+
+#[test]
+fn issuer_override_takes_precedence_over_default() {
+    let (env, client, _admin) = admin_setup();
+    let partner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.set_max_offerings_per_issuer(&1);
+    client.set_issuer_offering_limit(&partner, &Some(3));
+    assert_eq!(client.get_offering_limit(&partner), 3);
+    assert_eq!(client.get_offering_limit(&other), 1);
+
+    register_n(&env, &client, &partner, 3);
+    let r = client.try_register_offering(&partner, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::LimitReached)));
+
+    // Clearing the override falls back to the default
+    client.set_issuer_offering_limit(&partner, &None);
+    assert_eq!(client.get_offering_limit(&partner), 1);
+}
+
+#[test]
+fn set_max_offerings_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+
+    let r = client.try_set_max_offerings_per_issuer(&5);
+    assert!(r.is_err());
+}
+
+// ===========================================================================
+// Issuer allowlist
+// ===========================================================================
+
+#[test]
+fn registration_is_permissionless_by_default() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+
+    assert!(!client.is_issuer_allowlist_enabled());
+    assert!(client.is_issuer_allowed(&issuer));
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+}
+
+#[test]
+fn permissioned_mode_rejects_unlisted_issuer() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+
+    client.set_issuer_allowlist_enabled(&true);
+    assert!(!client.is_issuer_allowed(&issuer));
+    let r = client.try_register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::IssuerNotAllowed)));
+}
+
+#[test]
+fn permissioned_mode_accepts_allowlisted_issuer() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+
+    client.set_issuer_allowlist_enabled(&true);
+    client.allow_issuer(&issuer);
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+
+    client.disallow_issuer(&issuer);
+    let r = client.try_register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::IssuerNotAllowed)));
+    assert_eq!(client.get_offering_count(&issuer), 1);
+}
+
+#[test]
+fn toggling_back_to_open_mode_allows_anyone() {
+    let (env, client, _admin) = admin_setup();
+    let issuer = Address::generate(&env);
+
+    client.set_issuer_allowlist_enabled(&true);
+    client.set_issuer_allowlist_enabled(&false);
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+}
+
+// ===========================================================================
+// Registration fee and waivers
+// ===========================================================================
+
+/// Helper: admin + treasury + 500-unit registration fee. Returns (env, client, fee_token, treasury).
+fn fee_setup() -> (Env, RevoraRevenueShareClient<'static>, Address, Address) {
+    let (env, client, _admin) = admin_setup();
+    let treasury = Address::generate(&env);
+    let (fee_token, _fee_admin) = create_payment_token(&env);
+    client.set_treasury(&treasury);
+    client.set_registration_fee(&fee_token, &500);
+    (env, client, fee_token, treasury)
+}
+
+#[test]
+fn set_registration_fee_requires_treasury() {
+    let (env, client, _admin) = admin_setup();
+    let (fee_token, _fee_admin) = create_payment_token(&env);
+
+    let r = client.try_set_registration_fee(&fee_token, &500);
+    assert_eq!(r, Err(Ok(RevoraError::RecipientNotSet)));
+    let r = client.try_set_registration_fee(&fee_token, &-1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+}
+
+#[test]
+fn register_offering_collects_fee_to_treasury() {
+    let (env, client, fee_token, treasury) = fee_setup();
+    let issuer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &fee_token).mint(&issuer, &1_000);
+
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(balance(&env, &fee_token, &treasury), 500);
+    assert_eq!(balance(&env, &fee_token, &issuer), 500);
+}
+
+#[test]
+#[should_panic]
+fn register_offering_fails_without_fee_balance() {
+    let (env, client, _fee_token, _treasury) = fee_setup();
+    let issuer = Address::generate(&env);
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+}
+
+#[test]
+fn fee_waiver_skips_fee() {
+    let (env, client, fee_token, treasury) = fee_setup();
+    let partner = Address::generate(&env);
+
+    client.add_fee_waiver(&partner);
+    assert!(client.has_fee_waiver(&partner));
+    client.register_offering(&partner, &Address::generate(&env), &1_000);
+    assert_eq!(balance(&env, &fee_token, &treasury), 0);
+
+    client.remove_fee_waiver(&partner);
+    assert!(!client.has_fee_waiver(&partner));
+}
+
+#[test]
+fn registration_fee_change_through_timelock() {
+    let (env, client, fee_token, _treasury) = fee_setup();
+
+    let id = client.propose_admin_op(&AdminAction::SetRegistrationFee(fee_token.clone(), 0));
+    client.execute_admin_op(&id);
+    assert_eq!(client.get_registration_fee().unwrap().amount, 0);
+
+    // Zero fee: registration works without any balance
+    let issuer = Address::generate(&env);
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+}
+
+// ===========================================================================
+// Detailed offerings listing
+// ===========================================================================
+
+#[test]
+fn list_offerings_detailed_returns_full_structs() {
+    let (env, client, issuer) = setup();
+    register_n(&env, &client, &issuer, 3);
+
+    let page = client.list_offerings_detailed(&issuer, &0, &10);
+    assert_eq!(page.len(), 3);
+    for i in 0..3 {
+        let offering = page.get(i).unwrap();
+        assert_eq!(offering.issuer, issuer);
+        assert_eq!(offering.revenue_share_bps, 100 + i);
+    }
+}
+
+#[test]
+fn list_offerings_detailed_respects_cursor_and_limit() {
+    let (env, client, issuer) = setup();
+    register_n(&env, &client, &issuer, 5);
+
+    let page = client.list_offerings_detailed(&issuer, &3, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().revenue_share_bps, 103);
+
+    assert_eq!(client.list_offerings_detailed(&issuer, &10, &5).len(), 0);
+}
+
+// ===========================================================================
+// Offerings by status
+// ===========================================================================
+
+#[test]
+fn list_offerings_by_status_tracks_transitions() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let other = Address::generate(&env);
+    client.register_offering(&issuer, &other, &1_000);
+
+    let (active, cursor) =
+        client.list_offerings_by_status(&issuer, &OfferingStatus::Active, &0, &10);
+    assert_eq!(active.len(), 2);
+    assert_eq!(cursor, None);
+
+    suspend_offering(&env, &client, &issuer, &token);
+    let (active, _) = client.list_offerings_by_status(&issuer, &OfferingStatus::Active, &0, &10);
+    let (suspended, _) =
+        client.list_offerings_by_status(&issuer, &OfferingStatus::Suspended, &0, &10);
+    assert_eq!(active, soroban_sdk::vec![&env, other.clone()]);
+    assert_eq!(suspended, soroban_sdk::vec![&env, token.clone()]);
+
+    for p in 0..3u64 {
+        client.report_revenue(&issuer, &token, &1_000, &p, &false);
+    }
+    client.reinstate_offering(&issuer, &token);
+    let (active, _) = client.list_offerings_by_status(&issuer, &OfferingStatus::Active, &0, &10);
+    let (suspended, _) =
+        client.list_offerings_by_status(&issuer, &OfferingStatus::Suspended, &0, &10);
+    assert_eq!(active.len(), 2);
+    assert_eq!(suspended.len(), 0);
+}
+
+#[test]
+fn list_offerings_by_status_paginates() {
+    let (env, client, issuer) = setup();
+    register_n(&env, &client, &issuer, 5);
+
+    let (p1, c1) = client.list_offerings_by_status(&issuer, &OfferingStatus::Active, &0, &2);
+    assert_eq!(p1.len(), 2);
+    assert_eq!(c1, Some(2));
+    let (p2, c2) = client.list_offerings_by_status(&issuer, &OfferingStatus::Active, &4, &2);
+    assert_eq!(p2.len(), 1);
+    assert_eq!(c2, None);
+}
+
+// ===========================================================================
+// Sequential offering IDs
+// ===========================================================================
+
+#[test]
+fn offering_ids_are_sequential_and_resolvable() {
+    let (env, client, issuer) = setup();
+    let other_issuer = Address::generate(&env);
+    let t1 = Address::generate(&env);
+    let t2 = Address::generate(&env);
+    client.register_offering(&issuer, &t1, &1_000);
+    client.register_offering(&other_issuer, &t2, &2_000);
+
+    assert_eq!(client.get_offering_id(&t1), Some(1));
+    assert_eq!(client.get_offering_id(&t2), Some(2));
+    let offering = client.get_offering_by_id(&2).unwrap();
+    assert_eq!(offering.issuer, other_issuer);
+    assert_eq!(offering.token, t2);
+    assert_eq!(client.get_offering_by_id(&3), None);
+}
+
+#[test]
+fn offering_id_unknown_token() {
+    let (env, client, _issuer) = setup();
+    assert_eq!(client.get_offering_id(&Address::generate(&env)), None);
+}
+
+#[test]
+fn offering_events_carry_id_topic() {
+    use soroban_sdk::{IntoVal, Val};
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    client.report_revenue(&issuer, &token, &5_000, &1, &false);
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    let id: Val = 1u64.into_val(&env);
+    assert_eq!(topics.len(), 4);
+    assert_eq!(topics.get(3).unwrap().get_payload(), id.get_payload());
+}
+
+// ===========================================================================
+// Per-offering operator
+// ===========================================================================
+
+#[test]
+fn operator_can_manage_blacklist_and_report() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    client.set_offering_operator(&issuer, &token, &Some(operator.clone()));
+    assert_eq!(client.get_offering_operator(&token), Some(operator.clone()));
+
+    client.blacklist_add(&operator, &token, &investor);
+    assert!(client.is_blacklisted(&token, &investor));
+    client.blacklist_remove(&operator, &token, &investor);
+    assert!(!client.is_blacklisted(&token, &investor));
+
+    client.operator_report_revenue(&operator, &token, &5_000, &1, &false);
+    assert_eq!(
+        client
+            .get_audit_summary(&issuer, &token)
+            .unwrap()
+            .report_count,
+        1
+    );
+}
+
+#[test]
+fn stranger_cannot_manage_registered_offering() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+
+    let r = client.try_blacklist_add(&stranger, &token, &Address::generate(&env));
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    let r = client.try_operator_report_revenue(&stranger, &token, &5_000, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+#[test]
+fn only_issuer_sets_operator() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    client.set_offering_operator(&issuer, &token, &Some(operator.clone()));
+
+    // Operator cannot re-delegate
+    let r = client.try_set_offering_operator(&operator, &token, &Some(operator.clone()));
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.set_offering_operator(&issuer, &token, &None);
+    assert_eq!(client.get_offering_operator(&token), None);
+    let r = client.try_operator_report_revenue(&operator, &token, &5_000, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Merkle whitelist
+// ===========================================================================
+
+fn wl_leaf(env: &Env, investor: &Address) -> BytesN<32> {
+    env.crypto()
+        .sha256(&investor.clone().to_xdr(env))
+        .to_bytes()
+}
+
+fn wl_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (lo, hi) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut buf = Bytes::from_array(env, &lo.to_array());
+    buf.append(&Bytes::from_array(env, &hi.to_array()));
+    env.crypto().sha256(&buf).to_bytes()
+}
+
+/// Helper: 4-investor tree. Returns (investors, root).
+fn wl_tree(env: &Env) -> ([Address; 4], BytesN<32>) {
+    let investors = [
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    let leaves = investors.clone().map(|i| wl_leaf(env, &i));
+    let left = wl_pair(env, &leaves[0], &leaves[1]);
+    let right = wl_pair(env, &leaves[2], &leaves[3]);
+    (investors, wl_pair(env, &left, &right))
+}
+
+#[test]
+fn whitelist_claim_slot_with_valid_proof() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let (investors, root) = wl_tree(&env);
+    client.set_whitelist_root(&issuer, &token, &root);
+
+    // Proof for investor 2: sibling leaf 3, then hash(0,1)
+    let proof = soroban_sdk::vec![
+        &env,
+        wl_leaf(&env, &investors[3]),
+        wl_pair(
+            &env,
+            &wl_leaf(&env, &investors[0]),
+            &wl_leaf(&env, &investors[1])
+        ),
+    ];
+    assert!(!client.is_whitelisted(&token, &investors[2]));
+    client.whitelist_claim_slot(&token, &investors[2], &proof);
+    assert!(client.is_whitelisted(&token, &investors[2]));
+    assert!(!client.is_whitelisted(&token, &investors[3]));
+}
+
+#[test]
+fn whitelist_claim_slot_rejects_bad_proof() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let (investors, root) = wl_tree(&env);
+    client.set_whitelist_root(&issuer, &token, &root);
+
+    let outsider = Address::generate(&env);
+    let proof = soroban_sdk::vec![
+        &env,
+        wl_leaf(&env, &investors[3]),
+        wl_pair(
+            &env,
+            &wl_leaf(&env, &investors[0]),
+            &wl_leaf(&env, &investors[1])
+        ),
+    ];
+    let r = client.try_whitelist_claim_slot(&token, &outsider, &proof);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidProof)));
+}
+
+#[test]
+fn whitelist_root_rotation_revokes_slots() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let (investors, root) = wl_tree(&env);
+    client.set_whitelist_root(&issuer, &token, &root);
+    let proof = soroban_sdk::vec![
+        &env,
+        wl_leaf(&env, &investors[1]),
+        wl_pair(
+            &env,
+            &wl_leaf(&env, &investors[2]),
+            &wl_leaf(&env, &investors[3])
+        ),
+    ];
+    client.whitelist_claim_slot(&token, &investors[0], &proof);
+    assert!(client.is_whitelisted(&token, &investors[0]));
+
+    let (_, new_root) = wl_tree(&env);
+    client.set_whitelist_root(&issuer, &token, &new_root);
+    assert!(!client.is_whitelisted(&token, &investors[0]));
+}
+
+#[test]
+fn whitelist_root_requires_issuer_or_operator() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let (_, root) = wl_tree(&env);
+    let r = client.try_set_whitelist_root(&Address::generate(&env), &token, &root);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Chunked blacklist import
+// ===========================================================================
+
+fn addresses(env: &Env, n: u32) -> Vec<Address> {
+    let mut v = Vec::new(env);
+    for _ in 0..n {
+        v.push_back(Address::generate(env));
+    }
+    v
+}
+
+#[test]
+fn blacklist_import_tracks_progress_to_completion() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+
+    let c0 = addresses(&env, 3);
+    let c1 = addresses(&env, 2);
+    let p = client.blacklist_import(&issuer, &token, &c0, &0, &2);
+    assert_eq!((p.next_chunk, p.imported, p.complete), (1, 3, false));
+    let p = client.blacklist_import(&issuer, &token, &c1, &1, &2);
+    assert_eq!((p.next_chunk, p.imported, p.complete), (2, 5, true));
+
+    assert_eq!(client.get_blacklist(&token).len(), 5);
+    assert!(client.is_blacklisted(&token, &c1.get(1).unwrap()));
+    assert_eq!(client.get_blacklist_import(&token), Some(p));
+}
+
+#[test]
+fn blacklist_remove_keeps_remaining_members_listed() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let members = addresses(&env, 3);
+    client.blacklist_import(&issuer, &token, &members, &0, &1);
+
+    let first = members.get(0).unwrap();
+    client.blacklist_remove(&issuer, &token, &first);
+    let list = client.get_blacklist(&token);
+    assert_eq!(list.len(), 2);
+    assert!(!list.contains(&first));
+    assert!(list.contains(members.get(1).unwrap()));
+    assert!(list.contains(members.get(2).unwrap()));
+
+    client.blacklist_remove(&issuer, &token, &members.get(2).unwrap());
+    client.blacklist_add(&issuer, &token, &first);
+    assert_eq!(
+        client.get_blacklist(&token),
+        soroban_sdk::vec![&env, members.get(1).unwrap(), first]
+    );
+}
+
+#[test]
+fn blacklist_import_rejects_out_of_order_chunk() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+
+    let r = client.try_blacklist_import(&issuer, &token, &addresses(&env, 1), &1, &3);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+
+    client.blacklist_import(&issuer, &token, &addresses(&env, 1), &0, &3);
+    let r = client.try_blacklist_import(&issuer, &token, &addresses(&env, 1), &2, &3);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+    // Mismatched total
+    let r = client.try_blacklist_import(&issuer, &token, &addresses(&env, 1), &1, &4);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+}
+
+#[test]
+fn blacklist_import_rejects_oversized_chunk() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let r = client.try_blacklist_import(&issuer, &token, &addresses(&env, 101), &0, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+}
+
+// ===========================================================================
+// Blacklist hash commitment
+// ===========================================================================
+
+fn xor_hashes(env: &Env, investors: &[Address]) -> BytesN<32> {
+    let mut acc = [0u8; 32];
+    for inv in investors {
+        let leaf = wl_leaf(env, inv).to_array();
+        for (a, l) in acc.iter_mut().zip(leaf.iter()) {
+            *a ^= l;
+        }
+    }
+    BytesN::from_array(env, &acc)
+}
+
+#[test]
+fn blacklist_hash_matches_offchain_recompute() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    assert_eq!(
+        client.get_blacklist_hash(&token),
+        BytesN::from_array(&env, &[0u8; 32])
+    );
+
+    client.blacklist_add(&issuer, &token, &a);
+    client.blacklist_add(&issuer, &token, &b);
+    client.blacklist_add(&issuer, &token, &a); // idempotent, hash unchanged
+    assert_eq!(
+        client.get_blacklist_hash(&token),
+        xor_hashes(&env, &[a.clone(), b.clone()])
+    );
+
+    client.blacklist_remove(&issuer, &token, &a);
+    client.blacklist_remove(&issuer, &token, &a); // idempotent
+    assert_eq!(client.get_blacklist_hash(&token), xor_hashes(&env, &[b]));
+}
+
+#[test]
+fn legacy_blacklist_map_still_blocks_and_folds_on_change() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let mut legacy = soroban_sdk::Map::new(&env);
+    legacy.set(a.clone(), true);
+    legacy.set(b.clone(), true);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Blacklist(token.clone()), &legacy);
+    });
+    // Not read until the deployment has migrated from layout 0
+    assert!(!client.is_blacklisted(&token, &a));
+    client.set_admin(&Address::generate(&env));
+    simulate_layout_zero(&env, &client);
+    client.post_upgrade(&0);
+
+    assert!(client.is_blacklisted(&token, &a));
+    assert_eq!(client.get_blacklist(&token).len(), 2);
+
+    client.blacklist_remove(&issuer, &token, &a);
+    assert!(!client.is_blacklisted(&token, &a));
+    assert!(client.is_blacklisted(&token, &b));
+    assert_eq!(
+        client.get_blacklist(&token),
+        soroban_sdk::vec![&env, b.clone()]
+    );
+    assert_eq!(client.get_blacklist_hash(&token), xor_hashes(&env, &[b]));
+}
+
+#[test]
+fn blacklist_hash_covers_imports() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    client.blacklist_add(&issuer, &token, &a);
+    client.blacklist_import(
+        &issuer,
+        &token,
+        &soroban_sdk::vec![&env, a.clone(), b.clone()],
+        &0,
+        &1,
+    );
+    assert_eq!(client.get_blacklist_hash(&token), xor_hashes(&env, &[a, b]));
+}
+
+// ===========================================================================
+// Combined eligibility
+// ===========================================================================
+
+#[test]
+fn is_eligible_reports_first_failing_rule() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::Eligible
+    );
+
+    client.set_investor_jurisdiction(&issuer, &token, &investor, &44);
+    client.set_jurisdiction_blocked(&issuer, &token, &44, &true);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::JurisdictionBlocked
+    );
+
+    client.set_kyc_required(&issuer, &token, &true);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::KycMissing
+    );
+    client.set_kyc_status(&issuer, &token, &investor, &true);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::JurisdictionBlocked
+    );
+
+    client.set_investor_frozen(&issuer, &token, &investor, &true);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::InvestorFrozen
+    );
+
+    client.blacklist_add(&issuer, &token, &investor);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::Blacklisted
+    );
+
+    client.close_offering(&issuer, &token);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::OfferingClosed
+    );
+}
+
+#[test]
+fn whitelist_mode_requires_slot() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let (investors, root) = wl_tree(&env);
+    client.set_whitelist_root(&issuer, &token, &root);
+    assert_eq!(
+        client.is_eligible(&token, &investors[0]),
+        EligibilityResult::NotWhitelisted
+    );
+}
+
+#[test]
+fn claim_blocked_for_frozen_investor() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    client.set_investor_frozen(&issuer, &token, &holder, &true);
+    assert_eq!(
+        client.try_claim(&holder, &token, &0),
+        Err(Ok(RevoraError::NotEligible))
+    );
+    assert!(!client.get_investor_statement(&token, &holder).can_claim);
+
+    client.set_investor_frozen(&issuer, &token, &holder, &false);
+    assert_eq!(client.claim(&holder, &token, &0), 50_000);
+}
+
+#[test]
+fn compliance_setters_require_issuer_or_operator() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let stranger = Address::generate(&env);
+    let r = client.try_set_kyc_required(&stranger, &token, &true);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Compliance modules
+// ===========================================================================
+
+/// Test compliance module: rejects investors on its deny list.
+#[contract]
+pub struct DenyListModule;
+
+#[contractimpl]
+impl DenyListModule {
+    pub fn deny(env: Env, investor: Address) {
+        env.storage().instance().set(&investor, &true);
+    }
+
+    pub fn check(env: Env, investor: Address, _context: Address) -> bool {
+        !env.storage().instance().has(&investor)
+    }
+}
+
+/// Test compliance module that always traps. In its own module because contract
+/// functions of the same name collide within one module.
+mod broken_module {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct BrokenModule;
+
+    #[contractimpl]
+    impl BrokenModule {
+        pub fn check(_env: Env, _investor: Address, _context: Address) -> bool {
+            panic!("module unavailable")
+        }
+    }
+}
+use broken_module::BrokenModule;
+
+#[test]
+fn compliance_module_rejects_investor() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let module_id = env.register_contract(None, DenyListModule);
+    let module = DenyListModuleClient::new(&env, &module_id);
+    let investor = Address::generate(&env);
+
+    client.set_compliance_module(&issuer, &token, &Some(module_id.clone()));
+    assert_eq!(client.get_compliance_module(&token), Some(module_id));
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::Eligible
+    );
+
+    module.deny(&investor);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::ModuleRejected
+    );
+
+    client.set_compliance_module(&issuer, &token, &None);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::Eligible
+    );
+}
+
+#[test]
+fn failing_compliance_module_rejects() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let module_id = env.register_contract(None, BrokenModule);
+    client.set_compliance_module(&issuer, &token, &Some(module_id));
+    let investor = Address::generate(&env);
+    assert_eq!(
+        client.is_eligible(&token, &investor),
+        EligibilityResult::ModuleRejected
+    );
+}
+
+// ===========================================================================
+// Distribution strategies
+// ===========================================================================
+
+/// Test strategy: waterfall where the first-listed senior holder takes everything
+/// up to its cap before pro-rata applies to the rest.
+#[contract]
+pub struct SeniorFirstStrategy;
+
+#[contractimpl]
+impl SeniorFirstStrategy {
+    pub fn set_senior(env: Env, senior: Address, cap: i128) {
+        env.storage().instance().set(&0u32, &(senior, cap));
+    }
+
+    pub fn compute_payout(
+        env: Env,
+        _token: Address,
+        holder: Address,
+        share_bps: u32,
+        _period_id: u64,
+        revenue: i128,
+    ) -> i128 {
+        let (senior, cap): (Address, i128) = env.storage().instance().get(&0u32).unwrap();
+        let senior_take = core::cmp::min(cap, revenue);
+        if holder == senior {
+            senior_take
+        } else {
+            (revenue - senior_take) * (share_bps as i128) / 10_000
+        }
+    }
+}
+
+#[test]
+fn claim_uses_configured_strategy() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let senior = Address::generate(&env);
+    let junior = Address::generate(&env);
+    let strategy_id = env.register_contract(None, SeniorFirstStrategy);
+    SeniorFirstStrategyClient::new(&env, &strategy_id).set_senior(&senior, &60_000);
+
+    client.set_distribution_strategy(&issuer, &token, &Some(strategy_id.clone()));
+    assert_eq!(client.get_distribution_strategy(&token), Some(strategy_id));
+    client.set_holder_share(&issuer, &token, &senior, &5_000);
+    client.set_holder_share(&issuer, &token, &junior, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+
+    assert_eq!(client.get_claimable(&token, &senior), 60_000);
+    assert_eq!(client.claim(&senior, &token, &0), 60_000);
+    assert_eq!(client.claim(&junior, &token, &0), 20_000);
+}
+
+#[test]
+fn clearing_strategy_restores_pro_rata() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let strategy_id = env.register_contract(None, SeniorFirstStrategy);
+    SeniorFirstStrategyClient::new(&env, &strategy_id).set_senior(&holder, &1);
+
+    client.set_distribution_strategy(&issuer, &token, &Some(strategy_id));
+    client.set_distribution_strategy(&issuer, &token, &None);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    assert_eq!(client.get_claimable(&token, &holder), 50_000);
+}
+
+#[test]
+fn only_issuer_sets_strategy() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let operator = Address::generate(&env);
+    client.set_offering_operator(&issuer, &token, &Some(operator.clone()));
+    let r = client.try_set_distribution_strategy(&operator, &token, &None);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Report finalization and listeners
+// ===========================================================================
+
+/// Test listener: records the last notification it received.
+#[contract]
+pub struct RecordingListener;
+
+#[contractimpl]
+impl RecordingListener {
+    pub fn subscribe(env: Env, revora: Address, token: Address) {
+        RevoraRevenueShareClient::new(&env, &revora)
+            .register_listener(&env.current_contract_address(), &token);
+    }
+
+    pub fn notify(env: Env, token: Address, period_id: u64, amount: i128) {
+        env.storage()
+            .instance()
+            .set(&0u32, &(token, period_id, amount));
+    }
+
+    pub fn last(env: Env) -> Option<(Address, u64, i128)> {
+        env.storage().instance().get(&0u32)
+    }
+}
+
+#[test]
+fn finalize_report_locks_period_and_notifies_listeners() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let listener_id = env.register_contract(None, RecordingListener);
+    let listener = RecordingListenerClient::new(&env, &listener_id);
+    listener.subscribe(&contract_id, &token);
+    assert_eq!(client.get_report_listeners(&token).len(), 1);
+
+    client.report_revenue(&issuer, &token, &7_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &7_000, &1);
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 7_000);
+    assert!(client.is_period_finalized(&token, &1));
+    assert_eq!(listener.last(), Some((token.clone(), 1, 7_000)));
+
+    let r = client.try_report_revenue(&issuer, &token, &9_000, &1, &true);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFinalized)));
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFinalized)));
+}
+
+#[test]
+fn finalize_report_requires_report() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let r = client.try_finalize_report(&issuer, &token, &5);
+    assert_eq!(r, Err(Ok(RevoraError::ReportNotFound)));
+}
+
+#[test]
+fn broken_listener_does_not_block_finalization() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let broken = env.register_contract(None, BrokenModule);
+    client.register_listener(&broken, &token);
+    client.report_revenue(&issuer, &token, &7_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &7_000, &1);
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 7_000);
+}
+
+#[test]
+fn listener_list_is_bounded_and_admin_removable() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let first = Address::generate(&env);
+    client.register_listener(&first, &token);
+    for _ in 1..10 {
+        client.register_listener(&Address::generate(&env), &token);
+    }
+    let r = client.try_register_listener(&Address::generate(&env), &token);
+    assert_eq!(r, Err(Ok(RevoraError::LimitReached)));
+
+    client.remove_listener(&token, &first);
+    assert_eq!(client.get_report_listeners(&token).len(), 9);
+}
+
+// ===========================================================================
+// Partial reports
+// ===========================================================================
+
+#[test]
+fn partial_reports_accumulate_until_finalized() {
+    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    for _ in 0..4 {
+        client.report_partial_revenue(&issuer, &token, &2_500, &7);
+    }
+    assert_eq!(client.get_period_report(&issuer, &token, &7), Some(10_000));
+    assert_eq!(
+        client
+            .get_audit_summary(&issuer, &token)
+            .unwrap()
+            .total_revenue,
+        10_000
+    );
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &7);
+    assert_eq!(client.finalize_report(&issuer, &token, &7), 10_000);
+    let r = client.try_report_partial_revenue(&issuer, &token, &1, &7);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFinalized)));
+}
+
+#[test]
+fn partial_report_adds_to_full_report() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &4_000, &1, &false);
+    client.report_partial_revenue(&issuer, &token, &1_000, &1);
+    assert_eq!(client.get_period_report(&issuer, &token, &1), Some(5_000));
+    assert_eq!(client.get_period_report(&issuer, &token, &2), None);
+}
+
+// ===========================================================================
+// Auditor co-signature
+// ===========================================================================
+
+#[test]
+fn finalize_requires_auditor_attestation() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let auditor = Address::generate(&env);
+    client.set_auditor(&issuer, &token, &Some(auditor.clone()), &true);
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::AttestationRequired)));
+
+    assert_eq!(client.attest_report(&auditor, &token, &1), 8_000);
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 8_000);
+}
+
+#[test]
+fn report_change_invalidates_attestation() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let auditor = Address::generate(&env);
+    client.set_auditor(&issuer, &token, &Some(auditor.clone()), &true);
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &9_000, &1);
+    client.attest_report(&auditor, &token, &1);
+
+    client.report_revenue(&issuer, &token, &9_000, &1, &true);
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::AttestationRequired)));
+}
+
+#[test]
+fn only_designated_auditor_attests() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_auditor(&issuer, &token, &Some(Address::generate(&env)), &false);
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    let r = client.try_attest_report(&Address::generate(&env), &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    // Attestation optional: finalization proceeds without it
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 8_000);
+}
+
+// ===========================================================================
+// Signed accounting attestations
+// ===========================================================================
+
+fn attestation_signature(
+    env: &Env,
+    signer: &ed25519_dalek::SigningKey,
+    token: &Address,
+    period_id: u64,
+    amount: i128,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    let mut message = token.clone().to_xdr(env);
+    message.append(&Bytes::from_array(env, &period_id.to_be_bytes()));
+    message.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+    let mut buf = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(env, &signer.sign(&buf[..len]).to_bytes())
+}
+
+#[test]
+fn signed_attestation_is_verified_and_stored() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    client.set_accounting_key(&issuer, &token, &Some(public_key.clone()));
+    client.report_revenue(&issuer, &token, &12_345, &3, &false);
+
+    let sig = attestation_signature(&env, &signer, &token, 3, 12_345);
+    client.submit_signed_attestation(&token, &3, &12_345, &sig);
+    let stored = client.get_signed_attestation(&token, &3).unwrap();
+    assert_eq!(stored.amount, 12_345);
+    assert_eq!(stored.public_key, public_key);
+}
+
+#[test]
+#[should_panic]
+fn signed_attestation_rejects_wrong_signer() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let forger = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let public_key = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    client.set_accounting_key(&issuer, &token, &Some(public_key));
+    client.report_revenue(&issuer, &token, &12_345, &3, &false);
+
+    let sig = attestation_signature(&env, &forger, &token, 3, 12_345);
+    client.submit_signed_attestation(&token, &3, &12_345, &sig);
+}
+
+#[test]
+fn signed_attestation_must_match_report() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let signer = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    client.set_accounting_key(&issuer, &token, &Some(public_key));
+    client.report_revenue(&issuer, &token, &12_345, &3, &false);
+
+    let sig = attestation_signature(&env, &signer, &token, 3, 99);
+    let r = client.try_submit_signed_attestation(&token, &3, &99, &sig);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+}
+
+// ===========================================================================
+// Fiat-denominated reporting
+// ===========================================================================
+
+/// Test oracle with a settable price.
+#[contract]
+pub struct FixedPriceOracle;
+
+#[contractimpl]
+impl FixedPriceOracle {
+    pub fn set_price(env: Env, price: i128) {
+        env.storage().instance().set(&0u32, &price);
+    }
+
+    pub fn price(env: Env, _currency: soroban_sdk::Symbol, _asset: Address) -> i128 {
+        env.storage().instance().get(&0u32).unwrap_or(0)
+    }
+}
+
+fn fiat_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let oracle = env.register_contract(None, FixedPriceOracle);
+    client.set_fiat_reporting(
+        &issuer,
+        &token,
+        &Some(FiatReportingConfig {
+            oracle: oracle.clone(),
+            currency: soroban_sdk::symbol_short!("USD"),
+            payout_token: payment_token.clone(),
+        }),
+    );
+    (env, client, issuer, token, payment_token, oracle)
+}
+
+#[test]
+fn finalize_converts_fiat_at_oracle_rate() {
+    let (env, client, issuer, token, payment_token, oracle) = fiat_setup();
+    // 1 USD = 2.5 payout tokens
+    FixedPriceOracleClient::new(&env, &oracle).set_price(&25_000_000);
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_500, &1);
+
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 2_500);
+    let conversion = client.get_fiat_conversion(&token, &1).unwrap();
+    assert_eq!(conversion.fiat_amount, 1_000);
+    assert_eq!(conversion.rate, 25_000_000);
+    assert_eq!(conversion.payout_amount, 2_500);
+}
+
+#[test]
+fn finalize_rejects_non_positive_rate() {
+    let (_env, client, issuer, token, payment_token, _oracle) = fiat_setup();
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_500, &1);
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOraclePrice)));
+    assert!(!client.is_period_finalized(&token, &1));
+}
+
+// ===========================================================================
+// Deduction caps
+// ===========================================================================
+
+#[test]
+fn structured_report_records_net_and_breakdown() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_deduction_cap(&issuer, &token, &3_000);
+    client.report_structured_revenue(&issuer, &token, &10_000, &1_000, &1, &false);
+
+    assert_eq!(client.get_period_report(&issuer, &token, &1), Some(9_000));
+    let s = client.get_structured_report(&token, &1).unwrap();
+    assert_eq!((s.gross, s.deductions), (10_000, 1_000));
+    assert!(!client.is_flagged_for_review(&token, &1));
+}
+
+#[test]
+fn structured_report_rejects_deductions_over_cap() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_deduction_cap(&issuer, &token, &3_000);
+    let r = client.try_report_structured_revenue(&issuer, &token, &10_000, &3_001, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::DeductionCapExceeded)));
+}
+
+#[test]
+fn borderline_deductions_flagged_until_attested() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let auditor = Address::generate(&env);
+    client.set_deduction_cap(&issuer, &token, &3_000);
+    client.set_auditor(&issuer, &token, &Some(auditor.clone()), &false);
+
+    client.report_structured_revenue(&issuer, &token, &10_000, &2_800, &1, &false);
+    assert!(client.is_flagged_for_review(&token, &1));
+    client.attest_report(&auditor, &token, &1);
+    assert!(!client.is_flagged_for_review(&token, &1));
+}
+
+#[test]
+fn deduction_cap_fixed_once_reporting_starts() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    let r = client.try_set_deduction_cap(&issuer, &token, &3_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+// ===========================================================================
+// Issuer bonds
+// ===========================================================================
+
+#[test]
+fn register_offering_stakes_bond() {
+    let (env, client, _admin) = admin_setup();
+    let (bond_token, bond_admin) = create_payment_token(&env);
+    client.set_issuer_bond(&bond_token, &1_000);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    mint_tokens(&env, &bond_token, &bond_admin, &issuer, &1_500);
+
+    client.register_offering(&issuer, &token, &1_000);
+    let bond = client.get_offering_bond(&token).unwrap();
+    assert_eq!((bond.amount, bond.slashed), (1_000, 0));
+    assert_eq!(bond.issuer, issuer);
+    assert_eq!(balance(&env, &bond_token, &issuer), 500);
+    assert_eq!(balance(&env, &bond_token, &client.address), 1_000);
+}
+
+#[test]
+fn no_bond_when_unconfigured_or_testnet() {
+    let (env, client, _admin) = admin_setup();
+    let (bond_token, _bond_admin) = create_payment_token(&env);
+    let token = Address::generate(&env);
+    client.register_offering(&Address::generate(&env), &token, &1_000);
+    assert_eq!(client.get_offering_bond(&token), None);
+
+    client.set_issuer_bond(&bond_token, &1_000);
+    client.set_testnet_mode(&true);
+    let token2 = Address::generate(&env);
+    client.register_offering(&Address::generate(&env), &token2, &1_000);
+    assert_eq!(client.get_offering_bond(&token2), None);
+}
+
+// ===========================================================================
+// Disputes, slashing and appeals
+// ===========================================================================
+
+/// Helper: bonded offering with a report for period 1, an arbiter and a treasury.
+/// Returns (env, client, issuer, token, bond_token, arbiter, treasury).
+fn bonded_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, _admin) = admin_setup();
+    let (bond_token, bond_admin) = create_payment_token(&env);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    mint_tokens(&env, &bond_token, &bond_admin, &issuer, &1_000);
+    client.set_issuer_bond(&bond_token, &1_000);
+    client.set_treasury(&treasury);
+    client.grant_role(&Role::Arbiter, &arbiter);
+    client.register_offering(&issuer, &token, &1_000);
+    client.report_revenue(&issuer, &token, &5_000, &1, &false);
+    (env, client, issuer, token, bond_token, arbiter, treasury)
+}
+
+fn evidence(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[1u8; 32])
+}
+
+#[test]
+fn lost_dispute_slash_executes_after_appeal_window() {
+    let (env, client, issuer, token, bond_token, arbiter, treasury) = bonded_setup();
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    assert_eq!(
+        client
+            .get_offering_health(&issuer, &token)
+            .unwrap()
+            .pending_disputes,
+        1
+    );
+
+    client.resolve_dispute(&arbiter, &token, &1, &true);
+    assert_eq!(
+        client.get_dispute(&token, &1).unwrap().status,
+        DisputeStatus::Upheld
+    );
+    assert_eq!(
+        client
+            .get_offering_health(&issuer, &token)
+            .unwrap()
+            .pending_disputes,
+        0
+    );
+
+    assert_eq!(client.slash_bond(&token, &1), 1_000);
+    assert_eq!(client.get_offering_bond(&token).unwrap().amount, 0);
+    let r = client.try_execute_slash(&token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+
+    env.ledger()
+        .set_timestamp(client.get_slash(&token, &1).unwrap().appeal_deadline + 1);
+    assert_eq!(client.execute_slash(&token, &1), 1_000);
+    assert_eq!(balance(&env, &bond_token, &treasury), 1_000);
+    assert_eq!(
+        client.get_slash(&token, &1).unwrap().status,
+        SlashStatus::Executed
+    );
+}
+
+#[test]
+fn arbiter_reverses_slash_on_appeal() {
+    let (env, client, _issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    client.resolve_dispute(&arbiter, &token, &1, &true);
+    client.slash_bond(&token, &1);
+
+    client.reverse_slash(&arbiter, &token, &1);
+    let bond = client.get_offering_bond(&token).unwrap();
+    assert_eq!((bond.amount, bond.slashed), (1_000, 0));
+    let r = client.try_execute_slash(&token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidDisputeState)));
+}
+
+#[test]
+fn slash_requires_upheld_dispute() {
+    let (env, client, _issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    assert_eq!(
+        client.try_slash_bond(&token, &1),
+        Err(Ok(RevoraError::DisputeNotFound))
+    );
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    assert_eq!(
+        client.try_slash_bond(&token, &1),
+        Err(Ok(RevoraError::InvalidDisputeState))
+    );
+    client.resolve_dispute(&arbiter, &token, &1, &false);
+    assert_eq!(
+        client.try_slash_bond(&token, &1),
+        Err(Ok(RevoraError::InvalidDisputeState))
+    );
+}
+
+#[test]
+fn only_arbiter_resolves() {
+    let (env, client, _issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    let r = client.try_resolve_dispute(&Address::generate(&env), &token, &1, &true);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.revoke_role(&Role::Arbiter, &arbiter);
+    assert!(!client.has_role(&Role::Arbiter, &arbiter));
+    let r = client.try_resolve_dispute(&arbiter, &token, &1, &true);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Insurance pool
+// ===========================================================================
+
+#[test]
+fn registration_fee_slice_funds_insurance_pool() {
+    let (env, client, fee_token, treasury) = fee_setup();
+    client.set_insurance_bps(&2_000);
+    let issuer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &fee_token).mint(&issuer, &500);
+
+    client.register_offering(&issuer, &Address::generate(&env), &1_000);
+    assert_eq!(client.get_insurance_pool(&fee_token), 100);
+    assert_eq!(balance(&env, &fee_token, &treasury), 400);
+    assert_eq!(balance(&env, &fee_token, &client.address), 100);
+}
+
+#[test]
+fn compensate_pays_investors_after_upheld_dispute() {
+    let (env, client, issuer, token, bond_token, arbiter, _treasury) = bonded_setup();
+    client.set_insurance_bps(&10_000);
+    // Offering pays out in the bond token so slashed funds can compensate
+    mint_tokens(&env, &bond_token, &Address::generate(&env), &issuer, &5_000);
+    client.deposit_revenue(&issuer, &token, &bond_token, &5_000, &1);
+
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    client.resolve_dispute(&arbiter, &token, &1, &true);
+    client.slash_bond(&token, &1);
+    env.ledger()
+        .set_timestamp(client.get_slash(&token, &1).unwrap().appeal_deadline + 1);
+    client.execute_slash(&token, &1);
+    assert_eq!(client.get_insurance_pool(&bond_token), 1_000);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let paid = client.compensate(
+        &token,
+        &1,
+        &soroban_sdk::vec![&env, a.clone(), b.clone()],
+        &soroban_sdk::vec![&env, 600i128, 300i128],
+    );
+    assert_eq!(paid, 900);
+    assert_eq!(balance(&env, &bond_token, &a), 600);
+    assert_eq!(client.get_insurance_pool(&bond_token), 100);
+    assert_eq!(client.get_compensation_paid(&token, &1), 900);
+
+    let r = client.try_compensate(
+        &token,
+        &1,
+        &soroban_sdk::vec![&env, a],
+        &soroban_sdk::vec![&env, 101i128],
+    );
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+}
+
+#[test]
+fn compensate_requires_upheld_dispute() {
+    let (env, client, _issuer, token, _bond_token, _arbiter, _treasury) = bonded_setup();
+    let r = client.try_compensate(&token, &1, &Vec::new(&env), &Vec::new(&env));
+    assert_eq!(r, Err(Ok(RevoraError::DisputeNotFound)));
+}
+
+// ===========================================================================
+// NFT share class
+// ===========================================================================
+
+#[contract]
+pub struct MockNftCollection;
+
+#[contractimpl]
+impl MockNftCollection {
+    pub fn set_owner(env: Env, token_id: u32, owner: Address) {
+        env.storage().instance().set(&token_id, &owner);
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        env.storage().instance().get(&token_id).unwrap()
+    }
+}
+
+/// Offering with a 4-NFT share class (ids 1..=4): `a` owns 1-3, `b` owns 4.
+fn nft_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+    MockNftCollectionClient<'static>,
+) {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let collection =
+        MockNftCollectionClient::new(&env, &env.register_contract(None, MockNftCollection));
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    for id in 1..=3u32 {
+        collection.set_owner(&id, &a);
+    }
+    collection.set_owner(&4, &b);
+    client.set_nft_share_class(&issuer, &token, &collection.address, &1, &4);
+    (env, client, issuer, token, payment_token, a, b, collection)
+}
+
+#[test]
+fn nft_class_pays_equal_share_per_token() {
+    let (env, client, issuer, token, payment_token, a, b, collection) = nft_setup();
+    assert_eq!(
+        client.get_nft_share_class(&token),
+        Some(NftShareClass {
+            collection: collection.address.clone(),
+            first_id: 1,
+            supply: 4
+        })
+    );
+    assert_eq!(client.snapshot_nft_owners(&issuer, &token, &1, &1, &10), 4);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(client.get_nft_payout_per_token(&token, &1), 250);
+
+    let ids = soroban_sdk::vec![&env, 1u32, 2, 3];
+    assert_eq!(client.claim_nft(&a, &token, &1, &ids), 750);
+    assert_eq!(
+        client.claim_nft(&b, &token, &1, &soroban_sdk::vec![&env, 4u32]),
+        250
+    );
+    assert_eq!(balance(&env, &payment_token, &a), 750);
+    assert!(client.is_nft_claimed(&token, &1, &4));
+
+    let r = client.try_claim_nft(&a, &token, &1, &soroban_sdk::vec![&env, 1u32]);
+    assert_eq!(r, Err(Ok(RevoraError::NoPendingClaims)));
+}
+
+#[test]
+fn nft_claim_uses_snapshot_not_current_owner() {
+    let (env, client, issuer, token, payment_token, a, b, collection) = nft_setup();
+    client.snapshot_nft_owners(&issuer, &token, &1, &1, &4);
+    // NFT 1 changes hands after the snapshot
+    collection.set_owner(&1, &b);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    let r = client.try_claim_nft(&b, &token, &1, &soroban_sdk::vec![&env, 1u32]);
+    assert_eq!(r, Err(Ok(RevoraError::NotEligible)));
+    assert_eq!(
+        client.claim_nft(&a, &token, &1, &soroban_sdk::vec![&env, 1u32]),
+        250
+    );
+    // Re-snapshotting leaves recorded owners untouched
+    assert_eq!(client.snapshot_nft_owners(&issuer, &token, &1, &1, &4), 0);
+    assert_eq!(client.get_nft_owner_at(&token, &1, &1), Some(a));
+}
+
+#[test]
+fn nft_class_locked_after_first_deposit() {
+    let (_env, client, issuer, token, payment_token, _a, _b, collection) = nft_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let r = client.try_set_nft_share_class(&issuer, &token, &collection.address, &1, &8);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+#[test]
+fn nft_class_disables_share_based_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let collection =
+        MockNftCollectionClient::new(&env, &env.register_contract(None, MockNftCollection));
+    let a = Address::generate(&env);
+    let custodian = Address::generate(&env);
+    collection.set_owner(&1, &a);
+    // A share set before the class cannot draw on NFT-class revenue
+    client.set_holder_share(&issuer, &token, &a, &10_000);
+    client.set_nft_share_class(&issuer, &token, &collection.address, &1, &1);
+    client.snapshot_nft_owners(&issuer, &token, &1, &1, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    let r = client.try_set_holder_share(&issuer, &token, &Address::generate(&env), &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    assert_eq!(client.get_claimable(&token, &a), 0);
+    let r = client.try_claim(&a, &token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    client.approve_manager(&a, &token, &custodian);
+    let r = client.try_claim_for(&custodian, &a, &token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+
+    assert_eq!(
+        client.claim_nft(&a, &token, &1, &soroban_sdk::vec![&env, 1u32]),
+        1_000
+    );
+    assert_eq!(balance(&env, &payment_token, &a), 1_000);
+}
+
+// ===========================================================================
+// Share class conversion
+// ===========================================================================
+
+const PREFERRED: u32 = 1;
+const COMMON: u32 = 0;
+
+#[test]
+fn class_shares_sum_into_holder_share() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    client.set_class_share(&issuer, &token, &investor, &PREFERRED, &2_000);
+    client.set_class_share(&issuer, &token, &investor, &COMMON, &1_000);
+    assert_eq!(client.get_holder_share(&token, &investor), 3_000);
+
+    let r = client.try_set_class_share(&issuer, &token, &investor, &COMMON, &9_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidShareBps)));
+}
+
+#[test]
+fn convert_class_applies_ratio_inside_window() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    client.set_class_share(&issuer, &token, &investor, &PREFERRED, &2_000);
+    // 1 preferred -> 1.5 common, window [100, 200)
+    let rule = ClassConversion {
+        ratio_bps: 15_000,
+        opens_at: 100,
+        closes_at: 200,
+    };
+    client.set_class_conversion(&issuer, &token, &PREFERRED, &COMMON, &rule);
+    assert_eq!(
+        client.get_class_conversion(&token, &PREFERRED, &COMMON),
+        Some(rule)
+    );
+
+    let r = client.try_convert_class(&investor, &token, &PREFERRED, &COMMON, &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+
+    env.ledger().set_timestamp(150);
+    assert_eq!(
+        client.convert_class(&investor, &token, &PREFERRED, &COMMON, &1_000),
+        1_500
+    );
+    assert_eq!(client.get_class_share(&token, &investor, &PREFERRED), 1_000);
+    assert_eq!(client.get_class_share(&token, &investor, &COMMON), 1_500);
+    assert_eq!(client.get_holder_share(&token, &investor), 2_500);
+
+    // Accrual follows the new weight
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    assert_eq!(client.get_claimable(&token, &investor), 2_500);
+
+    let r = client.try_convert_class(&investor, &token, &PREFERRED, &COMMON, &5_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+    env.ledger().set_timestamp(200);
+    let r = client.try_convert_class(&investor, &token, &PREFERRED, &COMMON, &500);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+}
+
+// ===========================================================================
+// Transfer agent
+// ===========================================================================
+
+fn agent_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let agent = Address::generate(&env);
+    client.grant_role(&Role::TransferAgent, &agent);
+    (env, client, issuer, token, payment_token, agent)
+}
+
+#[test]
+fn force_transfer_moves_shares_to_new_holder() {
+    let (env, client, issuer, token, payment_token, agent) = agent_setup();
+    let lost = Address::generate(&env);
+    let recovered = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &lost, &4_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    client.force_transfer(&agent, &token, &lost, &recovered, &4_000, &evidence(&env));
+    assert_eq!(client.get_holder_share(&token, &lost), 0);
+    assert_eq!(client.get_holder_share(&token, &recovered), 4_000);
+    // New holder accrues from the next period only
+    assert_eq!(client.get_claimable(&token, &recovered), 0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.get_claimable(&token, &recovered), 400);
+}
+
+#[test]
+fn clawback_returns_shares_to_issuer() {
+    let (env, client, issuer, token, _payment_token, agent) = agent_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &3_000);
+
+    client.clawback_shares(&agent, &token, &holder, &1_000, &evidence(&env));
+    assert_eq!(client.get_holder_share(&token, &holder), 2_000);
+    assert_eq!(client.get_holder_share(&token, &issuer), 1_000);
+
+    let r = client.try_clawback_shares(&agent, &token, &holder, &5_000, &evidence(&env));
+    assert_eq!(r, Err(Ok(RevoraError::InvalidShareBps)));
+}
+
+#[test]
+fn force_transfer_requires_transfer_agent_role() {
+    let (env, client, issuer, token, _payment_token, _agent) = agent_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &3_000);
+    let r = client.try_force_transfer(
+        &Address::generate(&env),
+        &token,
+        &holder,
+        &issuer,
+        &1_000,
+        &evidence(&env),
+    );
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Historical balances
+// ===========================================================================
+
+#[test]
+fn balance_at_returns_share_in_effect_at_deposit() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &investor, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &10);
+    // Two changes before the next deposit collapse into one checkpoint
+    client.set_holder_share(&issuer, &token, &investor, &2_000);
+    client.set_holder_share(&issuer, &token, &investor, &3_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &20);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &30);
+    client.set_holder_share(&issuer, &token, &investor, &0);
+
+    assert_eq!(client.balance_at(&token, &investor, &10), Some(1_000));
+    assert_eq!(client.balance_at(&token, &investor, &20), Some(3_000));
+    assert_eq!(client.balance_at(&token, &investor, &30), Some(3_000));
+    assert_eq!(client.balance_at(&token, &investor, &40), None);
+    assert_eq!(client.get_holder_share(&token, &investor), 0);
+}
+
+#[test]
+fn balance_at_is_zero_before_first_share() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.set_holder_share(&issuer, &token, &investor, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    assert_eq!(client.balance_at(&token, &investor, &1), Some(0));
+    assert_eq!(client.balance_at(&token, &investor, &2), Some(1_000));
+}
+
+// ===========================================================================
+// Accumulator accrual
+// ===========================================================================
+
+#[test]
+fn accumulator_splits_deposits_by_share_weight() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_accrual_mode(&issuer, &token, &AccrualMode::Shares);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &3_000);
+    client.set_holder_share(&issuer, &token, &b, &1_000);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &4_000, &1);
+    assert_eq!(client.get_accumulator(&token).total_weight, 4_000);
+    // b's weight changes mid-stream; earlier accrual is settled first
+    client.set_holder_share(&issuer, &token, &b, &3_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &6_000, &2);
+
+    assert_eq!(client.claim_accrued(&a, &token), 3_000 + 3_000);
+    assert_eq!(client.claim_accrued(&b, &token), 1_000 + 3_000);
+    assert_eq!(balance(&env, &payment_token, &b), 4_000);
+    assert_eq!(
+        client.try_claim_accrued(&a, &token),
+        Err(Ok(RevoraError::NoPendingClaims))
+    );
+    // Period claims are disabled for accumulator offerings
+    assert_eq!(
+        client.try_claim(&a, &token, &0),
+        Err(Ok(RevoraError::InvalidOfferingStatus))
+    );
+}
+
+#[test]
+fn accumulator_carries_revenue_deposited_without_weight() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_accrual_mode(&issuer, &token, &AccrualMode::Shares);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(client.get_accumulator(&token).undistributed, 1_000);
+
+    let a = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    assert_eq!(client.claim_accrued(&a, &token), 2_000);
+}
+
+#[test]
+fn accrual_mode_locked_after_first_deposit() {
+    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let r = client.try_set_accrual_mode(&issuer, &token, &AccrualMode::Shares);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    assert_eq!(client.get_accrual_mode(&token), AccrualMode::Periods);
+}
+
+// ===========================================================================
+// Staking
+// ===========================================================================
+
+/// Offering whose share token is a real token contract, in Staked accrual mode.
+/// Returns (env, client, issuer, share_token, payment_token, alice, bob); each investor
+/// holds 1_000 share tokens.
+fn staking_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    let issuer = Address::generate(&env);
+    let (share_token, share_admin) = create_payment_token(&env);
+    let (payment_token, pt_admin) = create_payment_token(&env);
+    client.register_offering(&issuer, &share_token, &5_000);
+    client.set_accrual_mode(&issuer, &share_token, &AccrualMode::Staked);
+    mint_tokens(&env, &payment_token, &pt_admin, &issuer, &1_000_000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_tokens(&env, &share_token, &share_admin, &alice, &1_000);
+    mint_tokens(&env, &share_token, &share_admin, &bob, &1_000);
+    (env, client, issuer, share_token, payment_token, alice, bob)
+}
+
+#[test]
+fn revenue_accrues_only_to_staked_balances() {
+    let (env, client, issuer, token, payment_token, alice, bob) = staking_setup();
+    assert_eq!(client.stake(&alice, &token, &600), 600);
+    client.stake(&bob, &token, &200);
+    assert_eq!(balance(&env, &token, &alice), 400);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    assert_eq!(client.claim_accrued(&alice, &token), 6_000);
+    assert_eq!(client.claim_accrued(&bob, &token), 2_000);
+}
+
+#[test]
+fn unstake_stops_accrual_and_returns_tokens() {
+    let (env, client, issuer, token, payment_token, alice, bob) = staking_setup();
+    client.stake(&alice, &token, &500);
+    client.stake(&bob, &token, &500);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    assert_eq!(client.unstake(&alice, &token, &500), 0);
+    assert_eq!(balance(&env, &token, &alice), 1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    assert_eq!(client.claim_accrued(&alice, &token), 500);
+    assert_eq!(client.claim_accrued(&bob, &token), 1_500);
+    let r = client.try_unstake(&alice, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+}
+
+#[test]
+fn stake_requires_staked_mode() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let r = client.try_stake(&Address::generate(&env), &token, &100);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+#[test]
+fn request_unstake_waits_out_cooldown() {
+    let (env, client, issuer, token, payment_token, alice, bob) = staking_setup();
+    client.set_unstake_cooldown(&issuer, &token, &3_600);
+    client.stake(&alice, &token, &500);
+    client.stake(&bob, &token, &500);
+
+    // Immediate exit is not available once a cooldown is configured
+    let r = client.try_unstake(&alice, &token, &500);
+    assert_eq!(r, Err(Ok(RevoraError::ClaimDelayNotElapsed)));
+
+    env.ledger().set_timestamp(1_000);
+    assert_eq!(client.request_unstake(&alice, &token, &500), 0);
+    let pending = client.get_pending_unstake(&token, &alice).unwrap();
+    assert_eq!(pending.amount, 500);
+    assert_eq!(pending.available_at, 4_600);
+
+    // Requested stake no longer accrues
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    assert_eq!(
+        client.try_claim_accrued(&alice, &token),
+        Err(Ok(RevoraError::NoPendingClaims))
+    );
+
+    let r = client.try_withdraw_unstaked(&alice, &token);
+    assert_eq!(r, Err(Ok(RevoraError::ClaimDelayNotElapsed)));
+    env.ledger().set_timestamp(4_600);
+    assert_eq!(client.withdraw_unstaked(&alice, &token), 500);
+    assert_eq!(balance(&env, &token, &alice), 1_000);
+    assert_eq!(client.get_pending_unstake(&token, &alice), None);
+}
+
+#[test]
+fn pending_rewards_previews_accrual_without_settling() {
+    let (_env, client, issuer, token, payment_token, alice, bob) = staking_setup();
+    assert_eq!(client.pending_rewards(&token, &alice), 0);
+    client.stake(&alice, &token, &300);
+    client.stake(&bob, &token, &100);
+    client.deposit_revenue(&issuer, &token, &payment_token, &4_000, &1);
+
+    assert_eq!(client.pending_rewards(&token, &alice), 3_000);
+    assert_eq!(client.pending_rewards(&token, &bob), 1_000);
+    // Preview does not touch the stored position
+    assert_eq!(client.get_accrual_position(&token, &alice).accrued, 0);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &4_000, &2);
+    assert_eq!(client.pending_rewards(&token, &alice), 6_000);
+    assert_eq!(client.claim_accrued(&alice, &token), 6_000);
+    assert_eq!(client.pending_rewards(&token, &alice), 0);
+}
+
+// ===========================================================================
+// testutils helpers
+// ===========================================================================
+
+#[test]
+fn testutils_offering_blacklist_and_revenue_helpers() {
+    use crate::testutils;
+
+    let (env, client) = testutils::setup();
+    let offering = testutils::register_test_offering(&env, &client, 2_500);
+    assert_eq!(
+        client
+            .get_offering(&offering.issuer, &offering.token)
+            .unwrap()
+            .revenue_share_bps,
+        2_500
+    );
+
+    let blocked = testutils::seed_blacklist(&env, &client, &offering, 3);
+    assert_eq!(client.get_blacklist(&offering.token).len(), 3);
+    assert!(client.is_blacklisted(&offering.token, &blocked.get(0).unwrap()));
+
+    let payment_token = testutils::create_payment_token(&env);
+    testutils::deposit_mock_revenue(&env, &client, &offering, &payment_token, 1_000, 1);
+    assert_eq!(client.get_period_count(&offering.token), 1);
+    assert_eq!(balance(&env, &payment_token, &client.address), 1_000);
+
+    testutils::fast_forward_periods(&env, 86_400, 3);
+    assert_eq!(env.ledger().timestamp(), 259_200);
+}
+
+#[test]
+fn mock_token_drives_deposit_and_claim_end_to_end() {
+    use crate::testutils;
+
+    let (env, client) = testutils::setup();
+    let offering = testutils::register_test_offering(&env, &client, 5_000);
+    let payment = testutils::create_mock_token(&env);
+    let holder = Address::generate(&env);
+    testutils::mint_mock_token(
+        &env,
+        &payment.address,
+        core::slice::from_ref(&offering.issuer),
+        10_000,
+    );
+    assert_eq!(payment.decimals(), 7);
+
+    client.set_holder_share(&offering.issuer, &offering.token, &holder, &2_500);
+    client.deposit_revenue(
+        &offering.issuer,
+        &offering.token,
+        &payment.address,
+        &4_000,
+        &1,
+    );
+    assert_eq!(payment.balance(&client.address), 4_000);
+
+    assert_eq!(client.claim(&holder, &offering.token, &0), 1_000);
+    assert_eq!(payment.balance(&holder), 1_000);
+    assert_eq!(payment.balance(&offering.issuer), 6_000);
+}
+
+// ===========================================================================
+// Smart-wallet authorization
+// ===========================================================================
+
+#[test]
+fn claim_and_stake_authorize_stable_argument_sets() {
+    use soroban_sdk::testutils::AuthorizedFunction;
+    use soroban_sdk::{IntoVal, Symbol};
+
+    let (env, client, issuer, token, payment_token, alice, _bob) = staking_setup();
+    client.stake(&alice, &token, &100);
+    let (signer, invocation) = env.auths()[0].clone();
+    assert_eq!(signer, alice);
+    assert_eq!(
+        invocation.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "stake"),
+            (token.clone(), 100i128).into_val(&env),
+        ))
+    );
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.claim_accrued(&alice, &token);
+    let (signer, invocation) = env.auths()[0].clone();
+    assert_eq!(signer, alice);
+    assert_eq!(
+        invocation.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "claim_accrued"),
+            (token,).into_val(&env),
+        ))
+    );
+}
+
+// ===========================================================================
+// Claim managers
+// ===========================================================================
+
+#[test]
+fn manager_claims_to_custodial_payout_address() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    let custodian = Address::generate(&env);
+    let vault = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &investor, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    let r = client.try_claim_for(&custodian, &investor, &token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.approve_manager(&investor, &token, &custodian);
+    assert_eq!(
+        client.get_manager(&token, &investor),
+        Some(custodian.clone())
+    );
+    client.set_payout_address(&custodian, &token, &investor, &vault);
+    assert_eq!(client.claim_for(&custodian, &investor, &token, &0), 500);
+    assert_eq!(balance(&env, &payment_token, &vault), 500);
+    assert_eq!(balance(&env, &payment_token, &investor), 0);
+}
+
+#[test]
+fn revoked_manager_loses_access() {
+    let (env, client, _issuer, token, _payment_token, _contract_id) = claim_setup();
+    let investor = Address::generate(&env);
+    let custodian = Address::generate(&env);
+    client.approve_manager(&investor, &token, &custodian);
+    client.revoke_manager(&investor, &token);
+    assert_eq!(client.get_manager(&token, &investor), None);
+
+    let r = client.try_set_payout_address(&custodian, &token, &investor, &custodian);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    assert_eq!(client.get_payout_address(&token, &investor), investor);
+}
+
+// ===========================================================================
+// Term amendments
+// ===========================================================================
+
+/// Offering with two holders: `a` (4000 bps) and `b` (2000 bps).
+fn amendment_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &4_000);
+    client.set_holder_share(&issuer, &token, &b, &2_000);
+    (env, client, issuer, token, payment_token, a, b)
+}
+
+#[test]
+fn approved_amendment_applies_and_keeps_past_terms() {
+    let (_env, client, issuer, token, payment_token, a, b) = amendment_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+
+    let id = client.propose_amendment(&issuer, &token, &3_000, &None);
+    // Not yet approved: terms unchanged
+    let r = client.try_execute_amendment(&token, &id);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+
+    client.vote_amendment(&a, &token, &id, &true);
+    client.vote_amendment(&b, &token, &id, &true);
+    let r = client.try_vote_amendment(&a, &token, &id, &true);
+    assert_eq!(r, Err(Ok(RevoraError::AlreadyVoted)));
+    assert_eq!(client.get_amendment(&token, &id).unwrap().votes_for, 6_000);
+
+    assert_eq!(
+        client.execute_amendment(&token, &id),
+        AmendmentStatus::Applied
+    );
+    assert_eq!(
+        client
+            .get_offering(&issuer, &token)
+            .unwrap()
+            .revenue_share_bps,
+        3_000
+    );
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+
+    assert_eq!(
+        client.get_terms_at(&token, &1),
+        Some(OfferingTerms {
+            revenue_share_bps: 5_000,
+            payment_token: Some(payment_token.clone())
+        })
+    );
+    assert_eq!(
+        client.get_terms_at(&token, &2).unwrap().revenue_share_bps,
+        3_000
+    );
+}
+
+#[test]
+fn amendment_without_majority_is_rejected_after_deadline() {
+    let (env, client, issuer, token, payment_token, a, b) = amendment_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let id = client.propose_amendment(&issuer, &token, &1_000, &None);
+    client.vote_amendment(&a, &token, &id, &true);
+    client.vote_amendment(&b, &token, &id, &false);
+
+    env.ledger()
+        .set_timestamp(client.get_amendment(&token, &id).unwrap().deadline);
+    let r = client.try_vote_amendment(&Address::generate(&env), &token, &id, &true);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+    assert_eq!(
+        client.execute_amendment(&token, &id),
+        AmendmentStatus::Rejected
+    );
+    assert_eq!(
+        client
+            .get_offering(&issuer, &token)
+            .unwrap()
+            .revenue_share_bps,
+        5_000
+    );
+}
+
+#[test]
+fn amendment_votes_use_shares_at_proposal_snapshot() {
+    let (env, client, issuer, token, payment_token, a, _b) = amendment_setup();
+    let r = client.try_propose_amendment(&issuer, &token, &1_000, &None);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let id = client.propose_amendment(&issuer, &token, &1_000, &None);
+
+    client.vote_amendment(&a, &token, &id, &true);
+    // a's share moves to c after a voted; c cannot vote it again
+    let c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &0);
+    client.set_holder_share(&issuer, &token, &c, &4_000);
+    let r = client.try_vote_amendment(&c, &token, &id, &true);
+    assert_eq!(r, Err(Ok(RevoraError::NotEligible)));
+    assert_eq!(client.get_amendment(&token, &id).unwrap().votes_for, 4_000);
+}
+
+#[test]
+fn currency_swap_requires_empty_escrow() {
+    let (env, client, issuer, token, payment_token, a, _b) = amendment_setup();
+    client.set_holder_share(&issuer, &token, &a, &6_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let (new_token, _admin) = create_payment_token(&env);
+    let id = client.propose_amendment(&issuer, &token, &5_000, &Some(new_token.clone()));
+    client.vote_amendment(&a, &token, &id, &true);
+
+    let r = client.try_execute_amendment(&token, &id);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    let r = client.try_execute_amendment(&token, &99);
+    assert_eq!(r, Err(Ok(RevoraError::ProposalNotFound)));
+}
+
+// ===========================================================================
+// Arbiter rulings
+// ===========================================================================
+
+#[test]
+fn arbiter_amends_disputed_report() {
+    let (env, client, issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    client.rule_on_report(&arbiter, &token, &1, &Ruling::Amend(3_000));
+
+    assert_eq!(
+        client.get_dispute(&token, &1).unwrap().status,
+        DisputeStatus::Upheld
+    );
+    let ruling = client.get_ruling(&token, &1).unwrap();
+    assert_eq!(ruling.ruling, Ruling::Amend(3_000));
+    assert_eq!(ruling.previous_amount, 5_000);
+    assert_eq!(
+        client
+            .get_audit_summary(&issuer, &token)
+            .unwrap()
+            .total_revenue,
+        3_000
+    );
+    let r = client.try_rule_on_report(&arbiter, &token, &1, &Ruling::Void);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidDisputeState)));
+}
+
+#[test]
+fn offering_arbiter_voids_report() {
+    let (env, client, issuer, token, _bond_token, _arbiter, _treasury) = bonded_setup();
+    let local = Address::generate(&env);
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    let r = client.try_rule_on_report(&local, &token, &1, &Ruling::Void);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.set_offering_arbiter(&token, &Some(local.clone()));
+    client.rule_on_report(&local, &token, &1, &Ruling::Void);
+    let summary = client.get_audit_summary(&issuer, &token).unwrap();
+    assert_eq!((summary.total_revenue, summary.report_count), (0, 0));
+    // The voided period can be reported again
+    client.report_revenue(&issuer, &token, &4_000, &1, &false);
+}
+
+#[test]
+fn uphold_ruling_rejects_dispute_and_keeps_report() {
+    let (env, client, issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    client.open_dispute(&Address::generate(&env), &token, &1, &evidence(&env));
+    client.rule_on_report(&arbiter, &token, &1, &Ruling::Uphold);
+    assert_eq!(
+        client.get_dispute(&token, &1).unwrap().status,
+        DisputeStatus::Rejected
+    );
+    assert_eq!(
+        client
+            .get_audit_summary(&issuer, &token)
+            .unwrap()
+            .total_revenue,
+        5_000
+    );
+}
+
+// ===========================================================================
+// Late-funding penalties
+// ===========================================================================
+
+#[test]
+fn late_deposit_pays_penalty_to_investors() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    client.set_penalty_terms(&token, &86_400, &10); // 0.1%/day after one day
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+
+    client.report_revenue(&issuer, &token, &100_000, &1, &false);
+    env.ledger().set_timestamp(86_400);
+    assert_eq!(client.get_penalty_owed(&token, &1), 0);
+
+    // Three full days past the window
+    env.ledger().set_timestamp(86_400 + 3 * 86_400 + 100);
+    assert_eq!(client.get_penalty_owed(&token, &1), 300);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    assert_eq!(client.get_escrow_balance(&token), 100_300);
+
+    // Penalty is fixed once funded
+    env.ledger().set_timestamp(30 * 86_400);
+    assert_eq!(client.get_penalty_owed(&token, &1), 300);
+    assert_eq!(client.claim(&holder, &token, &0), 100_300);
+    assert_eq!(
+        client.get_penalty_terms(&token),
+        Some(PenaltyTerms {
+            window_secs: 86_400,
+            bps_per_day: 10
+        })
+    );
+}
+
+// ===========================================================================
+// Period lifecycle
+// ===========================================================================
+
+#[test]
+fn period_moves_through_lifecycle_in_order() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let engine = Address::generate(&env);
+    client.add_engine(&engine);
+    assert_eq!(client.get_period_state(&token, &1), None);
+
+    client.report_revenue(&issuer, &token, &5_000, &1, &false);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Declared)
+    );
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &5_000, &1);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Deposited)
+    );
+    let r = client.try_mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+
+    client.finalize_report(&issuer, &token, &1);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Finalized)
+    );
+    let r = client.try_settle_period(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+
+    client.mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &1);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Distributed)
+    );
+    client.settle_period(&issuer, &token, &1);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Settled)
+    );
+}
+
+#[test]
+fn undeclared_deposit_enters_at_deposited() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &3);
+    assert_eq!(
+        client.get_period_state(&token, &3),
+        Some(PeriodState::Deposited)
+    );
+    // A later declaration does not move the period backwards
+    client.report_revenue(&issuer, &token, &1_000, &3, &false);
+    assert_eq!(
+        client.get_period_state(&token, &3),
+        Some(PeriodState::Deposited)
+    );
+
+    let r = client.try_mark_period_distributed(
+        &Address::generate(&env),
+        &token,
+        &3,
+        &zero_root(&env),
+        &0,
+        &1,
+    );
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+// ===========================================================================
+// Escrow timeout refunds
+// ===========================================================================
+
+#[test]
+fn refund_escrow_returns_unclaimed_after_timeout() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let late = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_holder_share(&issuer, &token, &late, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    assert_eq!(client.claim(&holder, &token, &0), 5_000);
+
+    let reason = soroban_sdk::symbol_short!("no_snap");
+    let r = client.try_refund_escrow(&issuer, &token, &1, &reason);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+
+    env.ledger().set_timestamp(180 * 86_400);
+    assert_eq!(client.refund_escrow(&issuer, &token, &1, &reason), 5_000);
+    assert_eq!(client.get_escrow_balance(&token), 0);
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Settled)
+    );
+    let refund = client.get_escrow_refund(&token, &1).unwrap();
+    assert_eq!((refund.amount, refund.reason), (5_000, reason.clone()));
+
+    // Remaining claims on the refunded period pay nothing
+    assert_eq!(client.claim(&late, &token, &0), 0);
+    let r = client.try_refund_escrow(&issuer, &token, &1, &reason);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+}
+
+#[test]
+fn refund_escrow_rejects_distributed_periods() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    client.finalize_report(&issuer, &token, &1);
+    client.set_admin(&Address::generate(&env));
+    let engine = Address::generate(&env);
+    client.add_engine(&engine);
+    client.mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &1);
+    env.ledger().set_timestamp(365 * 86_400);
+    let r = client.try_refund_escrow(&issuer, &token, &1, &soroban_sdk::symbol_short!("late"));
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+}
+
+#[test]
+fn refund_escrow_counts_claims_at_the_share_they_were_paid() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let late = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_holder_share(&issuer, &token, &late, &2_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    assert_eq!(client.claim(&holder, &token, &0), 5_000);
+
+    // A later share change does not alter what was already paid from period 1
+    client.set_holder_share(&issuer, &token, &holder, &1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &2);
+    env.ledger().set_timestamp(180 * 86_400);
+    let reason = soroban_sdk::symbol_short!("no_snap");
+    assert_eq!(client.refund_escrow(&issuer, &token, &1, &reason), 5_000);
+    assert_eq!(client.get_escrow_balance(&token), 10_000);
+    assert_eq!(client.claim(&holder, &token, &0), 1_000);
+}
+
+// ===========================================================================
+// Installment funding
+// ===========================================================================
+
+#[test]
+fn declared_period_is_funded_in_installments() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &4_000, &1);
+    // Claims wait for full funding, as does finalization
+    let r = client.try_claim(&holder, &token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::ClaimDelayNotElapsed)));
+    let r = client.try_finalize_report(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+    let r = client.try_deposit_revenue(&issuer, &token, &payment_token, &7_000, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &6_000, &1);
+    assert_eq!(
+        client.get_period_funding(&token, &1),
+        PeriodFunding {
+            declared: 10_000,
+            deposited: 10_000,
+            shortfall: 0
+        }
+    );
+    let r = client.try_deposit_revenue(&issuer, &token, &payment_token, &1, &1);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodAlreadyDeposited)));
+    assert_eq!(client.finalize_report(&issuer, &token, &1), 10_000);
+    assert_eq!(client.claim(&holder, &token, &0), 10_000);
+    assert_eq!(client.get_period_count(&token), 1);
+}
+
+#[test]
+fn short_finalization_records_shortfall_and_opens_claims() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+
+    assert_eq!(client.finalize_report_short(&issuer, &token, &1), 10_000);
+    assert_eq!(client.get_period_funding(&token, &1).shortfall, 2_000);
+    assert_eq!(client.claim(&holder, &token, &0), 4_000);
+    let r = client.try_deposit_revenue(&issuer, &token, &payment_token, &2_000, &1);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodAlreadyDeposited)));
+}
+
+// ===========================================================================
+// Funding sources
+// ===========================================================================
+
+#[test]
+fn funding_source_funds_issuer_declared_period() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let (payment_token, pt_admin) = create_payment_token(&env);
+    let treasury = Address::generate(&env);
+    mint_tokens(&env, &payment_token, &pt_admin, &treasury, &10_000);
+
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    let r = client.try_fund_period(&treasury, &token, &payment_token, &10_000, &1);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.set_funding_source(&issuer, &token, &Some(treasury.clone()));
+    assert_eq!(client.get_funding_source(&token), Some(treasury.clone()));
+    client.fund_period(&treasury, &token, &payment_token, &10_000, &1);
+    assert_eq!(client.get_escrow_balance(&token), 10_000);
+    assert_eq!(
+        token::Client::new(&env, &payment_token).balance(&treasury),
+        0
+    );
+    assert_eq!(
+        client.get_period_state(&token, &1),
+        Some(PeriodState::Deposited)
+    );
+
+    client.set_funding_source(&issuer, &token, &None);
+    assert_eq!(client.get_funding_source(&token), None);
+}
+
+// ===========================================================================
+// Distribution engines
+// ===========================================================================
+
+fn zero_root(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0; 32])
+}
+
+fn engine_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    let engine = Address::generate(&env);
+    client.add_engine(&engine);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    client.finalize_report(&issuer, &token, &1);
+    (env, client, issuer, token, engine)
+}
+
+#[test]
+fn engine_registry_gates_payout_recording() {
+    let (env, client, issuer, token, engine) = engine_setup();
+    assert!(client.is_engine(&engine));
+    let investor = Address::generate(&env);
+    let r = client.try_record_payout(&issuer, &token, &1, &investor, &1_000, &1);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.record_payout(&engine, &token, &1, &investor, &1_000, &1);
+    client.record_payout(&engine, &token, &1, &investor, &500, &2);
+    assert_eq!(client.get_recorded_payout(&token, &1, &investor), 1_500);
+
+    client.remove_engine(&engine);
+    assert!(!client.is_engine(&engine));
+    let r = client.try_mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &3);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+#[test]
+fn recorded_payouts_are_bounded_by_deposit() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let investors = Vec::from_array(&env, [a.clone(), b.clone()]);
+    client.record_payouts(
+        &engine,
+        &token,
+        &1,
+        &investors,
+        &Vec::from_array(&env, [6_000i128, 4_000]),
+        &1,
+    );
+    assert_eq!(client.get_recorded_payout_total(&token, &1), 10_000);
+    let r = client.try_record_payout(&engine, &token, &1, &a, &1, &2);
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+    let r = client.try_record_payouts(&engine, &token, &1, &investors, &Vec::new(&env), &2);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+
+    client.mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &2);
+    let r = client.try_record_payout(&engine, &token, &2, &b, &1, &3);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidPeriodState)));
+}
+
+// ===========================================================================
+// Engine liveness
+// ===========================================================================
+
+#[test]
+fn engine_acks_report_and_updates_last_seen() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    env.ledger().set_timestamp(500);
+    let r = client.try_ack_report(&engine, &token, &9);
+    assert_eq!(r, Err(Ok(RevoraError::ReportNotFound)));
+
+    client.ack_report(&engine, &token, &1);
+    assert_eq!(
+        client.get_report_ack(&token, &1),
+        Some(EngineAck {
+            engine: engine.clone(),
+            acked_at: 500
+        })
+    );
+    assert_eq!(client.get_engine_last_seen(&engine), Some(500));
+}
+
+#[test]
+fn stale_engine_can_be_flagged_and_removed() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    client.set_engine_stale_secs(&1_000);
+    env.ledger().set_timestamp(1_000);
+    assert!(!client.is_engine_stale(&engine));
+    let r = client.try_flag_stale_engine(&engine);
+    assert_eq!(r, Err(Ok(RevoraError::WindowClosed)));
+
+    // Activity resets the clock
+    client.ack_report(&engine, &token, &1);
+    env.ledger().set_timestamp(2_001);
+    assert!(client.is_engine_stale(&engine));
+    client.flag_stale_engine(&engine);
+    assert!(!client.is_engine(&engine));
+}
+
+// ===========================================================================
+// Distribution status
+// ===========================================================================
+
+#[test]
+fn claims_move_distribution_status_forward() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    assert_eq!(client.get_distribution_status(&token, &1), None);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    assert_eq!(
+        client.get_distribution_status(&token, &1),
+        Some(DistributionStatus::Pending)
+    );
+    client.claim(&holder, &token, &0);
+    assert_eq!(
+        client.get_distribution_status(&token, &1),
+        Some(DistributionStatus::InProgress)
+    );
+}
+
+#[test]
+fn engine_flow_reaches_verified() {
+    let (env, client, issuer, token, engine) = engine_setup();
+    client.record_payout(&engine, &token, &1, &Address::generate(&env), &1_000, &1);
+    assert_eq!(
+        client.get_distribution_status(&token, &1),
+        Some(DistributionStatus::InProgress)
+    );
+    client.mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &2);
+    assert_eq!(
+        client.get_distribution_status(&token, &1),
+        Some(DistributionStatus::Distributed)
+    );
+    client.settle_period(&issuer, &token, &1);
+    assert_eq!(
+        client.get_distribution_status(&token, &1),
+        Some(DistributionStatus::Verified)
+    );
+}
+
+// ===========================================================================
+// Payout roots
+// ===========================================================================
+
+#[test]
+fn distribution_commits_payout_root() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    env.ledger().set_timestamp(700);
+    let root = BytesN::from_array(&env, &[7; 32]);
+    let r = client.try_mark_period_distributed(&engine, &token, &1, &root, &10_001, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+
+    client.mark_period_distributed(&engine, &token, &1, &root, &9_500, &1);
+    assert_eq!(
+        client.get_payout_root(&token, &1),
+        Some(PayoutRoot {
+            engine: engine.clone(),
+            root,
+            total_paid: 9_500,
+            submitted_at: 700
+        })
+    );
+}
+
+fn payout_leaf(env: &Env, investor: &Address, amount: i128) -> BytesN<32> {
+    let mut data = investor.clone().to_xdr(env);
+    data.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+    env.crypto().sha256(&data).to_bytes()
+}
+
+#[test]
+fn verify_payout_checks_leaf_against_root() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let alice_leaf = payout_leaf(&env, &alice, 6_000);
+    let bob_leaf = payout_leaf(&env, &bob, 4_000);
+    let root = wl_pair(&env, &alice_leaf, &bob_leaf);
+    let proof = soroban_sdk::vec![&env, bob_leaf.clone()];
+    assert!(!client.verify_payout(&token, &1, &alice, &6_000, &proof));
+
+    client.mark_period_distributed(&engine, &token, &1, &root, &10_000, &1);
+    assert!(client.verify_payout(&token, &1, &alice, &6_000, &proof));
+    assert!(!client.verify_payout(&token, &1, &alice, &6_001, &proof));
+    let bob_proof = soroban_sdk::vec![&env, alice_leaf];
+    assert!(client.verify_payout(&token, &1, &bob, &4_000, &bob_proof));
+}
+
+// ===========================================================================
+// Payout challenges
+// ===========================================================================
+
+/// Bonded offering whose period 1 (5_000) was distributed under a root paying alice
+/// 2_000 of her 2_500 entitlement. Alice holds the 100 challenge bond.
+/// Returns (env, client, token, arbiter, treasury, alice, alice_proof).
+fn challenge_setup() -> (
+    Env,
+    RevoraRevenueShareClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Vec<BytesN<32>>,
+) {
+    let (env, client, issuer, token, bond_token, arbiter, treasury) = bonded_setup();
+    let (payment_token, pt_admin) = create_payment_token(&env);
+    mint_tokens(&env, &payment_token, &pt_admin, &issuer, &5_000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &alice, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &5_000, &1);
+    client.finalize_report(&issuer, &token, &1);
+
+    let engine = Address::generate(&env);
+    client.add_engine(&engine);
+    let alice_leaf = payout_leaf(&env, &alice, 2_000);
+    let bob_leaf = payout_leaf(&env, &bob, 2_500);
+    let root = wl_pair(&env, &alice_leaf, &bob_leaf);
+    client.mark_period_distributed(&engine, &token, &1, &root, &4_500, &1);
+
+    client.set_challenge_bond(&bond_token, &100);
+    token::StellarAssetClient::new(&env, &bond_token).mint(&alice, &100);
+    let proof = soroban_sdk::vec![&env, bob_leaf];
+    (env, client, token, arbiter, treasury, alice, proof)
+}
+
+#[test]
+fn sustained_payout_challenge_enables_bond_slash() {
+    let (env, client, token, arbiter, _treasury, alice, proof) = challenge_setup();
+    let bond_token = client.get_challenge_bond_config().unwrap().token;
+    let r = client.try_challenge_payout(&alice, &token, &1, &2_100, &proof);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidProof)));
+
+    client.challenge_payout(&alice, &token, &1, &2_000, &proof);
+    assert_eq!(balance(&env, &bond_token, &alice), 0);
+    let challenge = client.get_payout_challenge(&token, &1, &alice).unwrap();
+    assert_eq!((challenge.received, challenge.entitled), (2_000, 2_500));
+    let r = client.try_challenge_payout(&alice, &token, &1, &2_000, &proof);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidDisputeState)));
+
+    client.resolve_payout_challenge(&arbiter, &token, &1, &alice, &true);
+    assert_eq!(balance(&env, &bond_token, &alice), 100);
+    assert_eq!(
+        client.get_dispute(&token, &1).unwrap().status,
+        DisputeStatus::Upheld
+    );
+    assert_eq!(client.slash_bond(&token, &1), 1_000);
+}
+
+#[test]
+fn rejected_payout_challenge_forfeits_bond() {
+    let (env, client, token, arbiter, treasury, alice, proof) = challenge_setup();
+    let bond_token = client.get_challenge_bond_config().unwrap().token;
+    client.challenge_payout(&alice, &token, &1, &2_000, &proof);
+    let r = client.try_resolve_payout_challenge(&alice, &token, &1, &alice, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.resolve_payout_challenge(&arbiter, &token, &1, &alice, &false);
+    assert_eq!(balance(&env, &bond_token, &treasury), 100);
+    assert_eq!(
+        client
+            .get_payout_challenge(&token, &1, &alice)
+            .unwrap()
+            .status,
+        DisputeStatus::Rejected
+    );
+    assert_eq!(client.get_dispute(&token, &1), None);
+}
+
+// ===========================================================================
+// Complaints
+// ===========================================================================
+
+#[test]
+fn complaints_are_bounded_per_investor_and_period() {
+    let (env, client, _issuer, token, _bond_token, arbiter, _treasury) = bonded_setup();
+    let investor = Address::generate(&env);
+    for expected in 0..3u32 {
+        assert_eq!(
+            client.file_complaint(&investor, &token, &1, &evidence(&env)),
+            expected
+        );
+    }
+    let r = client.try_file_complaint(&investor, &token, &1, &evidence(&env));
+    assert_eq!(r, Err(Ok(RevoraError::LimitReached)));
+    // Other periods have their own budget
+    assert_eq!(
+        client.file_complaint(&investor, &token, &2, &evidence(&env)),
+        3
+    );
+
+    client.close_complaint(&arbiter, &token, &1);
+    assert_eq!(
+        client.get_open_complaints(&token),
+        soroban_sdk::vec![&env, 0u32, 2, 3]
+    );
+    let complaint = client.get_complaint(&token, &1).unwrap();
+    assert_eq!((complaint.investor, complaint.open), (investor, false));
+    let r = client.try_close_complaint(&arbiter, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidDisputeState)));
+}
+
+// ===========================================================================
+// Token rescue
+// ===========================================================================
+
+#[test]
+fn rescue_tokens_recovers_only_untracked_surplus() {
+    let (env, client, issuer, token, payment_token, contract_id) = claim_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    client.claim(&holder, &token, &0);
+    assert_eq!(client.get_held_balance(&payment_token), 5_000);
+
+    // Sent by mistake
+    token::StellarAssetClient::new(&env, &payment_token).mint(&contract_id, &700);
+    let to = Address::generate(&env);
+    let r = client.try_rescue_tokens(&admin, &payment_token, &701, &to);
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+    let r = client.try_rescue_tokens(&issuer, &payment_token, &700, &to);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    client.rescue_tokens(&admin, &payment_token, &700, &to);
+    assert_eq!(balance(&env, &payment_token, &to), 700);
+    assert_eq!(balance(&env, &payment_token, &contract_id), 5_000);
+}
+
+#[test]
+fn rescue_tokens_excludes_staked_balances() {
+    let (env, client, _issuer, token, _payment_token, alice, _bob) = staking_setup();
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.stake(&alice, &token, &600);
+    let to = Address::generate(&env);
+    let r = client.try_rescue_tokens(&admin, &token, &1, &to);
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+    client.unstake(&alice, &token, &600);
+    assert_eq!(client.get_held_balance(&token), 0);
+}
+
+// ===========================================================================
+// Native XLM
+// ===========================================================================
+
+fn native_token(env: &Env) -> Address {
+    let native = Bytes::from_array(env, &[0, 0, 0, 0]);
+    env.deployer().with_stellar_asset(native).deploy()
+}
+
+#[test]
+fn native_sac_has_stellar_metadata() {
+    let env = Env::default();
+    let xlm = token::Client::new(&env, &native_token(&env));
+    assert_eq!(xlm.decimals(), 7);
+    assert_eq!(xlm.symbol(), soroban_sdk::String::from_str(&env, "native"));
+}
+
+#[test]
+fn native_deposit_from_unfunded_issuer_leaves_no_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    let xlm = native_token(&env);
+    client.register_offering(&issuer, &token, &5_000);
+
+    // The SAC rejects the transfer, so the whole deposit rolls back
+    assert!(client
+        .try_deposit_revenue(&issuer, &token, &xlm, &1_000, &1)
+        .is_err());
+    assert_eq!(client.get_period_count(&token), 0);
+    assert_eq!(client.get_held_balance(&xlm), 0);
+    assert_eq!(balance(&env, &xlm, &contract_id), 0);
+}
+
+#[test]
+fn native_rescue_respects_zero_surplus() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let xlm = native_token(&env);
+    let r = client.try_rescue_tokens(&admin, &xlm, &1, &Address::generate(&env));
+    assert_eq!(r, Err(Ok(RevoraError::InsufficientFunds)));
+}
+
+// ===========================================================================
+// Offering timestamps
+// ===========================================================================
+
+#[test]
+fn offering_records_creation_and_update_times() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    env.ledger().set_sequence_number(42);
+    let issuer = Address::generate(&env);
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    let offering = client.get_offering(&issuer, &token).unwrap();
+    assert_eq!(offering.created_at, 0);
+    assert_eq!(offering.created_ledger, 42);
+    assert_eq!(offering.updated_at, 0);
+
+    // Suspension at t = 301 is a mutation
+    suspend_offering(&env, &client, &issuer, &token);
+    let offering = client.get_offering(&issuer, &token).unwrap();
+    assert_eq!(offering.created_at, 0);
+    assert_eq!(offering.updated_at, 301);
+}
+
+// ===========================================================================
+// Offering versioning
+// ===========================================================================
+
+#[test]
+fn new_offerings_store_current_version() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let offering = client.get_offering(&issuer, &token).unwrap();
+    assert_eq!(offering.version, OFFERING_VERSION);
+}
+
+#[test]
+fn legacy_offering_layouts_upgrade_on_read() {
+    let (env, client, issuer, token, _payment_token, contract_id) = claim_setup();
+    let v1_token = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let store = env.storage().persistent();
+        store.set(
+            &DataKey::OfferItem(issuer.clone(), 0),
+            &OfferingV0 {
+                issuer: issuer.clone(),
+                token: token.clone(),
+                revenue_share_bps: 5_000,
+            },
+        );
+        store.set(
+            &DataKey::OfferItem(issuer.clone(), 1),
+            &OfferingV1 {
+                issuer: issuer.clone(),
+                token: v1_token.clone(),
+                revenue_share_bps: 2_500,
+                created_at: 7,
+                created_ledger: 3,
+                updated_at: 9,
+            },
+        );
+        store.set(&DataKey::OfferCount(issuer.clone()), &2u32);
+    });
+
+    let offering = client.get_offering(&issuer, &token).unwrap();
+    assert_eq!(offering.version, OFFERING_VERSION);
+    assert_eq!(offering.revenue_share_bps, 5_000);
+    assert_eq!(offering.created_at, 0);
+    let offering = client.get_offering(&issuer, &v1_token).unwrap();
+    assert_eq!(offering.version, OFFERING_VERSION);
+    assert_eq!((offering.created_at, offering.updated_at), (7, 9));
+
+    // Upgraded records are written back in the current layout
+    env.as_contract(&contract_id, || {
+        let stored: crate::Offering = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OfferItem(issuer.clone(), 0))
+            .unwrap();
+        assert_eq!(stored.version, OFFERING_VERSION);
+    });
+}
+
+// ===========================================================================
+// Role enumeration
+// ===========================================================================
+
+#[test]
+fn role_members_are_enumerable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    client.set_admin(&Address::generate(&env));
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    for who in [&a, &b, &c] {
+        client.grant_role(&Role::Arbiter, who);
+    }
+    client.grant_role(&Role::Arbiter, &a);
+    client.grant_role(&Role::TransferAgent, &b);
+
+    let (page, next) = client.list_role_members(&Role::Arbiter, &0, &2);
+    assert_eq!(page, soroban_sdk::vec![&env, a.clone(), b.clone()]);
+    assert_eq!(next, Some(2));
+    let (page, next) = client.list_role_members(&Role::Arbiter, &2, &2);
+    assert_eq!(page, soroban_sdk::vec![&env, c.clone()]);
+    assert_eq!(next, None);
+    assert_eq!(
+        client.list_roles_of(&b),
+        soroban_sdk::vec![&env, Role::Arbiter, Role::TransferAgent]
+    );
+
+    client.revoke_role(&Role::Arbiter, &b);
+    let (page, _) = client.list_role_members(&Role::Arbiter, &0, &0);
+    assert_eq!(page, soroban_sdk::vec![&env, a, c]);
+    assert_eq!(
+        client.list_roles_of(&b),
+        soroban_sdk::vec![&env, Role::TransferAgent]
+    );
+}
+
+// ===========================================================================
+// Event catalog
+// ===========================================================================
+
+#[test]
+fn event_catalog_describes_config_events() {
+    let env = Env::default();
+    let client = make_client(&env);
+    let catalog = client.get_event_catalog();
+    let spec = catalog
+        .iter()
+        .find(|e| e.name == soroban_sdk::symbol_short!("conc_set"))
+        .unwrap();
+    assert_eq!(
+        spec.topics,
+        soroban_sdk::vec![
+            &env,
+            soroban_sdk::symbol_short!("issuer"),
+            soroban_sdk::symbol_short!("token"),
+            soroban_sdk::Symbol::new(&env, "offering_id"),
+        ]
+    );
+    assert_eq!(
+        spec.data,
+        soroban_sdk::vec![
+            &env,
+            soroban_sdk::symbol_short!("max_bps"),
+            soroban_sdk::symbol_short!("enforce"),
+        ]
+    );
+    let paused = catalog
+        .iter()
+        .find(|e| e.name == soroban_sdk::symbol_short!("paused"))
+        .unwrap();
+    assert!(paused.data.is_empty());
+}
+
+#[test]
+fn config_setters_emit_events() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let before = env.events().all().len();
+    client.set_concentration_limit(&issuer, &token, &2_000, &true);
+    client.set_rounding_mode(&issuer, &token, &RoundingMode::RoundHalfUp);
+    client.set_admin(&Address::generate(&env));
+    client.set_engine_stale_secs(&3_600);
+    assert_eq!(env.events().all().len(), before + 4);
+}
+
+// ===========================================================================
+// Investor cap
+// ===========================================================================
+
+#[test]
+fn investor_cap_limits_distinct_holders() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_investor_cap(&issuer, &token, &2);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &1_000);
+    client.set_holder_share(&issuer, &token, &b, &1_000);
+    // Updating an admitted holder does not count twice
+    client.set_holder_share(&issuer, &token, &a, &2_000);
+    assert_eq!(client.get_investor_count(&token), 2);
+
+    let r = client.try_set_holder_share(&issuer, &token, &c, &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvestorCapReached)));
+
+    // Zeroing a share frees the seat
+    client.set_holder_share(&issuer, &token, &b, &0);
+    client.set_holder_share(&issuer, &token, &c, &1_000);
+    assert_eq!(client.get_investor_count(&token), 2);
+}
+
+#[test]
+fn investor_cap_applies_to_whitelist_slots() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let (la, lb) = (wl_leaf(&env, &a), wl_leaf(&env, &b));
+    client.set_whitelist_root(&issuer, &token, &wl_pair(&env, &la, &lb));
+    client.set_investor_cap(&issuer, &token, &1);
+
+    client.whitelist_claim_slot(&token, &a, &soroban_sdk::vec![&env, lb.clone()]);
+    let r = client.try_whitelist_claim_slot(&token, &b, &soroban_sdk::vec![&env, la]);
+    assert_eq!(r, Err(Ok(RevoraError::InvestorCapReached)));
+    assert_eq!(client.get_investor_count(&token), 1);
+}
+
+// ===========================================================================
+// Reporter committees
+// ===========================================================================
+
+/// Helper: offering with a 2-of-3 reporter committee. Returns (issuer, token, reporters).
+fn committee_setup(
+    env: &Env,
+    client: &RevoraRevenueShareClient,
+) -> (Address, Address, [Address; 3]) {
+    let issuer = Address::generate(env);
+    let token = Address::generate(env);
+    client.register_offering(&issuer, &token, &5_000);
+    let reporters = [
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    let list = soroban_sdk::vec![
+        env,
+        reporters[0].clone(),
+        reporters[1].clone(),
+        reporters[2].clone()
+    ];
+    client.set_reporters(&issuer, &token, &list, &2);
+    (issuer, token, reporters)
+}
+
+#[test]
+fn committee_report_needs_threshold_of_matching_amounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, r) = committee_setup(&env, &client);
+
+    assert!(!client.submit_report(&r[0], &token, &1_000, &1, &false));
+    // A mismatching amount does not count towards the first one
+    assert!(!client.submit_report(&r[1], &token, &999, &1, &false));
+    assert!(client.get_period_report(&issuer, &token, &1).is_none());
+
+    assert!(client.submit_report(&r[2], &token, &1_000, &1, &false));
+    assert_eq!(client.get_period_report(&issuer, &token, &1), Some(1_000));
+    assert_eq!(client.get_reporter_vote(&token, &1, &r[0]), None);
+}
+
+#[test]
+fn committee_blocks_direct_and_outside_reports() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, _r) = committee_setup(&env, &client);
+
+    let r = client.try_report_revenue(&issuer, &token, &1_000, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    let outsider = Address::generate(&env);
+    let r = client.try_submit_report(&outsider, &token, &1_000, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    // Removing the committee restores direct reporting
+    client.set_reporters(&issuer, &token, &Vec::new(&env), &0);
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+}
+
+#[test]
+fn committee_blocks_structured_reports() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, _r) = committee_setup(&env, &client);
+
+    let r = client.try_report_structured_revenue(&issuer, &token, &1_000, &100, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    assert!(client.get_period_report(&issuer, &token, &1).is_none());
+}
+
+#[test]
+fn set_reporters_validates_threshold_and_duplicates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, r) = committee_setup(&env, &client);
+
+    let list = soroban_sdk::vec![&env, r[0].clone(), r[1].clone()];
+    let res = client.try_set_reporters(&issuer, &token, &list, &3);
+    assert_eq!(res, Err(Ok(RevoraError::InvalidReporterSet)));
+    let dup = soroban_sdk::vec![&env, r[0].clone(), r[0].clone()];
+    let res = client.try_set_reporters(&issuer, &token, &dup, &1);
+    assert_eq!(res, Err(Ok(RevoraError::InvalidReporterSet)));
+}
+
+#[test]
+fn reporter_rotation_hands_over_seat_at_effective_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, r) = committee_setup(&env, &client);
+    let fresh = Address::generate(&env);
+    client.schedule_reporter_rotation(&issuer, &token, &r[0], &fresh, &5);
+
+    // Before the effective period the old key still holds the seat
+    let res = client.try_submit_report(&fresh, &token, &1_000, &4, &false);
+    assert_eq!(res, Err(Ok(RevoraError::Unauthorized)));
+    client.submit_report(&r[0], &token, &1_000, &4, &false);
+    assert!(client.submit_report(&r[1], &token, &1_000, &4, &false));
+
+    // From it on, only the new key does
+    let res = client.try_submit_report(&r[0], &token, &2_000, &5, &false);
+    assert_eq!(res, Err(Ok(RevoraError::Unauthorized)));
+    client.submit_report(&fresh, &token, &2_000, &5, &false);
+    assert_eq!(client.get_reporter_vote(&token, &5, &r[0]), Some(2_000));
+    assert!(client.submit_report(&r[2], &token, &2_000, &5, &false));
+    assert_eq!(client.get_period_report(&issuer, &token, &5), Some(2_000));
+}
+
+#[test]
+fn reporter_rotation_must_target_a_future_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, r) = committee_setup(&env, &client);
+    client.submit_report(&r[0], &token, &1_000, &3, &false);
+    client.submit_report(&r[1], &token, &1_000, &3, &false);
+
+    let fresh = Address::generate(&env);
+    let res = client.try_schedule_reporter_rotation(&issuer, &token, &r[0], &fresh, &3);
+    assert_eq!(res, Err(Ok(RevoraError::WindowClosed)));
+    let res = client.try_schedule_reporter_rotation(&issuer, &token, &r[0], &r[1], &4);
+    assert_eq!(res, Err(Ok(RevoraError::InvalidReporterSet)));
+    client.schedule_reporter_rotation(&issuer, &token, &r[0], &fresh, &4);
+}
+
+// ===========================================================================
+// Protocol metrics
+// ===========================================================================
+
+#[test]
+fn protocol_metrics_track_reports_payouts_and_investors() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    // An override replaces, not adds to, the reported value
+    client.report_revenue(&issuer, &token, &12_000, &1, &true);
+    client.deposit_revenue(&issuer, &token, &payment_token, &12_000, &1);
+    client.claim(&holder, &token, &0);
+
+    let metrics = client.get_protocol_metrics();
+    assert_eq!(metrics.total_reported, 12_000);
+    assert_eq!(metrics.total_distributed, 6_000);
+    assert_eq!(metrics.active_offerings, 1);
+    assert_eq!(metrics.unique_investors, 1);
+}
+
+#[test]
+fn protocol_metrics_follow_offering_status() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    assert_eq!(client.get_protocol_metrics().active_offerings, 1);
+    suspend_offering(&env, &client, &issuer, &token);
+    assert_eq!(client.get_protocol_metrics().active_offerings, 0);
+}
+
+// ===========================================================================
+// Period summaries
+// ===========================================================================
+
+#[test]
+fn finalization_persists_period_summary() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.blacklist_add(&issuer, &token, &Address::generate(&env));
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    assert_eq!(client.get_period_summary(&token, &1), None);
+
+    env.ledger().set_timestamp(500);
+    client.finalize_report(&issuer, &token, &1);
+    let summary = client.get_period_summary(&token, &1).unwrap();
+    assert_eq!(summary.amount, 8_000);
+    assert_eq!(summary.blacklist_hash, client.get_blacklist_hash(&token));
+    assert_eq!(summary.holder_count, 1);
+    assert_eq!(summary.fee_taken, 0);
+    assert_eq!(summary.distributed, 0);
+    assert_eq!(summary.finalized_at, 500);
+}
+
+#[test]
+fn period_summary_reports_amount_claimed_and_withheld() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.set_withholding_rate(&issuer, &token, &0, &1_000);
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    client.finalize_report(&issuer, &token, &1);
+
+    assert_eq!(client.claim(&holder, &token, &0), 3_600);
+    let summary = client.get_period_summary(&token, &1).unwrap();
+    assert_eq!(summary.distributed, 4_000);
+    assert_eq!(summary.fee_taken, 400);
+}
+
+#[test]
+fn finalize_report_requires_offering_issuer() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &8_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+
+    let stranger = Address::generate(&env);
+    let r = client.try_finalize_report(&stranger, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+    let r = client.try_finalize_report_short(&stranger, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+}
+
+// ===========================================================================
+// State commitment
+// ===========================================================================
+
+fn expected_state_hash(
+    env: &Env,
+    bps: u32,
+    payment_token: Option<&Address>,
+    blacklist_hash: &BytesN<32>,
+    last_finalized: Option<u64>,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &bps.to_be_bytes());
+    match payment_token {
+        Some(pt) => {
+            preimage.push_back(1);
+            preimage.append(&pt.clone().to_xdr(env));
+        }
+        None => preimage.push_back(0),
+    }
+    preimage.append(&Bytes::from_array(env, &blacklist_hash.to_array()));
+    match last_finalized {
+        Some(p) => {
+            preimage.push_back(1);
+            preimage.append(&Bytes::from_array(env, &p.to_be_bytes()));
+        }
+        None => preimage.push_back(0),
+    }
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+#[test]
+fn state_hash_tracks_terms_blacklist_and_finalization() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let zero = BytesN::from_array(&env, &[0u8; 32]);
+    assert_eq!(
+        client.get_state_hash(&token),
+        Some(expected_state_hash(&env, 5_000, None, &zero, None))
+    );
+
+    client.blacklist_add(&issuer, &token, &Address::generate(&env));
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.finalize_report(&issuer, &token, &1);
+    let bl = client.get_blacklist_hash(&token);
+    assert_eq!(
+        client.get_state_hash(&token),
+        Some(expected_state_hash(
+            &env,
+            5_000,
+            Some(&payment_token),
+            &bl,
+            Some(1)
+        ))
+    );
+    assert_eq!(client.get_state_hash(&Address::generate(&env)), None);
+}
+
+#[test]
+fn finalization_emits_sequenced_checkpoints() {
+    use soroban_sdk::{FromVal, Symbol};
+
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    for period_id in 1..=2u64 {
+        client.report_revenue(&issuer, &token, &1_000, &period_id, &false);
+        client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &period_id);
+        client.finalize_report(&issuer, &token, &period_id);
+    }
+    assert_eq!(client.get_checkpoint_seq(), 2);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let symbol = Symbol::from_val(&env, &topics.get(0).unwrap());
+    assert_eq!(symbol, soroban_sdk::symbol_short!("checkpt"));
+    let (seq, offering_seq, period_id, state_hash) =
+        <(u64, u64, u64, Option<BytesN<32>>)>::from_val(&env, &data);
+    assert_eq!((seq, offering_seq, period_id), (2, 2, 2));
+    assert_eq!(state_hash, client.get_state_hash(&token));
+}
+
+// ===========================================================================
+// Bulk offering reads
+// ===========================================================================
+
+#[test]
+fn get_offerings_bulk_preserves_order_and_misses() {
+    let (env, client, issuer) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    client.register_offering(&issuer, &a, &1_000);
+    client.register_offering(&issuer, &b, &2_000);
+    let unknown = Address::generate(&env);
+
+    let entries = soroban_sdk::vec![
+        &env,
+        (issuer.clone(), b.clone()),
+        (issuer.clone(), unknown),
+        (issuer.clone(), a.clone()),
+    ];
+    let results = client.get_offerings_bulk(&entries);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.get(0).unwrap().unwrap().revenue_share_bps, 2_000);
+    assert_eq!(results.get(1).unwrap(), None);
+    assert_eq!(results.get(2).unwrap().unwrap().token, a);
+}
+
+// ===========================================================================
+// Batch execution
+// ===========================================================================
+
+#[test]
+fn batch_applies_all_operations() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let bad = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let ops = soroban_sdk::vec![
+        &env,
+        BatchOp::BlacklistAdd(token.clone(), bad.clone()),
+        BatchOp::SetKycStatus(token.clone(), holder.clone(), true),
+        BatchOp::SetHolderShare(token.clone(), holder.clone(), 2_500),
+        BatchOp::SetRoundingMode(token.clone(), RoundingMode::RoundHalfUp),
+    ];
+    assert_eq!(client.batch(&issuer, &ops), 4);
+    assert!(client.is_blacklisted(&token, &bad));
+    assert_eq!(client.get_holder_share(&token, &holder), 2_500);
+    assert_eq!(
+        client.get_rounding_mode(&issuer, &token),
+        RoundingMode::RoundHalfUp
+    );
+}
+
+#[test]
+fn batch_reverts_atomically_on_failure() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let bad = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let ops = soroban_sdk::vec![
+        &env,
+        BatchOp::BlacklistAdd(token.clone(), bad.clone()),
+        BatchOp::SetHolderShare(token.clone(), holder, 10_001),
+    ];
+    let r = client.try_batch(&issuer, &ops);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidShareBps)));
+    assert!(!client.is_blacklisted(&token, &bad));
+
+    let r = client.try_batch(&issuer, &Vec::new(&env));
+    assert_eq!(r, Err(Ok(RevoraError::InvalidChunk)));
+}
+
+// ===========================================================================
+// Factory mode
+// ===========================================================================
+
+#[test]
+fn child_wasm_hash_set_and_cleared_by_admin() {
+    let (env, client, _admin) = admin_setup();
+    assert_eq!(client.get_child_wasm_hash(), None);
+    assert_eq!(client.get_offering_child(&Address::generate(&env)), None);
+
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+    client.set_child_wasm_hash(&Some(hash.clone()));
+    assert_eq!(client.get_child_wasm_hash(), Some(hash));
+
+    client.set_child_wasm_hash(&None);
+    assert_eq!(client.get_child_wasm_hash(), None);
+}
+
+#[test]
+fn registration_without_factory_mode_deploys_no_child() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    client.register_offering(&issuer, &token, &1_000);
+    assert_eq!(client.get_offering_child(&token), None);
+}
+
+// ===========================================================================
+// Cross-chain reports
+// ===========================================================================
+
+fn bridge_report(env: &Env, id: u8, period_id: u64, amount: i128) -> BridgeReport {
+    BridgeReport {
+        source_chain: soroban_sdk::symbol_short!("ethereum"),
+        message_id: BytesN::from_array(env, &[id; 32]),
+        period_id,
+        amount,
+        override_existing: false,
+    }
+}
+
+#[test]
+fn bridge_report_is_recorded_once() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let bridge = Address::generate(&env);
+    client.set_bridge(&issuer, &token, &Some(bridge.clone()));
+    assert_eq!(client.get_bridge(&token), Some(bridge.clone()));
+
+    client.submit_bridge_report(&bridge, &token, &bridge_report(&env, 1, 1, 50_000));
+    assert_eq!(client.get_period_report(&issuer, &token, &1), Some(50_000));
+    assert_eq!(
+        client.get_bridge_message(&token, &BytesN::from_array(&env, &[1; 32])),
+        Some(1)
+    );
+
+    let r = client.try_submit_bridge_report(&bridge, &token, &bridge_report(&env, 1, 2, 10));
+    assert_eq!(r, Err(Ok(RevoraError::MessageReplayed)));
+}
+
+#[test]
+fn bridge_report_requires_registered_bridge() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let bridge = Address::generate(&env);
+    let r = client.try_submit_bridge_report(&bridge, &token, &bridge_report(&env, 1, 1, 100));
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    client.set_bridge(&issuer, &token, &Some(bridge.clone()));
+    client.set_bridge(&issuer, &token, &None);
+    let r = client.try_submit_bridge_report(&bridge, &token, &bridge_report(&env, 2, 1, 100));
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+#[test]
+fn bridge_report_rejected_while_committee_is_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = make_client(&env);
+    let (issuer, token, _r) = committee_setup(&env, &client);
+    client.set_bridge(&issuer, &token, &Some(issuer.clone()));
+
+    let r = client.try_submit_bridge_report(&issuer, &token, &bridge_report(&env, 1, 1, 100));
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    assert!(client.get_period_report(&issuer, &token, &1).is_none());
+    assert_eq!(
+        client.get_bridge_message(&token, &BytesN::from_array(&env, &[1; 32])),
+        None
+    );
+}
+
+// ===========================================================================
+// Event verbosity
+// ===========================================================================
+
+#[test]
+fn event_verbosity_resolves_override_then_default() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    assert_eq!(client.get_event_verbosity(&token), EventVerbosity::Full);
+
+    client.set_default_event_verbosity(&EventVerbosity::Compact);
+    assert_eq!(client.get_event_verbosity(&token), EventVerbosity::Compact);
+
+    client.set_event_verbosity(&issuer, &token, &Some(EventVerbosity::Full));
+    assert_eq!(client.get_event_verbosity(&token), EventVerbosity::Full);
+
+    client.set_event_verbosity(&issuer, &token, &None);
+    assert_eq!(client.get_event_verbosity(&token), EventVerbosity::Compact);
+
+    let other = Address::generate(&env);
+    let r = client.try_set_event_verbosity(&issuer, &other, &None);
+    assert_eq!(r, Err(Ok(RevoraError::OfferingNotFound)));
+}
+
+#[test]
+fn compact_report_events_carry_blacklist_hash() {
+    use soroban_sdk::{FromVal, Val};
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.blacklist_add(&issuer, &token, &Address::generate(&env));
+    client.set_event_verbosity(&issuer, &token, &Some(EventVerbosity::Compact));
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (amount, period_id, blacklist) = <(i128, u64, Val)>::from_val(&env, &data);
+    assert_eq!((amount, period_id), (1_000, 1));
+    assert_eq!(
+        BytesN::<32>::from_val(&env, &blacklist),
+        client.get_blacklist_hash(&token)
+    );
+}
+
+// ===========================================================================
+// Monotonic periods
+// ===========================================================================
+
+#[test]
+fn reports_below_highest_finalized_period_are_rejected() {
+    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &1_000, &3, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &3);
+    client.finalize_report(&issuer, &token, &3);
+    assert_eq!(client.get_highest_finalized_period(&token), Some(3));
+
+    let r = client.try_report_revenue(&issuer, &token, &500, &2, &false);
+    assert_eq!(r, Err(Ok(RevoraError::StalePeriod)));
+    let r = client.try_report_partial_revenue(&issuer, &token, &500, &1);
+    assert_eq!(r, Err(Ok(RevoraError::StalePeriod)));
+    let r = client.try_report_revenue(&issuer, &token, &500, &3, &true);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFinalized)));
+
+    client.report_revenue(&issuer, &token, &2_000, &4, &false);
+    assert_eq!(client.get_period_report(&issuer, &token, &4), Some(2_000));
+}
+
+// ===========================================================================
+// Zero-amount reports
+// ===========================================================================
+
+#[test]
+fn forbidden_policy_rejects_zero_and_negative_reports() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    assert_eq!(
+        client.get_zero_report_policy(&token),
+        ZeroReportPolicy::Unrestricted
+    );
+    client.set_zero_report_policy(&issuer, &token, &ZeroReportPolicy::Forbidden);
+
+    let r = client.try_report_revenue(&issuer, &token, &0, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+    let r = client.try_report_revenue(&issuer, &token, &-5, &1, &false);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+    client.report_revenue(&issuer, &token, &5, &1, &false);
+}
+
+#[test]
+fn required_policy_accepts_zero_and_forbids_gaps() {
+    let (_env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    client.set_zero_report_policy(&issuer, &token, &ZeroReportPolicy::Required);
+
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    client.report_revenue(&issuer, &token, &0, &2, &false);
+    assert_eq!(client.get_period_report(&issuer, &token, &2), Some(0));
+
+    let r = client.try_report_revenue(&issuer, &token, &500, &4, &false);
+    assert_eq!(r, Err(Ok(RevoraError::MissedReportsOutstanding)));
+    let r = client.try_report_revenue(&issuer, &token, &-1, &3, &false);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidAmount)));
+    client.report_revenue(&issuer, &token, &500, &3, &false);
+}
+
+// ===========================================================================
+// Offering series
+// ===========================================================================
+
+#[test]
+fn close_offering_requires_settled_escrow() {
+    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.report_revenue(&issuer, &token, &1_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let r = client.try_close_offering(&issuer, &token);
+    assert_eq!(r, Err(Ok(RevoraError::EscrowOutstanding)));
+}
+
+#[test]
+fn closed_offering_is_reregistered_as_new_series() {
+    let (env, client, issuer) = setup();
+    let token = Address::generate(&env);
+    env.ledger().set_timestamp(100);
+    client.register_offering(&issuer, &token, &1_000);
+    client.report_revenue(&issuer, &token, &5_000, &1, &false);
+    let first_id = client.get_offering_id(&token);
+
+    client.close_offering(&issuer, &token);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Closed);
+    let r = client.try_report_revenue(&issuer, &token, &1, &2, &false);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+    let r = client.try_register_offering(&Address::generate(&env), &token, &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+
+    env.ledger().set_timestamp(900);
+    client.register_offering(&issuer, &token, &2_500);
+    assert_eq!(client.get_offering_count(&issuer), 1);
+    assert_eq!(client.get_offering_status(&token), OfferingStatus::Active);
+    assert_ne!(client.get_offering_id(&token), first_id);
+    let current = client.get_offering(&issuer, &token).unwrap();
+    assert_eq!(
+        (current.revenue_share_bps, current.created_at),
+        (2_500, 900)
+    );
+
+    assert_eq!(client.get_series_count(&token), 1);
+    let archived = client.get_archived_series(&token, &0).unwrap();
+    assert_eq!(Some(archived.offering_id), first_id);
+    assert_eq!(archived.offering.revenue_share_bps, 1_000);
+    assert_eq!(client.get_period_report(&issuer, &token, &1), Some(5_000));
+}
+
+#[test]
+fn archived_series_record_their_periods_and_follow_token_migration() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &2);
+    client.claim(&holder, &token, &0);
+    client.close_offering(&issuer, &token);
+    client.register_offering(&issuer, &token, &5_000);
+
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &3);
+    client.claim(&holder, &token, &0);
+    client.close_offering(&issuer, &token);
+    client.register_offering(&issuer, &token, &5_000);
+
+    let first = client.get_archived_series(&token, &0).unwrap();
+    let second = client.get_archived_series(&token, &1).unwrap();
+    assert_eq!((first.start_index, first.end_index), (0, 2));
+    assert_eq!((second.start_index, second.end_index), (2, 3));
+
+    let new_token = Address::generate(&env);
+    let eta = client.propose_token_migration(&issuer, &token, &new_token);
+    env.ledger().set_timestamp(eta);
+    client.migrate_token(&issuer, &token, &new_token);
+    assert_eq!(client.get_series_count(&new_token), 2);
+    assert_eq!(client.get_archived_series(&new_token, &1), Some(second));
+    assert_eq!(client.get_archived_series(&token, &0), None);
+}
+
+// ===========================================================================
+// Token migration
+// ===========================================================================
+
+#[test]
+fn token_migration_moves_offering_after_timelock() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let bad = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.blacklist_add(&issuer, &token, &bad);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    let offering_id = client.get_offering_id(&token);
+
+    let new_token = Address::generate(&env);
+    let eta = client.propose_token_migration(&issuer, &token, &new_token);
+    let r = client.try_migrate_token(&issuer, &token, &new_token);
+    assert_eq!(r, Err(Ok(RevoraError::TimelockNotElapsed)));
+
+    env.ledger().set_timestamp(eta);
+    client.migrate_token(&issuer, &token, &new_token);
+    assert_eq!(client.get_migrated_token(&token), Some(new_token.clone()));
+    assert_eq!(client.get_pending_token_migration(&token), None);
+    assert!(client.get_offering(&issuer, &token).is_none());
+    assert!(client.get_offering(&issuer, &new_token).is_some());
+    assert_eq!(client.get_offering_id(&new_token), offering_id);
+    assert!(client.is_blacklisted(&new_token, &bad));
+    assert_eq!(client.get_escrow_balance(&new_token), 10_000);
+    assert_eq!(
+        client.get_period_report(&issuer, &new_token, &1),
+        Some(10_000)
+    );
+    assert_eq!(client.get_holder_share(&new_token, &holder), 0);
+
+    let holders = soroban_sdk::vec![&env, holder.clone(), Address::generate(&env)];
+    assert_eq!(client.migrate_token_holders(&issuer, &token, &holders), 1);
+    assert_eq!(client.get_holder_share(&new_token, &holder), 5_000);
+    assert_eq!(client.claim(&holder, &new_token, &0), 5_000);
+
+    let r = client.try_register_offering(&issuer, &token, &1_000);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+#[test]
+fn token_migration_can_be_cancelled_and_rejects_registered_target() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let taken = Address::generate(&env);
+    client.register_offering(&issuer, &taken, &1_000);
+    let r = client.try_propose_token_migration(&issuer, &token, &taken);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidMigration)));
+
+    let new_token = Address::generate(&env);
+    client.propose_token_migration(&issuer, &token, &new_token);
+    client.cancel_token_migration(&issuer, &token);
+    env.ledger().set_timestamp(30 * 24 * 60 * 60);
+    let r = client.try_migrate_token(&issuer, &token, &new_token);
+    assert_eq!(r, Err(Ok(RevoraError::ProposalNotFound)));
+}
+
+#[test]
+fn token_migration_carries_withholding_jurisdictions_and_amendments() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let blocked = Address::generate(&env);
+    let tax_office = Address::generate(&env);
+    client.set_withholding_rate(&issuer, &token, &7, &2_000);
+    client.set_investor_jurisdiction(&issuer, &token, &holder, &7);
+    client.set_withholding_recipient(&token, &7, &tax_office);
+    client.set_investor_jurisdiction(&issuer, &token, &blocked, &9);
+    client.set_jurisdiction_blocked(&issuer, &token, &9, &true);
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &100_000, &1);
+    client.claim(&holder, &token, &0);
+    assert_eq!(client.get_withheld_balance(&token, &7), 20_000);
+    let amendment_id = client.propose_amendment(&issuer, &token, &2_000, &None);
+    client.vote_amendment(&holder, &token, &amendment_id, &true);
+
+    let new_token = Address::generate(&env);
+    let eta = client.propose_token_migration(&issuer, &token, &new_token);
+    env.ledger().set_timestamp(eta);
+    client.migrate_token(&issuer, &token, &new_token);
+    let holders = soroban_sdk::vec![&env, holder.clone(), blocked.clone()];
+    client.migrate_token_holders(&issuer, &token, &holders);
+
+    let r = client.try_remit_withholding(&token, &7);
+    assert_eq!(r, Err(Ok(RevoraError::RecipientNotSet)));
+    assert_eq!(client.remit_withholding(&new_token, &7), 20_000);
+    assert_eq!(balance(&env, &payment_token, &tax_office), 20_000);
+    assert_eq!(client.get_remitted_withholding(&new_token, &7), 20_000);
+    assert_eq!(client.get_withholding_rate(&new_token, &7), 2_000);
+    assert_eq!(
+        client.is_eligible(&new_token, &blocked),
+        EligibilityResult::JurisdictionBlocked
+    );
+    let amendment = client.get_amendment(&new_token, &amendment_id).unwrap();
+    assert_eq!(amendment.status, AmendmentStatus::Pending);
+    assert_eq!(amendment.votes_for, 10_000);
+    env.as_contract(&client.address, || {
+        let vote = crate::GovKey::AmendmentVote(new_token.clone(), amendment_id, holder.clone());
+        assert!(env.storage().persistent().has(&vote));
+    });
+}
+
+// ===========================================================================
+// Offering merges
+// ===========================================================================
+
+#[test]
+fn merge_combines_shares_and_blacklists_and_closes_source() {
+    let (env, client, issuer, target, _payment_token, _contract_id) = claim_setup();
+    let source = Address::generate(&env);
+    client.register_offering(&issuer, &source, &5_000);
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    let (bad_1, bad_2) = (Address::generate(&env), Address::generate(&env));
+    client.set_holder_share(&issuer, &source, &a, &3_000);
+    client.set_holder_share(&issuer, &source, &b, &2_000);
+    client.set_holder_share(&issuer, &target, &a, &1_000);
+    client.blacklist_add(&issuer, &source, &bad_1);
+    client.blacklist_add(&issuer, &source, &bad_2);
+    client.blacklist_add(&issuer, &target, &bad_1);
+
+    let holders = soroban_sdk::vec![&env, a.clone(), b.clone()];
+    assert_eq!(
+        client.merge_offerings(&issuer, &source, &target, &holders),
+        2
+    );
+    assert_eq!(client.get_holder_share(&target, &a), 4_000);
+    assert_eq!(client.get_holder_share(&target, &b), 2_000);
+    assert_eq!(client.get_holder_share(&source, &a), 0);
+    assert!(client.is_blacklisted(&target, &bad_2));
+    assert_eq!(client.get_blacklist(&target).len(), 2);
+    assert_eq!(client.get_offering_status(&source), OfferingStatus::Closed);
+    assert_eq!(client.get_merged_into(&source), Some(target));
+}
+
+#[test]
+fn merge_requires_settled_source_and_valid_shares() {
+    let (env, client, issuer, target, payment_token, _contract_id) = claim_setup();
+    let source = Address::generate(&env);
+    client.register_offering(&issuer, &source, &5_000);
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &source, &holder, &6_000);
+    client.set_holder_share(&issuer, &target, &holder, &6_000);
+    let holders = soroban_sdk::vec![&env, holder.clone()];
+
+    let r = client.try_merge_offerings(&issuer, &source, &target, &holders);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidShareBps)));
+    assert_eq!(client.get_holder_share(&target, &holder), 6_000);
+
+    client.report_revenue(&issuer, &source, &1_000, &1, &false);
+    client.deposit_revenue(&issuer, &source, &payment_token, &1_000, &1);
+    let r = client.try_merge_offerings(&issuer, &source, &target, &holders);
+    assert_eq!(r, Err(Ok(RevoraError::EscrowOutstanding)));
+
+    let r = client.try_merge_offerings(&issuer, &target, &target, &holders);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+#[test]
+fn merge_requires_every_holder_and_new_holders_start_at_next_period() {
+    let (env, client, issuer, target, payment_token, _contract_id) = claim_setup();
+    let source = Address::generate(&env);
+    client.register_offering(&issuer, &source, &5_000);
+    let (a, b, c) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    client.set_holder_share(&issuer, &source, &a, &3_000);
+    client.set_holder_share(&issuer, &source, &b, &2_000);
+    client.set_holder_share(&issuer, &target, &c, &5_000);
+    client.deposit_revenue(&issuer, &target, &payment_token, &10_000, &1);
+
+    let r = client.try_merge_offerings(
+        &issuer,
+        &source,
+        &target,
+        &soroban_sdk::vec![&env, a.clone()],
+    );
+    assert_eq!(r, Err(Ok(RevoraError::HoldersIncomplete)));
+    assert_eq!(client.get_holder_share(&source, &a), 3_000);
+
+    let holders = soroban_sdk::vec![&env, a.clone(), b.clone()];
+    client.merge_offerings(&issuer, &source, &target, &holders);
+    // a joined after period 1 was deposited and takes nothing from it
+    assert_eq!(client.get_claimable(&target, &a), 0);
+    assert_eq!(client.claim(&c, &target, &0), 5_000);
+    client.deposit_revenue(&issuer, &target, &payment_token, &10_000, &2);
+    assert_eq!(client.get_claimable(&target, &a), 3_000);
+}
+
+// ===========================================================================
+// Offering splits
+// ===========================================================================
+
+#[test]
+fn split_apportions_unclaimed_escrow_and_copies_lists() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    let bad = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &6_000);
+    client.set_holder_share(&issuer, &token, &b, &4_000);
+    client.blacklist_add(&issuer, &token, &bad);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    client.claim(&a, &token, &0);
+    client.deposit_revenue(&issuer, &token, &payment_token, &20_000, &2);
+
+    let new_token = Address::generate(&env);
+    let holders = soroban_sdk::vec![&env, a.clone(), b.clone()];
+    // Period 1 is partially claimed and stays; 25% of period 2 moves.
+    assert_eq!(
+        client.split_offering(&issuer, &token, &new_token, &2_500, &holders),
+        5_000
+    );
+    assert_eq!(client.get_split_origin(&new_token), Some(token.clone()));
+    assert_eq!(
+        client
+            .get_offering(&issuer, &new_token)
+            .unwrap()
+            .revenue_share_bps,
+        5_000
+    );
+    assert!(client.is_blacklisted(&new_token, &bad));
+    assert_eq!(
+        client.get_blacklist_hash(&new_token),
+        client.get_blacklist_hash(&token)
+    );
+    assert_eq!(client.get_holder_share(&new_token, &a), 6_000);
+    assert_eq!(client.get_escrow_balance(&token), 4_000 + 15_000);
+    assert_eq!(client.get_escrow_balance(&new_token), 5_000);
+
+    assert_eq!(client.claim(&a, &new_token, &0), 3_000);
+    assert_eq!(client.claim(&b, &token, &0), 4_000 + 6_000);
+}
+
+#[test]
+fn split_copies_jurisdiction_blocks_and_withholding() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let (a, blocked) = (Address::generate(&env), Address::generate(&env));
+    let tax_office = Address::generate(&env);
+    client.set_withholding_rate(&issuer, &token, &0, &500);
+    client.set_withholding_rate(&issuer, &token, &7, &2_000);
+    client.set_withholding_recipient(&token, &7, &tax_office);
+    client.set_investor_jurisdiction(&issuer, &token, &a, &7);
+    client.set_investor_jurisdiction(&issuer, &token, &blocked, &9);
+    client.set_jurisdiction_blocked(&issuer, &token, &9, &true);
+    client.set_holder_share(&issuer, &token, &a, &10_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+
+    let new_token = Address::generate(&env);
+    let holders = soroban_sdk::vec![&env, a.clone(), blocked.clone()];
+    client.split_offering(&issuer, &token, &new_token, &5_000, &holders);
+    assert_eq!(client.get_withholding_rate(&new_token, &0), 500);
+    assert_eq!(client.get_withholding_rate(&new_token, &7), 2_000);
+    assert_eq!(
+        client.get_withholding_recipient(&new_token, &7),
+        Some(tax_office)
+    );
+    assert_eq!(
+        client.is_eligible(&new_token, &blocked),
+        EligibilityResult::JurisdictionBlocked
+    );
+
+    assert_eq!(client.claim(&a, &new_token, &0), 4_000);
+    assert_eq!(client.remit_withholding(&new_token, &7), 1_000);
+}
+
+#[test]
+fn split_rejects_invalid_ratio_and_registered_target() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let new_token = Address::generate(&env);
+    let holders = Vec::new(&env);
+    let r = client.try_split_offering(&issuer, &token, &new_token, &10_000, &holders);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidShareBps)));
+
+    client.register_offering(&issuer, &new_token, &1_000);
+    let r = client.try_split_offering(&issuer, &token, &new_token, &5_000, &holders);
+    assert_eq!(r, Err(Ok(RevoraError::InvalidOfferingStatus)));
+}
+
+// ===========================================================================
+// Period freezes
+// ===========================================================================
+
+#[test]
+fn frozen_period_blocks_claims_and_refunds_until_unfrozen() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    client.set_auditor(&issuer, &token, &Some(auditor.clone()), &false);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_000, &2);
+
+    client.freeze_period(&auditor, &token, &1);
+    assert!(client.is_period_frozen(&token, &1));
+    assert_eq!(client.get_period_freeze(&token, &1), Some(auditor.clone()));
+    let r = client.try_claim(&holder, &token, &0);
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFrozen)));
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    env.ledger().set_timestamp(400 * 24 * 60 * 60);
+    let r = client.try_refund_escrow(&issuer, &token, &1, &soroban_sdk::symbol_short!("audit"));
+    assert_eq!(r, Err(Ok(RevoraError::PeriodFrozen)));
+
+    client.unfreeze_period(&auditor, &token, &1);
+    assert_eq!(client.get_claimable(&token, &holder), 1_500);
+    assert_eq!(client.claim(&holder, &token, &0), 1_500);
+}
+
+#[test]
+fn freeze_period_requires_auditor_or_arbiter() {
+    let (_env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.deposit_revenue(&issuer, &token, &payment_token, &1_000, &1);
+    let r = client.try_freeze_period(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+    let r = client.try_unfreeze_period(&issuer, &token, &1);
+    assert_eq!(r, Err(Ok(RevoraError::Unauthorized)));
+}
+
+#[test]
+fn engine_calls_reject_replayed_sequence_numbers() {
+    let (env, client, _issuer, token, engine) = engine_setup();
+    let investor = Address::generate(&env);
+    assert_eq!(client.get_engine_seq(&engine), 0);
+    let r = client.try_record_payout(&engine, &token, &1, &investor, &1_000, &0);
+    assert_eq!(r, Err(Ok(RevoraError::StaleSequence)));
+
+    client.record_payout(&engine, &token, &1, &investor, &1_000, &5);
+    assert_eq!(client.get_engine_seq(&engine), 5);
+    let r = client.try_record_payout(&engine, &token, &1, &investor, &1_000, &5);
+    assert_eq!(r, Err(Ok(RevoraError::StaleSequence)));
+    assert_eq!(client.get_recorded_payout(&token, &1, &investor), 1_000);
+
+    let r = client.try_mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &4);
+    assert_eq!(r, Err(Ok(RevoraError::StaleSequence)));
+    client.mark_period_distributed(&engine, &token, &1, &zero_root(&env), &0, &6);
+}
+
+#[test]
+fn export_eligibility_pages_roster_with_reasons() {
+    let (env, client, issuer, token, _payment_token, _contract_id) = claim_setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &a, &1_000);
+    client.set_holder_share(&issuer, &token, &b, &1_000);
+    client.set_holder_share(&issuer, &token, &c, &1_000);
+    client.set_investor_frozen(&issuer, &token, &b, &true);
+    client.blacklist_add(&issuer, &token, &c);
+    // Re-setting a share does not re-enroll.
+    client.set_holder_share(&issuer, &token, &a, &2_000);
+    assert_eq!(client.get_roster_len(&token), 3);
+
+    let (page, next) = client.export_eligibility(&token, &0, &2);
+    assert_eq!(next, Some(2));
+    assert_eq!(
+        page.get(0).unwrap(),
+        EligibilityRow {
+            investor: a.clone(),
+            eligible: true,
+            reason: EligibilityResult::Eligible,
+        }
+    );
+    assert_eq!(page.get(1).unwrap().investor, b);
+    assert!(!page.get(1).unwrap().eligible);
+    assert_eq!(
+        page.get(1).unwrap().reason,
+        EligibilityResult::InvestorFrozen
+    );
+
+    let (page, next) = client.export_eligibility(&token, &2, &0);
+    assert_eq!(next, None);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().reason, EligibilityResult::Blacklisted);
+
+    // Released investors keep their roster position.
+    client.set_holder_share(&issuer, &token, &a, &0);
+    let (page, _) = client.export_eligibility(&token, &0, &0);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().investor, a);
+    assert_eq!(client.export_eligibility(&token, &3, &0).0.len(), 0);
+}
+
+#[test]
+fn release_schedule_unlocks_period_payout_in_tranches() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    let month = 30 * 24 * 60 * 60;
+    let tranche = |delay_secs: u64, bps: u32| ReleaseTranche { delay_secs, bps };
+
+    // Tranches must sum to 10000 bps with strictly increasing delays.
+    let bad = soroban_sdk::vec![&env, tranche(0, 5_000), tranche(month, 4_000)];
+    assert_eq!(
+        client.try_set_release_schedule(&issuer, &token, &1, &bad),
+        Err(Ok(RevoraError::InvalidSchedule))
+    );
+    let bad = soroban_sdk::vec![&env, tranche(month, 5_000), tranche(month, 5_000)];
+    assert_eq!(
+        client.try_set_release_schedule(&issuer, &token, &1, &bad),
+        Err(Ok(RevoraError::InvalidSchedule))
+    );
+
+    let schedule = soroban_sdk::vec![&env, tranche(0, 5_000), tranche(month, 5_000)];
+    client.set_release_schedule(&issuer, &token, &1, &schedule);
+    assert_eq!(client.get_release_schedule(&token, &1), schedule);
+
+    env.ledger().set_timestamp(1_000);
+    client.deposit_revenue(&issuer, &token, &payment_token, &10_000, &1);
+    client.deposit_revenue(&issuer, &token, &payment_token, &2_000, &2);
+
+    // Half of period 1 is unlocked; period 2 waits behind it.
+    assert_eq!(client.get_claimable(&token, &holder), 2_500);
+    assert_eq!(client.claim(&holder, &token, &0), 2_500);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 2);
+    assert_eq!(client.get_claimable(&token, &holder), 0);
+    assert_eq!(
+        client.try_claim(&holder, &token, &0),
+        Err(Ok(RevoraError::ClaimDelayNotElapsed))
+    );
+    assert_eq!(
+        client.try_set_release_schedule(&issuer, &token, &1, &soroban_sdk::vec![&env]),
+        Err(Ok(RevoraError::InvalidPeriodState))
+    );
+
+    env.ledger().set_timestamp(1_000 + month);
+    assert_eq!(client.get_claimable(&token, &holder), 3_500);
+    assert_eq!(client.claim(&holder, &token, &0), 3_500);
+    assert_eq!(balance(&env, &payment_token, &holder), 6_000);
+    assert_eq!(client.get_pending_periods(&token, &holder).len(), 0);
+}
+
+#[test]
+fn shortfall_accrues_interest_until_funded() {
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    client.set_admin(&Address::generate(&env));
+    client.set_shortfall_interest(&token, &10); // 0.1%/day
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    let day = 86_400;
+
+    env.ledger().set_timestamp(1_000);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    client.finalize_report_short(&issuer, &token, &1);
+    assert_eq!(client.claim(&holder, &token, &0), 4_000);
+
+    env.ledger().set_timestamp(1_000 + 3 * day + 100);
+    assert_eq!(client.get_shortfall_interest_owed(&token, &1), 6);
+    assert_eq!(
+        client.try_fund_shortfall(&issuer, &token, &1, &3_000),
+        Err(Ok(RevoraError::InvalidAmount))
+    );
+    assert_eq!(client.fund_shortfall(&issuer, &token, &1, &1_000), 6);
+    assert_eq!(client.get_period_funding(&token, &1).shortfall, 1_000);
+    assert_eq!(client.get_shortfall_interest_owed(&token, &1), 0);
+
+    // The holder collects their part of the principal and interest added since.
+    assert_eq!(client.claim_topup(&holder, &token, &1), 503);
+    assert_eq!(
+        client.try_claim_topup(&holder, &token, &1),
+        Err(Ok(RevoraError::NoPendingClaims))
+    );
+
+    env.ledger().set_timestamp(1_000 + 4 * day + 100);
+    assert_eq!(client.fund_shortfall(&issuer, &token, &1, &1_000), 1);
+    assert_eq!(client.get_period_funding(&token, &1).shortfall, 0);
+    assert_eq!(client.get_shortfall_interest_paid(&token, &1), 7);
+    assert_eq!(
+        client.try_fund_shortfall(&issuer, &token, &1, &1),
+        Err(Ok(RevoraError::InvalidPeriodState))
+    );
+    assert_eq!(client.claim_topup(&holder, &token, &1), 500);
+    assert_eq!(balance(&env, &payment_token, &holder), 5_003);
+}
+
+#[test]
+fn topup_pays_at_claimed_share_and_authorizes_period() {
+    use soroban_sdk::testutils::AuthorizedFunction;
+    use soroban_sdk::{IntoVal, Symbol};
+
+    let (env, client, issuer, token, payment_token, _contract_id) = claim_setup();
+    let holder = Address::generate(&env);
+    client.set_holder_share(&issuer, &token, &holder, &5_000);
+    client.report_revenue(&issuer, &token, &10_000, &1, &false);
+    client.deposit_revenue(&issuer, &token, &payment_token, &8_000, &1);
+    client.finalize_report_short(&issuer, &token, &1);
+    assert_eq!(client.claim(&holder, &token, &0), 4_000);
+
+    // A later share increase does not reach back into the claimed period
+    client.set_holder_share(&issuer, &token, &holder, &10_000);
+    client.fund_shortfall(&issuer, &token, &1, &2_000);
+    assert_eq!(client.claim_topup(&holder, &token, &1), 1_000);
+    let (signer, invocation) = env.auths()[0].clone();
+    assert_eq!(signer, holder);
+    assert_eq!(
+        invocation.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "claim_topup"),
+            (token, 1u64).into_val(&env),
+        ))
+    );
+}
@@ -0,0 +1,224 @@
+//! Helpers for contracts integrating with `RevoraRevenueShare` to write concise
+//! integration tests. Enabled with the `testutils` cargo feature.
+//!
+//! All helpers assume auths are mocked (`env.mock_all_auths()`, as done by `setup`).
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as _, Ledger as _},
+    token, Address, Bytes, Env, String, Vec,
+};
+
+use crate::{RevoraRevenueShare, RevoraRevenueShareClient};
+
+/// An offering registered by `register_test_offering`.
+#[derive(Clone, Debug)]
+pub struct TestOffering {
+    pub issuer: Address,
+    pub token: Address,
+}
+
+/// Create an env with mocked auths and a registered Revora contract.
+pub fn setup() -> (Env, RevoraRevenueShareClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RevoraRevenueShare);
+    let client = RevoraRevenueShareClient::new(&env, &contract_id);
+    (env, client)
+}
+
+/// Register an offering for a fresh issuer and token at `revenue_share_bps`.
+pub fn register_test_offering(
+    env: &Env,
+    client: &RevoraRevenueShareClient,
+    revenue_share_bps: u32,
+) -> TestOffering {
+    let issuer = Address::generate(env);
+    let token = Address::generate(env);
+    client.register_offering(&issuer, &token, &revenue_share_bps);
+    TestOffering { issuer, token }
+}
+
+/// Blacklist `count` fresh investors on an offering and return them.
+pub fn seed_blacklist(
+    env: &Env,
+    client: &RevoraRevenueShareClient,
+    offering: &TestOffering,
+    count: u32,
+) -> Vec<Address> {
+    let mut investors = Vec::new(env);
+    for _ in 0..count {
+        let investor = Address::generate(env);
+        client.blacklist_add(&offering.issuer, &offering.token, &investor);
+        investors.push_back(investor);
+    }
+    investors
+}
+
+/// Advance the ledger timestamp by `periods * period_secs` seconds.
+pub fn fast_forward_periods(env: &Env, period_secs: u64, periods: u64) {
+    let now = env.ledger().timestamp();
+    env.ledger()
+        .set_timestamp(now.saturating_add(period_secs.saturating_mul(periods)));
+}
+
+/// Create a Stellar asset contract to use as a payment token. Returns its address.
+pub fn create_payment_token(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+/// Deploy the native XLM Stellar Asset Contract and return its address. The native SAC
+/// has no admin, so it cannot be minted; balances must come from funded accounts.
+pub fn create_native_token(env: &Env) -> Address {
+    // XDR of `Asset::Native`
+    let native = Bytes::from_array(env, &[0, 0, 0, 0]);
+    env.deployer().with_stellar_asset(native).deploy()
+}
+
+/// Mint `amount` of `payment_token` to the issuer and deposit it as `period_id`'s revenue.
+pub fn deposit_mock_revenue(
+    env: &Env,
+    client: &RevoraRevenueShareClient,
+    offering: &TestOffering,
+    payment_token: &Address,
+    amount: i128,
+    period_id: u64,
+) {
+    token::StellarAssetClient::new(env, payment_token).mint(&offering.issuer, &amount);
+    client.deposit_revenue(
+        &offering.issuer,
+        &offering.token,
+        payment_token,
+        &amount,
+        &period_id,
+    );
+}
+
+/// Storage keys of `MockToken`.
+#[contracttype]
+#[derive(Clone)]
+pub enum MockTokenKey {
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+/// Minimal SEP-41 token for integration tests: unrestricted `mint`, balances and
+/// allowances in persistent storage, no expiry enforcement and no events.
+#[contract]
+pub struct MockToken;
+
+#[contractimpl]
+impl MockToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&MockTokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&MockTokenKey::Allowance(from, spender))
+            .unwrap_or(0)
+    }
+
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        _expiration_ledger: u32,
+    ) {
+        from.require_auth();
+        env.storage()
+            .persistent()
+            .set(&MockTokenKey::Allowance(from, spender), &amount);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&MockTokenKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::move_balance(&env, &from, &to, amount);
+    }
+
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::move_balance(&env, &from, &to, amount);
+    }
+
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        Self::debit(&env, &from, amount);
+    }
+
+    pub fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::debit(&env, &from, amount);
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Mock Token")
+    }
+
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "MOCK")
+    }
+}
+
+impl MockToken {
+    fn debit(env: &Env, from: &Address, amount: i128) {
+        if amount < 0 {
+            panic!("negative amount");
+        }
+        let balance = Self::balance(env.clone(), from.clone());
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage()
+            .persistent()
+            .set(&MockTokenKey::Balance(from.clone()), &(balance - amount));
+    }
+
+    fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+        Self::debit(env, from, amount);
+        Self::mint(env.clone(), to.clone(), amount);
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            panic!("insufficient allowance");
+        }
+        env.storage().persistent().set(
+            &MockTokenKey::Allowance(from.clone(), spender.clone()),
+            &(allowance - amount),
+        );
+    }
+}
+
+/// Register a `MockToken` and return its client.
+pub fn create_mock_token(env: &Env) -> MockTokenClient<'static> {
+    MockTokenClient::new(env, &env.register_contract(None, MockToken))
+}
+
+/// Mint `amount` of a `MockToken` to each of `recipients`.
+pub fn mint_mock_token(env: &Env, token: &Address, recipients: &[Address], amount: i128) {
+    let client = MockTokenClient::new(env, token);
+    for recipient in recipients {
+        client.mint(recipient, &amount);
+    }
+}